@@ -0,0 +1,356 @@
+///
+/// # src/analytics.rs
+/// Contains the analytics of a private leaderboard: for every member, the time between the two
+/// parts of each day, the median solve time, the longest streak and the days solved late.
+///
+/// The report can be rendered as a table, or exported as CSV or JSON to be shared.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use clap::ValueEnum;
+use serde_json::{json, Value};
+
+use std::{collections::BTreeMap, time::Duration};
+
+use crate::calendar::format_duration;
+use crate::leaderboard::{format_short, Leaderboard};
+use crate::output::document;
+
+// Variables  =========================================================================== Variables
+/// The formats a report can be rendered to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// A table, to read in the terminal
+    Text,
+    /// One line per member, the deltas in seconds
+    Csv,
+    /// The versioned document of the other commands, one object per member, the durations in
+    /// seconds
+    Json,
+}
+
+/// A solve is late when the second part is completed this long after the unlock.
+const LATE: Duration = Duration::from_secs(24 * 3600);
+
+/// The analytics of a member of a leaderboard.
+pub struct MemberReport {
+    /// The name of the member
+    pub name: String,
+    /// The local score of the member
+    pub local_score: u64,
+    /// The number of stars of the member
+    pub stars: u64,
+    /// The time between the first and the second part, for each day with both parts
+    pub deltas: BTreeMap<u8, Duration>,
+    /// The median time between the unlock and the second part
+    pub median: Option<Duration>,
+    /// The longest run of consecutive days with both stars
+    pub longest_streak: u8,
+    /// The days whose second part was completed more than 24h after the unlock
+    pub late_days: Vec<u8>,
+}
+
+// Functions  =========================================================================== Functions
+///
+/// # report
+/// Computes the analytics of every member of a leaderboard.
+///
+/// ## Arguments
+/// * `leaderboard` - The leaderboard
+/// * `days` - The number of unlocked days
+///
+/// ## Returns
+/// * `Vec<MemberReport>` - The analytics, in the order of the leaderboard
+pub fn report(leaderboard: &Leaderboard, days: u8) -> Vec<MemberReport> {
+    let year = leaderboard.year;
+
+    leaderboard
+        .members
+        .iter()
+        .map(|member| {
+            let solves = (1..=days)
+                .map(|day| {
+                    (
+                        member.solve_time(year, day, 1),
+                        member.solve_time(year, day, 2),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            let deltas = (1..=days)
+                .zip(&solves)
+                .filter_map(|(day, solve)| match solve {
+                    (Some(first), Some(second)) => Some((day, second.saturating_sub(*first))),
+                    _ => None,
+                })
+                .collect();
+
+            let mut times = solves
+                .iter()
+                .filter_map(|(_, second)| *second)
+                .collect::<Vec<_>>();
+            times.sort();
+
+            let (longest_streak, _) = solves.iter().fold((0, 0), |(longest, current), solve| {
+                let current = if solve.1.is_some() { current + 1 } else { 0 };
+                (longest.max(current), current)
+            });
+
+            MemberReport {
+                name: member.display_name(),
+                local_score: member.local_score,
+                stars: member.stars,
+                deltas,
+                median: median(&times),
+                longest_streak,
+                late_days: (1..=days)
+                    .zip(&solves)
+                    .filter(|(_, (_, second))| second.is_some_and(|time| time > LATE))
+                    .map(|(day, _)| day)
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+///
+/// # median
+/// Returns the median of sorted durations.
+///
+/// ## Arguments
+/// * `times` - The sorted durations
+///
+/// ## Returns
+/// * `Option<Duration>` - The median, `None` if there are no durations
+fn median(times: &[Duration]) -> Option<Duration> {
+    let middle = times.len() / 2;
+
+    match times.len() {
+        0 => None,
+        len if len % 2 == 0 => Some((times[middle - 1] + times[middle]) / 2),
+        _ => Some(times[middle]),
+    }
+}
+
+///
+/// # render
+/// Renders the analytics of a leaderboard.
+///
+/// ## Arguments
+/// * `reports` - The analytics of the members
+/// * `days` - The number of unlocked days
+/// * `format` - The format to render to
+///
+/// ## Returns
+/// * `String` - The rendered report
+pub fn render(reports: &[MemberReport], days: u8, format: ReportFormat) -> String {
+    match format {
+        ReportFormat::Text => render_text(reports, days),
+        ReportFormat::Csv => render_csv(reports, days),
+        ReportFormat::Json => {
+            let members = reports
+                .iter()
+                .enumerate()
+                .map(|(rank, report)| {
+                    json!({
+                        "rank": rank + 1,
+                        "name": report.name,
+                        "local_score": report.local_score,
+                        "stars": report.stars,
+                        "deltas": report
+                            .deltas
+                            .iter()
+                            .map(|(day, delta)| (day.to_string(), json!(delta.as_secs())))
+                            .collect::<serde_json::Map<_, _>>(),
+                        "median_seconds": report.median.map(|median| median.as_secs()),
+                        "longest_streak": report.longest_streak,
+                        "late_days": report.late_days,
+                    })
+                })
+                .collect::<Vec<Value>>();
+
+            document("leaderboard", json!({ "days": days, "members": members }))
+        }
+    }
+}
+
+///
+/// # render_text
+/// Renders the analytics as a table.
+///
+/// ## Arguments
+/// * `reports` - The analytics of the members
+/// * `days` - The number of unlocked days
+///
+/// ## Returns
+/// * `String` - The table
+fn render_text(reports: &[MemberReport], days: u8) -> String {
+    let rank_width = reports.len().to_string().len() + 1;
+    // Only the days someone has completed, to keep the lines short early in the event
+    let days = reports
+        .iter()
+        .filter_map(|report| report.deltas.keys().max())
+        .max()
+        .map_or(0, |day| (*day).min(days));
+
+    let mut table = String::from(
+        "Median solve time, longest streak of days with both stars, days solved more than 24h \
+        after the unlock\nand time between part 1 and part 2 of each day:\n",
+    );
+    table.push_str(&format!(
+        "{:>width$} {:>12} {:>6} {:>4}",
+        "",
+        "Median",
+        "Streak",
+        "Late",
+        width = rank_width
+    ));
+    for day in 1..=days {
+        table.push_str(&format!(" {:>6}", day));
+    }
+    table.push_str("  Name\n");
+
+    for (rank, report) in reports.iter().enumerate() {
+        table.push_str(&format!(
+            "{:>width$} {:>12} {:>6} {:>4}",
+            format!("{})", rank + 1),
+            report.median.map_or("-".to_string(), format_duration),
+            report.longest_streak,
+            report.late_days.len(),
+            width = rank_width
+        ));
+
+        for day in 1..=days {
+            let cell = report
+                .deltas
+                .get(&day)
+                .map_or("-".to_string(), |delta| format_short(*delta));
+            table.push_str(&format!(" {:>6}", cell));
+        }
+
+        table.push_str(&format!("  {}\n", report.name));
+    }
+
+    table
+}
+
+///
+/// # render_csv
+/// Renders the analytics as CSV, one line per member.
+///
+/// ## Arguments
+/// * `reports` - The analytics of the members
+/// * `days` - The number of unlocked days
+///
+/// ## Returns
+/// * `String` - The CSV, with a header line
+fn render_csv(reports: &[MemberReport], days: u8) -> String {
+    let mut csv =
+        String::from("rank,name,local_score,stars,median_seconds,longest_streak,late_days");
+    for day in 1..=days {
+        csv.push_str(&format!(",day_{:02}_delta_seconds", day));
+    }
+    csv.push('\n');
+
+    for (rank, report) in reports.iter().enumerate() {
+        let late_days = report
+            .late_days
+            .iter()
+            .map(u8::to_string)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        csv.push_str(&format!(
+            "{},{},{},{},{},{},{}",
+            rank + 1,
+            csv_field(&report.name),
+            report.local_score,
+            report.stars,
+            report
+                .median
+                .map_or(String::new(), |median| median.as_secs().to_string()),
+            report.longest_streak,
+            late_days
+        ));
+        for day in 1..=days {
+            csv.push(',');
+            if let Some(delta) = report.deltas.get(&day) {
+                csv.push_str(&delta.as_secs().to_string());
+            }
+        }
+        csv.push('\n');
+    }
+
+    csv
+}
+
+///
+/// # csv_field
+/// Quotes a CSV field when it contains a separator, a quote or a line break.
+///
+/// ## Arguments
+/// * `field` - The field
+///
+/// ## Returns
+/// * `String` - The field, quoted if needed
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_report() {
+    let leaderboard = crate::leaderboard::parse(crate::leaderboard::FIXTURE).unwrap();
+    let reports = report(&leaderboard, 2);
+
+    // The anonymous user solved the day 1 the next day
+    assert_eq!(reports[0].deltas[&1], Duration::from_secs(86600));
+    assert_eq!(reports[0].late_days, [1]);
+    assert_eq!(reports[1].deltas[&1], Duration::from_secs(300));
+    assert_eq!(reports[1].median, Some(Duration::from_secs(600)));
+    assert_eq!(reports[1].longest_streak, 1);
+    assert!(reports[1].late_days.is_empty());
+    assert_eq!(reports[2].median, None);
+
+    assert_eq!(
+        render(&reports, 2, ReportFormat::Csv),
+        "rank,name,local_score,stars,median_seconds,longest_streak,late_days,\
+         day_01_delta_seconds,day_02_delta_seconds\n\
+         1,(anonymous user #2),10,3,86800,1,1,86600,\n\
+         2,Alice,10,3,600,1,,300,\n\
+         3,Carol,0,0,,0,,,\n"
+    );
+    assert_eq!(csv_field("a, \"b\""), "\"a, \"\"b\"\"\"");
+
+    let document = serde_json::from_str::<Value>(&render(&reports, 2, ReportFormat::Json)).unwrap();
+    assert_eq!(document["command"], "leaderboard");
+    assert_eq!(document["members"][1]["name"], "Alice");
+    assert_eq!(document["members"][1]["deltas"]["1"], 300);
+}
+
+#[test]
+fn test_median() {
+    let seconds = |values: &[u64]| {
+        values
+            .iter()
+            .map(|value| Duration::from_secs(*value))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(median(&[]), None);
+    assert_eq!(median(&seconds(&[1, 5, 9])), Some(Duration::from_secs(5)));
+    assert_eq!(
+        median(&seconds(&[1, 5, 9, 11])),
+        Some(Duration::from_secs(7))
+    );
+}
+
+/*
+ * End of file src/analytics.rs
+ */
@@ -0,0 +1,261 @@
+///
+/// # src/auth.rs
+/// Contains the storage of the session cookie used to authenticate on the Advent of Code website.
+///
+/// The session is read from the `AOC_SESSION` environment variable, or from the credentials file
+/// written by `aoc auth login`, `~/.config/aoc/credentials.toml` (or under `$XDG_CONFIG_HOME`),
+/// which is only readable by its owner. Named profiles have their own session, the
+/// `AOC_SESSION` variable only applies to the default profile:
+/// ```toml
+/// session = "53616c746564..."
+///
+/// [profiles.partner]
+/// session = "5f5f5f5f5f5f..."
+/// ```
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use toml_edit::{value, DocumentMut, Item};
+
+use std::{
+    env,
+    fs::{read_to_string, remove_file, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::config::{get_string, get_table};
+use crate::file_utils::create_folder;
+use crate::profile::Profile;
+
+// Variables  =========================================================================== Variables
+/// The environment variable taking precedence over the credentials file.
+pub const SESSION_VARIABLE: &str = "AOC_SESSION";
+
+/// Where the session in use comes from.
+pub enum SessionSource {
+    /// The `AOC_SESSION` environment variable
+    Environment,
+    /// The credentials file
+    File(PathBuf),
+}
+
+// Functions  =========================================================================== Functions
+///
+/// # credentials_path
+/// Returns the path of the credentials file.
+///
+/// ## Returns
+/// * `Result<PathBuf, String>` - The path, an error if the home folder is unknown
+pub fn credentials_path() -> Result<PathBuf, String> {
+    let config_folder = match env::var_os("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join(".config"))
+            .ok_or("Failed to find the home folder, set the HOME environment variable.")?,
+    };
+
+    Ok(config_folder.join("aoc").join("credentials.toml"))
+}
+
+///
+/// # session
+/// Returns the session cookie of a profile, from the environment or the credentials file.
+///
+/// ## Arguments
+/// * `profile` - The profile of the session
+///
+/// ## Returns
+/// * `Result<(String, SessionSource), String>` - The session and where it comes from
+pub fn session(profile: &Profile) -> Result<(String, SessionSource), String> {
+    if let Some(session) = env::var(SESSION_VARIABLE)
+        .ok()
+        .filter(|s| profile.is_default() && !s.trim().is_empty())
+    {
+        return Ok((normalize_session(&session)?, SessionSource::Environment));
+    }
+
+    let path = credentials_path()?;
+    let session = read_credentials(&path).and_then(|doc| {
+        let table = if profile.is_default() {
+            doc.as_table()
+        } else {
+            get_table(doc.as_table(), "profiles")
+                .ok()??
+                .get(profile.name())?
+                .as_table_like()?
+        };
+
+        get_string(table, "session").ok().flatten()
+    });
+
+    match session {
+        Some(session) => Ok((session, SessionSource::File(path))),
+        None if profile.is_default() => Err(format!(
+            "No session found, run `aoc auth login` or set the {} environment variable.",
+            SESSION_VARIABLE
+        )),
+        None => Err(format!(
+            "No session found for the profile '{}', run `aoc auth login --profile {}`.",
+            profile, profile
+        )),
+    }
+}
+
+///
+/// # save_session
+/// Writes the session cookie of a profile to the credentials file.
+///
+/// ## Arguments
+/// * `profile` - The profile of the session
+/// * `session` - The session cookie
+///
+/// ## Returns
+/// * `Result<PathBuf, String>` - The path of the credentials file
+pub fn save_session(profile: &Profile, session: &str) -> Result<PathBuf, String> {
+    let path = credentials_path()?;
+    let mut doc = read_credentials(&path).unwrap_or_default();
+
+    if profile.is_default() {
+        doc["session"] = value(session);
+    } else {
+        doc["profiles"][profile.name()]["session"] = value(session);
+    }
+
+    write_credentials(&path, &doc)?;
+
+    Ok(path)
+}
+
+///
+/// # remove_session
+/// Removes the session cookie of a profile from the credentials file, which is deleted once it
+/// holds no session anymore.
+///
+/// ## Arguments
+/// * `profile` - The profile of the session
+///
+/// ## Returns
+/// * `Result<Option<PathBuf>, String>` - The path of the credentials file, `None` if there was
+///   no session to remove
+pub fn remove_session(profile: &Profile) -> Result<Option<PathBuf>, String> {
+    let path = credentials_path()?;
+    let Some(mut doc) = read_credentials(&path) else {
+        return Ok(None);
+    };
+
+    let removed = if profile.is_default() {
+        doc.remove("session")
+    } else {
+        doc.get_mut("profiles")
+            .and_then(Item::as_table_like_mut)
+            .and_then(|profiles| profiles.remove(profile.name()))
+    };
+
+    if removed.is_none() {
+        return Ok(None);
+    }
+
+    let empty_profiles = doc
+        .get("profiles")
+        .and_then(Item::as_table_like)
+        .is_none_or(|profiles| profiles.is_empty());
+
+    if doc.get("session").is_none() && empty_profiles {
+        remove_file(&path).map_err(|e| format!("Failed to delete {}: {}", path.display(), e))?;
+    } else {
+        write_credentials(&path, &doc)?;
+    }
+
+    Ok(Some(path))
+}
+
+///
+/// # read_credentials
+/// Reads the credentials file.
+///
+/// ## Arguments
+/// * `path` - The path of the credentials file
+///
+/// ## Returns
+/// * `Option<DocumentMut>` - The credentials, `None` if the file is missing or invalid
+fn read_credentials(path: &Path) -> Option<DocumentMut> {
+    read_to_string(path).ok()?.parse::<DocumentMut>().ok()
+}
+
+///
+/// # write_credentials
+/// Writes the credentials file, only readable by its owner.
+///
+/// ## Arguments
+/// * `path` - The path of the credentials file
+/// * `doc` - The credentials
+///
+/// ## Returns
+/// * `Result<(), String>` - An error if the file could not be written
+fn write_credentials(path: &Path, doc: &DocumentMut) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        create_folder(parent);
+    }
+
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = options
+        .open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+
+    // The mode is only applied on creation, an existing file is restricted as well
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))
+            .map_err(|e| format!("Failed to restrict {}: {}", path.display(), e))?;
+    }
+
+    file.write_all(doc.to_string().as_bytes())
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+///
+/// # normalize_session
+/// Cleans a pasted session cookie: surrounding spaces and a `session=` prefix are removed.
+///
+/// ## Arguments
+/// * `session` - The pasted session cookie
+///
+/// ## Returns
+/// * `Result<String, String>` - The session, an error if it is not hexadecimal
+pub fn normalize_session(session: &str) -> Result<String, String> {
+    let session = session.trim();
+    let session = session.strip_prefix("session=").unwrap_or(session).trim();
+
+    if session.is_empty() || !session.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(
+            "The session should be the hexadecimal value of the 'session' cookie of adventofcode.com."
+                .to_string(),
+        );
+    }
+
+    Ok(session.to_string())
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_normalize_session() {
+    assert_eq!(normalize_session(" 53616c74\n").unwrap(), "53616c74");
+    assert_eq!(normalize_session("session=53616c74").unwrap(), "53616c74");
+    assert!(normalize_session("").is_err());
+    assert!(normalize_session("not a session").is_err());
+}
+
+/*
+ * End of file src/auth.rs
+ */
@@ -0,0 +1,190 @@
+///
+/// # src/benchmarks.rs
+/// Contains the results of the latest `aoc bench` of every day of a year, stored in a
+/// `benchmarks.toml` file next to the answers file (`data/year_{year}/benchmarks.toml` by
+/// default), one table per profile and day:
+/// ```toml
+/// [default.day_01]
+/// part_1 = { median_ns = 1206411, peak_memory_bytes = 10694656 }
+/// part_2 = { median_ns = 1057293, peak_memory_bytes = 10801152 }
+/// ```
+///
+/// They are shown by `aoc readme` and `aoc site`, which do not run the solutions themselves.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use toml_edit::{value, DocumentMut, InlineTable};
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::layout::Layout;
+use crate::ledger::{day_key, day_table, load_next_to_answers, save};
+use crate::output::nanoseconds;
+use crate::profile::Profile;
+
+// Variables  =========================================================================== Variables
+/// The name of the benchmarks file, in the folder of the answers file.
+const BENCHMARKS_FILE: &str = "benchmarks.toml";
+
+/// The results of the benchmarks of a year.
+pub struct Benchmarks {
+    /// The content of the benchmarks file
+    doc: DocumentMut,
+    /// The path of the benchmarks file
+    path: PathBuf,
+}
+
+// Functions  =========================================================================== Functions
+impl Benchmarks {
+    ///
+    /// # load
+    /// Loads the benchmarks of a year, empty ones if the benchmarks file does not exist yet.
+    ///
+    /// ## Arguments
+    /// * `caller` - The folder from which the program was called
+    /// * `layout` - The layout of the project
+    /// * `year` - The year of the Advent of Code challenge
+    ///
+    /// ## Returns
+    /// * `Result<Benchmarks, String>` - The benchmarks
+    pub fn load(caller: &Path, layout: &Layout, year: u16) -> Result<Benchmarks, String> {
+        let (doc, path) = load_next_to_answers(caller, layout, year, BENCHMARKS_FILE)?;
+
+        Ok(Benchmarks { doc, path })
+    }
+
+    ///
+    /// # record
+    /// Records the result of the benchmark of a part, replacing the previous one.
+    ///
+    /// ## Arguments
+    /// * `profile` - The profile whose input was used
+    /// * `day` - The day of the Advent of Code challenge
+    /// * `part` - The part of the puzzle, 1 or 2
+    /// * `median` - The median duration of the runs
+    /// * `peak_memory` - The peak resident memory of the runs in bytes, `None` if unknown
+    ///
+    /// ## Returns
+    /// * `Result<(), String>` - An error if the benchmarks file holds something else for the day
+    pub fn record(
+        &mut self,
+        profile: &Profile,
+        day: u8,
+        part: u8,
+        median: Duration,
+        peak_memory: Option<u64>,
+    ) -> Result<(), String> {
+        let mut result = InlineTable::new();
+        result.insert("median_ns", (nanoseconds(median) as i64).into());
+        if let Some(peak_memory) = peak_memory {
+            result.insert("peak_memory_bytes", (peak_memory as i64).into());
+        }
+
+        day_table(&mut self.doc, &self.path, profile, day)?[&format!("part_{}", part)] =
+            value(result);
+
+        Ok(())
+    }
+
+    ///
+    /// # median
+    /// Returns the median duration of the latest benchmark of a part.
+    ///
+    /// ## Arguments
+    /// * `profile` - The profile whose input was used
+    /// * `day` - The day of the Advent of Code challenge
+    /// * `part` - The part of the puzzle, 1 or 2
+    ///
+    /// ## Returns
+    /// * `Option<Duration>` - The median duration, `None` if the part was never benchmarked
+    pub fn median(&self, profile: &Profile, day: u8, part: u8) -> Option<Duration> {
+        self.get(profile, day, part, "median_ns")
+            .map(Duration::from_nanos)
+    }
+
+    ///
+    /// # peak_memory
+    /// Returns the peak resident memory of the latest benchmark of a part.
+    ///
+    /// ## Arguments
+    /// * `profile` - The profile whose input was used
+    /// * `day` - The day of the Advent of Code challenge
+    /// * `part` - The part of the puzzle, 1 or 2
+    ///
+    /// ## Returns
+    /// * `Option<u64>` - The peak memory in bytes, `None` if unknown
+    pub fn peak_memory(&self, profile: &Profile, day: u8, part: u8) -> Option<u64> {
+        self.get(profile, day, part, "peak_memory_bytes")
+    }
+
+    ///
+    /// # save
+    /// Writes the benchmarks to the benchmarks file.
+    ///
+    /// ## Returns
+    /// * `Result<(), String>` - An error if the file could not be written
+    pub fn save(&self) -> Result<(), String> {
+        save(&self.doc, &self.path)
+    }
+
+    ///
+    /// # get
+    /// Returns a value of the benchmark of a part.
+    ///
+    /// ## Arguments
+    /// * `profile` - The profile whose input was used
+    /// * `day` - The day of the Advent of Code challenge
+    /// * `part` - The part of the puzzle, 1 or 2
+    /// * `key` - `median_ns` or `peak_memory_bytes`
+    ///
+    /// ## Returns
+    /// * `Option<u64>` - The value, `None` if it is not recorded
+    fn get(&self, profile: &Profile, day: u8, part: u8, key: &str) -> Option<u64> {
+        self.doc
+            .get(profile.name())?
+            .get(day_key(day))?
+            .get(format!("part_{}", part))?
+            .get(key)?
+            .as_integer()
+            .and_then(|value| u64::try_from(value).ok())
+    }
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_benchmarks() {
+    let mut benchmarks = Benchmarks {
+        doc: DocumentMut::new(),
+        path: PathBuf::new(),
+    };
+    let profile = Profile::default();
+
+    benchmarks
+        .record(&profile, 1, 1, Duration::from_millis(3), Some(4096))
+        .unwrap();
+    benchmarks
+        .record(&profile, 1, 2, Duration::from_micros(20), None)
+        .unwrap();
+
+    assert_eq!(
+        benchmarks.median(&profile, 1, 1),
+        Some(Duration::from_millis(3))
+    );
+    assert_eq!(benchmarks.median(&profile, 2, 1), None);
+    assert_eq!(benchmarks.peak_memory(&profile, 1, 1), Some(4096));
+    assert_eq!(benchmarks.peak_memory(&profile, 1, 2), None);
+    assert_eq!(
+        benchmarks.doc.to_string(),
+        "[default.day_01]\npart_1 = { median_ns = 3000000, peak_memory_bytes = 4096 }\n\
+         part_2 = { median_ns = 20000 }\n"
+    );
+}
+
+/*
+ * End of file src/benchmarks.rs
+ */
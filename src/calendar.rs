@@ -0,0 +1,209 @@
+///
+/// # src/calendar.rs
+/// Contains the Advent of Code puzzle calendar: which years exist, how many days each event has
+/// and when every puzzle unlocks.
+///
+/// Puzzles unlock at midnight EST (UTC-5), every day of December from the 1st onwards.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
+
+use std::time::Duration;
+
+// Variables  =========================================================================== Variables
+/// The first year of the Advent of Code.
+pub const FIRST_YEAR: u16 = 2015;
+
+/// Offset of the puzzle unlock timezone (EST, UTC-5) in seconds.
+const UNLOCK_OFFSET_SECONDS: i32 = 5 * 3600;
+
+// Functions  =========================================================================== Functions
+///
+/// # unlock_timezone
+/// Returns the timezone in which the puzzles unlock (UTC-5).
+///
+/// ## Returns
+/// * `FixedOffset` - The unlock timezone
+pub fn unlock_timezone() -> FixedOffset {
+    FixedOffset::west_opt(UNLOCK_OFFSET_SECONDS).expect("UTC-5 is a valid offset")
+}
+
+///
+/// # now
+/// Returns the current time in the unlock timezone.
+///
+/// ## Returns
+/// * `DateTime<FixedOffset>` - The current time
+pub fn now() -> DateTime<FixedOffset> {
+    Utc::now().with_timezone(&unlock_timezone())
+}
+
+///
+/// # days_in_year
+/// Returns the number of puzzles of the given year.
+/// Since 2025 the event only lasts 12 days.
+///
+/// ## Arguments
+/// * `year` - The year of the Advent of Code challenge
+///
+/// ## Returns
+/// * `u8` - The number of days of the event
+pub fn days_in_year(year: u16) -> u8 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
+///
+/// # unlock_time
+/// Returns the moment the puzzle of the given day and year unlocks.
+///
+/// ## Arguments
+/// * `year` - The year of the Advent of Code challenge
+/// * `day` - The day of the Advent of Code challenge
+///
+/// ## Returns
+/// * `DateTime<FixedOffset>` - The unlock time
+pub fn unlock_time(year: u16, day: u8) -> DateTime<FixedOffset> {
+    unlock_timezone()
+        .with_ymd_and_hms(i32::from(year), 12, u32::from(day), 0, 0, 0)
+        .single()
+        .expect("December days always exist")
+}
+
+///
+/// # latest_event_year
+/// Returns the year of the most recent event that has started.
+///
+/// ## Arguments
+/// * `now` - The current time
+///
+/// ## Returns
+/// * `u16` - The year of the latest event
+pub fn latest_event_year(now: &DateTime<FixedOffset>) -> u16 {
+    let year = now.year() as u16;
+
+    if now.month() == 12 {
+        year
+    } else {
+        year - 1
+    }
+}
+
+///
+/// # unlocked_days
+/// Returns the number of puzzles of the given year that are unlocked at the given time.
+///
+/// ## Arguments
+/// * `year` - The year of the Advent of Code challenge
+/// * `now` - The current time
+///
+/// ## Returns
+/// * `u8` - The number of unlocked days, 0 if the event has not started
+pub fn unlocked_days(year: u16, now: &DateTime<FixedOffset>) -> u8 {
+    (1..=days_in_year(year))
+        .take_while(|day| unlock_time(year, *day) <= *now)
+        .count() as u8
+}
+
+///
+/// # next_unlock
+/// Returns the next puzzle to unlock after the given time.
+///
+/// ## Arguments
+/// * `now` - The current time
+///
+/// ## Returns
+/// * `(u16, u8)` - The year and day of the next puzzle
+pub fn next_unlock(now: &DateTime<FixedOffset>) -> (u16, u8) {
+    let year = now.year() as u16;
+
+    (1..=days_in_year(year))
+        .find(|day| unlock_time(year, *day) > *now)
+        .map_or((year + 1, 1), |day| (year, day))
+}
+
+///
+/// # format_duration
+/// Formats a duration to the second, e.g. a countdown or the time taken to solve a puzzle.
+///
+/// ## Arguments
+/// * `duration` - The duration
+///
+/// ## Returns
+/// * `String` - The duration, e.g. `2d 03:12:45` or `00:00:09`
+pub fn format_duration(duration: Duration) -> String {
+    // Rounded up, so that a countdown shows 00:00:00 when the puzzle unlocks
+    let seconds = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+
+    match seconds / 86400 {
+        0 => time,
+        days => format!("{}d {}", days, time),
+    }
+}
+
+// Tests ==================================================================================== Tests
+#[cfg(test)]
+pub fn at(year: i32, month: u32, day: u32, hour: u32) -> DateTime<FixedOffset> {
+    unlock_timezone()
+        .with_ymd_and_hms(year, month, day, hour, 0, 0)
+        .unwrap()
+}
+
+#[test]
+fn test_days_in_year() {
+    assert_eq!(days_in_year(2015), 25);
+    assert_eq!(days_in_year(2024), 25);
+    assert_eq!(days_in_year(2025), 12);
+}
+
+#[test]
+fn test_latest_event_year() {
+    assert_eq!(latest_event_year(&at(2023, 11, 30, 23)), 2022);
+    assert_eq!(latest_event_year(&at(2023, 12, 1, 0)), 2023);
+}
+
+#[test]
+fn test_unlocked_days() {
+    assert_eq!(unlocked_days(2023, &at(2023, 11, 30, 23)), 0);
+    assert_eq!(unlocked_days(2023, &at(2023, 12, 1, 0)), 1);
+    assert_eq!(unlocked_days(2023, &at(2023, 12, 10, 12)), 10);
+    assert_eq!(unlocked_days(2016, &at(2023, 12, 10, 12)), 25);
+    assert_eq!(unlocked_days(2025, &at(2026, 1, 3, 0)), 12);
+}
+
+#[test]
+fn test_next_unlock() {
+    assert_eq!(next_unlock(&at(2023, 6, 1, 12)), (2023, 1));
+    assert_eq!(next_unlock(&at(2023, 12, 1, 0)), (2023, 2));
+    assert_eq!(next_unlock(&at(2023, 12, 24, 23)), (2023, 25));
+    assert_eq!(next_unlock(&at(2025, 12, 12, 1)), (2026, 1));
+}
+
+#[test]
+fn test_format_duration() {
+    assert_eq!(format_duration(Duration::from_millis(8_200)), "00:00:09");
+    assert_eq!(
+        format_duration(Duration::from_secs(3 * 3600 + 12 * 60 + 45)),
+        "03:12:45"
+    );
+    assert_eq!(
+        format_duration(Duration::from_secs(2 * 86400 + 61)),
+        "2d 00:01:01"
+    );
+}
+
+/*
+ * End of file src/calendar.rs
+ */
@@ -0,0 +1,242 @@
+///
+/// # src/config.rs
+/// Contains the configuration of a project, read from the optional `aoc.toml` file at its root.
+///
+/// ```toml
+/// # Name used for the {{author}} template placeholder, defaults to `git config user.name`
+/// author = "Tom Planche"
+///
+/// # Locations of the files of every day, see src/layout.rs
+/// [layout]
+/// solution = "src/bin/year_{year}/day_{day:02}.rs"
+/// input = "data/year_{year}/inputs/day_{day:02}.txt"
+/// puzzle = "data/year_{year}/puzzles/day_{day:02}.md"
+/// examples = "data/year_{year}/examples/day_{day:02}"
+/// readme = "data/year_{year}/README.md"
+/// answers = "data/year_{year}/answers.toml"
+///
+/// # Requests sent to the Advent of Code website, see src/http.rs
+/// [http]
+/// contact = "me@example.com"
+/// delay_ms = 1000
+///
+/// # Limits of the runs of the solutions, see src/runner.rs
+/// [run]
+/// timeout_ms = 60000
+/// memory_mb = 2048
+/// ```
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use toml_edit::{DocumentMut, TableLike};
+
+use std::{fs::read_to_string, path::Path, time::Duration};
+
+use crate::http::HttpConfig;
+use crate::layout::Layout;
+use crate::runner::Limits;
+
+// Variables  =========================================================================== Variables
+/// The name of the configuration file, at the root of the project.
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+/// The configuration of a project.
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+    /// The name of the author of the solutions
+    pub author: Option<String>,
+    /// The locations of the files of every day
+    pub layout: Layout,
+    /// The configuration of the requests to the Advent of Code website
+    pub http: HttpConfig,
+    /// The limits of the runs of the solutions
+    pub run: Limits,
+}
+
+// Functions  =========================================================================== Functions
+impl Config {
+    ///
+    /// # load
+    /// Loads the configuration of the project, the default one if there is no `aoc.toml` file.
+    ///
+    /// ## Arguments
+    /// * `caller` - The folder from which the program was called
+    ///
+    /// ## Returns
+    /// * `Result<Config, String>` - The configuration
+    pub fn load(caller: &Path) -> Result<Config, String> {
+        let path = caller.join(CONFIG_FILE);
+        if !path.exists() {
+            return Ok(Config::default());
+        }
+
+        let content =
+            read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", CONFIG_FILE, e))?;
+
+        Config::parse(&content).map_err(|e| format!("Invalid {}: {}", CONFIG_FILE, e))
+    }
+
+    ///
+    /// # parse
+    /// Parses the content of a configuration file.
+    ///
+    /// ## Arguments
+    /// * `content` - The content of the configuration file
+    ///
+    /// ## Returns
+    /// * `Result<Config, String>` - The configuration
+    pub fn parse(content: &str) -> Result<Config, String> {
+        let doc = content.parse::<DocumentMut>().map_err(|e| e.to_string())?;
+
+        let mut config = Config {
+            author: get_string(doc.as_table(), "author")?,
+            ..Config::default()
+        };
+
+        if let Some(layout) = get_table(doc.as_table(), "layout")? {
+            for (key, _) in layout.iter() {
+                let pattern = get_string(layout, key)?.unwrap_or_default();
+
+                match key {
+                    "solution" => config.layout.solution = pattern,
+                    "input" => config.layout.input = pattern,
+                    "puzzle" => config.layout.puzzle = pattern,
+                    "examples" => config.layout.examples = pattern,
+                    "readme" => config.layout.readme = pattern,
+                    "answers" => config.layout.answers = pattern,
+                    _ => return Err(format!("unknown key 'layout.{}'", key)),
+                }
+            }
+
+            config.layout.validate()?;
+        }
+
+        if let Some(http) = get_table(doc.as_table(), "http")? {
+            for (key, item) in http.iter() {
+                match key {
+                    "contact" => config.http.contact = get_string(http, key)?,
+                    "user_agent" => config.http.user_agent = get_string(http, key)?,
+                    "base_url" => config.http.base_url = get_string(http, key)?,
+                    "delay_ms" => {
+                        let delay = item
+                            .as_integer()
+                            .filter(|delay| *delay >= 0)
+                            .ok_or("'delay_ms' should be a positive integer")?;
+
+                        config.http.delay = Duration::from_millis(delay as u64);
+                    }
+                    _ => return Err(format!("unknown key 'http.{}'", key)),
+                }
+            }
+        }
+
+        if let Some(run) = get_table(doc.as_table(), "run")? {
+            for (key, item) in run.iter() {
+                let limit = item
+                    .as_integer()
+                    .filter(|limit| *limit >= 0)
+                    .ok_or_else(|| format!("'{}' should be a positive integer", key))?
+                    as u64;
+
+                // 0 disables a limit
+                match key {
+                    "timeout_ms" => {
+                        config.run.timeout =
+                            Some(Duration::from_millis(limit)).filter(|_| limit > 0)
+                    }
+                    "memory_mb" => {
+                        let bytes = limit
+                            .checked_mul(1024 * 1024)
+                            .ok_or_else(|| format!("'{}' is too large", key))?;
+                        config.run.memory = Some(bytes).filter(|_| limit > 0)
+                    }
+                    _ => return Err(format!("unknown key 'run.{}'", key)),
+                }
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+///
+/// # get_string
+/// Returns the string value of the given key of a table.
+///
+/// ## Arguments
+/// * `table` - The table to read
+/// * `key` - The key of the value
+///
+/// ## Returns
+/// * `Result<Option<String>, String>` - The value, `None` if the key is missing
+pub fn get_string(table: &dyn TableLike, key: &str) -> Result<Option<String>, String> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(item) => item
+            .as_str()
+            .map(|value| Some(value.to_string()))
+            .ok_or_else(|| format!("'{}' should be a string", key)),
+    }
+}
+
+///
+/// # get_table
+/// Returns the sub-table of the given key of a table.
+///
+/// ## Arguments
+/// * `table` - The table to read
+/// * `key` - The key of the sub-table
+///
+/// ## Returns
+/// * `Result<Option<&dyn TableLike>, String>` - The sub-table, `None` if the key is missing
+pub fn get_table<'a>(
+    table: &'a dyn TableLike,
+    key: &str,
+) -> Result<Option<&'a dyn TableLike>, String> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(item) => item
+            .as_table_like()
+            .map(Some)
+            .ok_or_else(|| format!("'{}' should be a table", key)),
+    }
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_parse_config() {
+    let config = Config::parse(
+        "author = \"Tom\"\n\
+        [layout]\n\
+        solution = \"src/{year}/day{day:02}.rs\"\n",
+    )
+    .unwrap();
+
+    assert_eq!(config.author.as_deref(), Some("Tom"));
+    assert_eq!(config.layout.solution, "src/{year}/day{day:02}.rs");
+    assert_eq!(config.layout.input, Layout::default().input);
+
+    assert!(Config::parse("").is_ok());
+    assert!(Config::parse("[layout]\nsolutions = \"src/{day}.rs\"").is_err());
+    assert!(Config::parse("[layout]\nsolution = 3").is_err());
+    assert!(Config::parse("[layout]\nsolution = \"src/{year}.rs\"").is_err());
+    assert!(Config::parse("layout = 3").is_err());
+
+    let config = Config::parse("[http]\ncontact = \"me@example.com\"\ndelay_ms = 250\n").unwrap();
+    assert_eq!(config.http.contact.as_deref(), Some("me@example.com"));
+    assert_eq!(config.http.delay, Duration::from_millis(250));
+    assert!(Config::parse("[http]\ndelay_ms = -1").is_err());
+
+    let config = Config::parse("[run]\ntimeout_ms = 0\nmemory_mb = 512\n").unwrap();
+    assert_eq!(config.run.timeout, None);
+    assert_eq!(config.run.memory, Some(512 * 1024 * 1024));
+    assert_eq!(Config::default().run.timeout, Some(Duration::from_secs(60)));
+    assert!(Config::parse("[run]\ntimeout = 5").is_err());
+    assert!(Config::parse("[run]\nmemory_mb = 9223372036854775807").is_err());
+}
+
+/*
+ * End of file src/config.rs
+ */
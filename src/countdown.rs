@@ -0,0 +1,85 @@
+///
+/// # src/countdown.rs
+/// Contains the wait for the unlock of a puzzle, used by `aoc create --wait`.
+///
+/// A live countdown is shown until midnight UTC-5, then a small random jitter is added, so that
+/// the requests of every user do not reach the website at the very same second.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use std::{
+    io::{stdout, Write},
+    thread::sleep,
+    time::{Duration, SystemTime},
+};
+
+use crate::calendar::{format_duration, now, unlock_time};
+
+// Variables  =========================================================================== Variables
+/// The minimum delay waited after the unlock.
+const MIN_JITTER: Duration = Duration::from_secs(1);
+
+/// The maximum delay added to the minimum one.
+const MAX_EXTRA_JITTER_MILLIS: u32 = 4000;
+
+// Functions  =========================================================================== Functions
+///
+/// # wait_for_unlock
+/// Shows a countdown until the puzzle of the given day unlocks, then waits for a small jitter.
+/// Returns immediately if the puzzle is already unlocked.
+///
+/// ## Arguments
+/// * `year` - The year of the Advent of Code challenge
+/// * `day` - The day of the Advent of Code challenge
+///
+/// ## Returns
+/// * `()` - Nothing
+pub fn wait_for_unlock(year: u16, day: u8) {
+    let unlock = unlock_time(year, day);
+
+    while let Ok(remaining) = (unlock - now()).to_std() {
+        if remaining.is_zero() {
+            break;
+        }
+
+        print!(
+            "\rDay {} of {} unlocks in {} ",
+            day,
+            year,
+            format_duration(remaining)
+        );
+        let _ = stdout().flush();
+
+        // Wakes up on the second boundaries, so that the countdown stays in step with the clock
+        let step = Duration::from_nanos(u64::from(remaining.subsec_nanos()));
+        sleep(if step.is_zero() {
+            Duration::from_secs(1)
+        } else {
+            step
+        });
+    }
+
+    println!("\rDay {} of {} is unlocked!{}", day, year, " ".repeat(16));
+    sleep(jitter());
+}
+
+///
+/// # jitter
+/// Returns a random delay to wait after the unlock.
+///
+/// ## Returns
+/// * `Duration` - The delay, between 1 and 5 seconds
+fn jitter() -> Duration {
+    let nanos = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos())
+        .unwrap_or_default();
+
+    MIN_JITTER + Duration::from_millis(u64::from(nanos % MAX_EXTRA_JITTER_MILLIS))
+}
+
+/*
+ * End of file src/countdown.rs
+ */
@@ -0,0 +1,208 @@
+///
+/// # src/doctor.rs
+/// Contains the checks of `aoc doctor`: the toolchain, the configuration, the structure of the
+/// project and the session, to find what keeps the other commands from working.
+///
+/// The checks never send requests to the website, `aoc auth status` checks the session against it.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use serde_json::{json, Value};
+
+use std::{fmt, path::Path, process::Command};
+
+use crate::auth::{session, SessionSource, SESSION_VARIABLE};
+use crate::config::{Config, CONFIG_FILE};
+use crate::profile::Profile;
+
+// Variables  =========================================================================== Variables
+/// The result of a check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// Everything is fine
+    Ok,
+    /// Some commands will not work as expected
+    Warning,
+    /// Some commands will not work at all
+    Error,
+}
+
+/// A check and its result.
+pub struct Check {
+    /// What is checked
+    pub name: &'static str,
+    /// The result of the check
+    pub status: Status,
+    /// What was found, or how to fix it
+    pub detail: String,
+}
+
+// Functions  =========================================================================== Functions
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Ok => f.pad("ok"),
+            Status::Warning => f.pad("warning"),
+            Status::Error => f.pad("error"),
+        }
+    }
+}
+
+impl Check {
+    ///
+    /// # to_json
+    /// Returns the JSON object of the check, see src/output.rs.
+    ///
+    /// ## Returns
+    /// * `Value` - The object
+    pub fn to_json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "status": self.status.to_string(),
+            "detail": self.detail,
+        })
+    }
+}
+
+///
+/// # check
+/// Runs every check.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `profile` - The profile whose session is checked
+///
+/// ## Returns
+/// * `Vec<Check>` - The checks, in the order they were run
+pub fn check(caller: &Path, profile: &Profile) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    checks.push(match Command::new("cargo").arg("--version").output() {
+        Ok(output) if output.status.success() => Check {
+            name: "cargo",
+            status: Status::Ok,
+            detail: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        },
+        _ => Check {
+            name: "cargo",
+            status: Status::Error,
+            detail: "cargo was not found, the solutions cannot be compiled".to_string(),
+        },
+    });
+
+    let config = Config::load(caller);
+    checks.push(match (&config, caller.join(CONFIG_FILE).exists()) {
+        (Ok(_), true) => Check {
+            name: "config",
+            status: Status::Ok,
+            detail: format!("{} is valid", CONFIG_FILE),
+        },
+        (Ok(_), false) => Check {
+            name: "config",
+            status: Status::Ok,
+            detail: format!("no {}, the defaults are used", CONFIG_FILE),
+        },
+        (Err(e), _) => Check {
+            name: "config",
+            status: Status::Error,
+            detail: e.clone(),
+        },
+    });
+
+    let missing = ["src", "Cargo.toml"]
+        .into_iter()
+        .filter(|path| !caller.join(path).exists())
+        .collect::<Vec<_>>();
+    checks.push(Check {
+        name: "project",
+        status: if missing.is_empty() {
+            Status::Ok
+        } else {
+            Status::Error
+        },
+        detail: if missing.is_empty() {
+            format!("{} is a cargo project", caller.display())
+        } else {
+            format!("missing {}, run `aoc init`", missing.join(" and "))
+        },
+    });
+
+    checks.push(match session(profile) {
+        Ok((_, SessionSource::Environment)) => Check {
+            name: "session",
+            status: Status::Ok,
+            detail: format!("read from {}", SESSION_VARIABLE),
+        },
+        Ok((_, SessionSource::File(path))) => Check {
+            name: "session",
+            status: Status::Ok,
+            detail: format!("read from {}", path.display()),
+        },
+        Err(e) => Check {
+            name: "session",
+            status: Status::Warning,
+            detail: e,
+        },
+    });
+
+    let user_agent = config
+        .map(|config| config.http.user_agent())
+        .unwrap_or_default();
+    checks.push(match user_agent {
+        Some(user_agent) => Check {
+            name: "contact",
+            status: Status::Ok,
+            detail: format!("requests are sent as {}", user_agent),
+        },
+        None => Check {
+            name: "contact",
+            status: Status::Warning,
+            detail: "no contact information in the User-Agent, set `contact` in the [http] table \
+                of aoc.toml or the AOC_CONTACT environment variable"
+                .to_string(),
+        },
+    });
+
+    checks
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_check() {
+    let caller = crate::file_utils::temp_folder("doctor");
+    let profile = "doctor-without-session".parse::<Profile>().unwrap();
+    let status = |checks: &[Check], name: &str| {
+        checks
+            .iter()
+            .find(|check| check.name == name)
+            .map(|check| (check.status, check.detail.clone()))
+            .unwrap()
+    };
+
+    let checks = check(&caller, &profile);
+    assert_eq!(
+        status(&checks, "config"),
+        (
+            Status::Ok,
+            format!("no {}, the defaults are used", CONFIG_FILE)
+        )
+    );
+    assert_eq!(
+        status(&checks, "project"),
+        (
+            Status::Error,
+            "missing src and Cargo.toml, run `aoc init`".to_string()
+        )
+    );
+    assert_eq!(status(&checks, "session").0, Status::Warning);
+    assert!(status(&checks, "session").1.starts_with("No session found"));
+
+    std::fs::write(caller.join(CONFIG_FILE), "[run]\ntimeout = 5\n").unwrap();
+    assert_eq!(status(&check(&caller, &profile), "config").0, Status::Error);
+}
+
+/*
+ * End of file src/doctor.rs
+ */
@@ -0,0 +1,364 @@
+///
+/// # src/download.rs
+/// Contains the download of the inputs and puzzle descriptions of the Advent of Code website.
+///
+/// An input never changes once downloaded, so it is never fetched again. A description gains
+/// its second part once the first one is solved, so it is revalidated through the HTTP cache.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use chrono::{DateTime, FixedOffset};
+
+use std::{
+    fs::{read_to_string, rename, write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::calendar::{now, unlock_time};
+use crate::config::Config;
+use crate::file_utils::create_folder;
+use crate::http::{Client, SESSION_EXPIRED};
+use crate::profile::Profile;
+use crate::puzzle::to_markdown;
+
+// Variables  =========================================================================== Variables
+/// What happened to a downloaded file.
+pub enum Download {
+    /// The file has been fetched and written
+    Fetched(PathBuf),
+    /// The file was already up to date, nothing has been written
+    Skipped(PathBuf),
+}
+
+/// What happened to the files of a bulk download.
+#[derive(Default)]
+pub struct Summary {
+    /// The number of fetched files
+    pub fetched: usize,
+    /// The number of files already downloaded
+    pub skipped: usize,
+    /// The errors of the failed downloads
+    pub failed: Vec<String>,
+}
+
+/// The header of a description converted by `to_markdown`, absent from the placeholders written by
+/// `aoc create` from the puzzle template, which start with `# Day N: `.
+const DESCRIPTION_HEADER: &str = "## --- Day ";
+
+// Functions  =========================================================================== Functions
+///
+/// # download_input
+/// Downloads the input of a day for a profile, unless it has already been downloaded.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `config` - The configuration of the project
+/// * `client` - The client authenticated with the session of the profile
+/// * `profile` - The profile the input belongs to
+/// * `year` - The year of the Advent of Code challenge
+/// * `day` - The day of the Advent of Code challenge
+///
+/// ## Returns
+/// * `Result<Download, String>` - What happened to the input file
+pub fn download_input(
+    caller: &Path,
+    config: &Config,
+    client: &Client,
+    profile: &Profile,
+    year: u16,
+    day: u8,
+) -> Result<Download, String> {
+    let input = profile.input(&config.layout, year, day);
+
+    // The input files created empty by `aoc create` are placeholders
+    if read_to_string(caller.join(&input)).is_ok_and(|content| !content.is_empty()) {
+        return Ok(Download::Skipped(input));
+    }
+
+    check_unlocked(year, day, &now())?;
+
+    let content = client.get(&format!("{}/day/{}/input", year, day))?;
+    write_file(caller, &input, &content)?;
+
+    Ok(Download::Fetched(input))
+}
+
+///
+/// # download_puzzle
+/// Downloads the description of a day, converted to markdown.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `config` - The configuration of the project
+/// * `client` - The client, authenticated to get the second part of the description
+/// * `year` - The year of the Advent of Code challenge
+/// * `day` - The day of the Advent of Code challenge
+///
+/// ## Returns
+/// * `Result<Download, String>` - What happened to the puzzle file
+pub fn download_puzzle(
+    caller: &Path,
+    config: &Config,
+    client: &Client,
+    year: u16,
+    day: u8,
+) -> Result<Download, String> {
+    let puzzle = config.layout.puzzle(year, day);
+
+    check_unlocked(year, day, &now())?;
+
+    let page = client.get_cached(&format!("{}/day/{}", year, day), Duration::ZERO)?;
+    let markdown = to_markdown(&page).ok_or_else(|| {
+        format!(
+            "The page of the day {} of {} has no description.",
+            day, year
+        )
+    })?;
+
+    if read_to_string(caller.join(&puzzle)).is_ok_and(|content| content == markdown) {
+        return Ok(Download::Skipped(puzzle));
+    }
+
+    write_file(caller, &puzzle, &markdown)?;
+
+    Ok(Download::Fetched(puzzle))
+}
+
+///
+/// # download_missing
+/// Downloads the description and the input of a day, unless they are already present, so that
+/// an interrupted bulk download resumes where it stopped.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `config` - The configuration of the project
+/// * `client` - The client authenticated with the session of the profile
+/// * `profile` - The profile the input belongs to
+/// * `year` - The year of the Advent of Code challenge
+/// * `day` - The day of the Advent of Code challenge
+/// * `summary` - The summary the results are added to
+///
+/// ## Returns
+/// * `Result<(), String>` - An error if the session is rejected, which would fail every download
+pub fn download_missing(
+    caller: &Path,
+    config: &Config,
+    client: &Client,
+    profile: &Profile,
+    year: u16,
+    day: u8,
+    summary: &mut Summary,
+) -> Result<(), String> {
+    let puzzle = config.layout.puzzle(year, day);
+    let described = read_to_string(caller.join(&puzzle))
+        .is_ok_and(|content| content.starts_with(DESCRIPTION_HEADER));
+
+    let downloads = [
+        if described {
+            Ok(Download::Skipped(puzzle))
+        } else {
+            download_puzzle(caller, config, client, year, day)
+        },
+        download_input(caller, config, client, profile, year, day),
+    ];
+
+    for download in downloads {
+        match download {
+            Ok(Download::Fetched(path)) => {
+                println!("Downloaded {}", path.display());
+                summary.fetched += 1;
+            }
+            Ok(Download::Skipped(_)) => summary.skipped += 1,
+            Err(e) if e == SESSION_EXPIRED => return Err(e),
+            Err(e) => {
+                eprintln!("{}", e);
+                summary.failed.push(e);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+///
+/// # download_year
+/// Downloads the descriptions and the inputs of the unlocked days of a year that are missing,
+/// stopping at the first rejected session.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `config` - The configuration of the project
+/// * `client` - The client authenticated with the session of the profile
+/// * `profile` - The profile the inputs belong to
+/// * `year` - The year of the Advent of Code challenge
+/// * `unlocked` - The number of unlocked days of the year
+/// * `summary` - The summary the results are added to
+///
+/// ## Returns
+/// * `Result<(), String>` - An error if the session is rejected
+pub fn download_year(
+    caller: &Path,
+    config: &Config,
+    client: &Client,
+    profile: &Profile,
+    year: u16,
+    unlocked: u8,
+    summary: &mut Summary,
+) -> Result<(), String> {
+    (1..=unlocked)
+        .try_for_each(|day| download_missing(caller, config, client, profile, year, day, summary))
+}
+
+///
+/// # check_unlocked
+/// Checks that a puzzle is unlocked, to avoid requesting pages that do not exist yet.
+///
+/// ## Arguments
+/// * `year` - The year of the Advent of Code challenge
+/// * `day` - The day of the Advent of Code challenge
+/// * `now` - The current time
+///
+/// ## Returns
+/// * `Result<(), String>` - An error if the puzzle is still locked
+fn check_unlocked(year: u16, day: u8, now: &DateTime<FixedOffset>) -> Result<(), String> {
+    let unlock = unlock_time(year, day);
+
+    if *now < unlock {
+        return Err(format!(
+            "The day {} of {} unlocks at {}.",
+            day,
+            year,
+            unlock.format("%Y-%m-%d %H:%M %:z")
+        ));
+    }
+
+    Ok(())
+}
+
+///
+/// # write_file
+/// Writes a downloaded file, creating its folder.
+/// The content is written to a temporary file first, so that an interruption never leaves a
+/// truncated file that would be taken for a complete one.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `path` - The path of the file, relative to the project root
+/// * `content` - The content of the file
+///
+/// ## Returns
+/// * `Result<(), String>` - An error if the file could not be written
+fn write_file(caller: &Path, path: &Path, content: &str) -> Result<(), String> {
+    let path = caller.join(path);
+    if let Some(parent) = path.parent() {
+        create_folder(parent);
+    }
+
+    let partial = path.with_extension("part");
+    write(&partial, content)
+        .and_then(|_| rename(&partial, &path))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_check_unlocked() {
+    use crate::calendar::at;
+
+    assert_eq!(
+        check_unlocked(2023, 1, &at(2023, 11, 30, 23)),
+        Err("The day 1 of 2023 unlocks at 2023-12-01 00:00 -05:00.".to_string())
+    );
+    assert!(check_unlocked(2023, 1, &at(2023, 12, 1, 0)).is_ok());
+    assert!(check_unlocked(2023, 2, &at(2023, 12, 1, 23)).is_err());
+    assert!(check_unlocked(2015, 25, &at(2023, 12, 1, 0)).is_ok());
+}
+
+#[test]
+fn test_download_input() {
+    use crate::http::test_client;
+
+    let caller = crate::file_utils::temp_folder("download");
+    let config = Config::default();
+    let profile = Profile::default();
+    // Nothing listens on the discard port, so any request fails
+    let client = test_client("http://127.0.0.1:9/", "abc123");
+
+    let input = profile.input(&config.layout, 2023, 1);
+    write_file(&caller, &input, "1\n2\n").unwrap();
+    assert!(matches!(
+        download_input(&caller, &config, &client, &profile, 2023, 1),
+        Ok(Download::Skipped(path)) if path == input
+    ));
+
+    // An empty input is a placeholder of `aoc create`, which is downloaded
+    write_file(&caller, &input, "").unwrap();
+    assert!(download_input(&caller, &config, &client, &profile, 2023, 1)
+        .is_err_and(|e| e.starts_with("Failed to reach")));
+    assert_eq!(read_to_string(caller.join(&input)).unwrap(), "");
+}
+
+#[test]
+fn test_description_header() {
+    use crate::template::{render, TemplateName, TemplateVariables};
+
+    let placeholder = render(
+        TemplateName::Puzzle.builtin(),
+        &TemplateVariables {
+            day: 1,
+            year: 2023,
+            title: "Day 1".to_string(),
+            input_path: String::new(),
+            author: String::new(),
+            tests: String::new(),
+        },
+    );
+
+    assert!(!placeholder.starts_with(DESCRIPTION_HEADER));
+}
+
+#[test]
+fn test_download_year() {
+    let fixtures = crate::mock::fixtures("download");
+    // The day 2 has a description but no input
+    std::fs::create_dir_all(fixtures.join("2023/2")).unwrap();
+    let url = crate::mock::start(fixtures);
+
+    let caller = crate::file_utils::temp_folder("download-year");
+    let config = Config::default();
+    let profile = Profile::default();
+    let client = crate::http::test_client(&url, "abc123");
+
+    let mut summary = Summary::default();
+    download_year(&caller, &config, &client, &profile, 2023, 2, &mut summary).unwrap();
+    assert_eq!(
+        (summary.fetched, summary.skipped, summary.failed.len()),
+        (3, 0, 1)
+    );
+    assert_eq!(
+        read_to_string(caller.join(profile.input(&config.layout, 2023, 1))).unwrap(),
+        "1\n2\n"
+    );
+
+    // Resumed: only the missing input is requested again
+    let mut summary = Summary::default();
+    download_year(&caller, &config, &client, &profile, 2023, 2, &mut summary).unwrap();
+    assert_eq!(
+        (summary.fetched, summary.skipped, summary.failed.len()),
+        (0, 3, 1)
+    );
+
+    let expired = crate::http::test_client(&url, "expired");
+    let mut summary = Summary::default();
+    assert_eq!(
+        download_year(&caller, &config, &expired, &profile, 2023, 2, &mut summary),
+        Err(SESSION_EXPIRED.to_string())
+    );
+}
+
+/*
+ * End of file src/download.rs
+ */
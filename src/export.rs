@@ -0,0 +1,590 @@
+///
+/// # src/export.rs
+/// Contains the export of a year of solutions to a self-contained Cargo project, which builds and
+/// runs without this tool.
+///
+/// Supported formats:
+/// - `standalone-crate`: `src/day_NN.rs` modules and a `src/main.rs` running the day given as
+///   argument (every day by default), inputs in `inputs/day_NN.txt`
+/// - `fspoettel`: the layout of fspoettel's advent-of-code-rust template, one binary per day in
+///   `src/bin/NN.rs`, inputs in `data/inputs/NN.txt`
+///
+/// Puzzle inputs and descriptions must not be shared, they are replaced by empty placeholders
+/// unless explicitly included.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use clap::ValueEnum;
+
+use std::{
+    fs::{copy, read_to_string, write},
+    path::{Component, Path, PathBuf},
+};
+
+use crate::calendar::days_in_year;
+use crate::config::Config;
+use crate::file_utils::create_folder;
+use crate::layout::relative_path;
+
+// Variables  =========================================================================== Variables
+/// The layouts a year can be exported to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// A crate with one module per day and a main running them
+    StandaloneCrate,
+    /// fspoettel's advent-of-code-rust layout, one binary per day
+    Fspoettel,
+}
+
+/// What to include in the exported project.
+pub struct ExportOptions {
+    /// Copies the puzzle inputs instead of empty placeholders
+    pub include_inputs: bool,
+    /// Copies the puzzle descriptions
+    pub include_puzzles: bool,
+    /// Exports into a folder that is not empty
+    pub force: bool,
+}
+
+/// The macros whose paths are rewritten in the exported solutions.
+const INCLUDE_MACROS: [&str; 2] = ["include_str!(\"", "include_bytes!(\""];
+
+// Functions  =========================================================================== Functions
+impl ExportFormat {
+    ///
+    /// # solution
+    /// Returns the path of the solution of a day in the exported project.
+    ///
+    /// ## Arguments
+    /// * `day` - The day of the solution
+    ///
+    /// ## Returns
+    /// * `PathBuf` - The path, relative to the exported project
+    fn solution(&self, day: u8) -> PathBuf {
+        match self {
+            ExportFormat::StandaloneCrate => PathBuf::from(format!("src/day_{:02}.rs", day)),
+            ExportFormat::Fspoettel => PathBuf::from(format!("src/bin/{:02}.rs", day)),
+        }
+    }
+
+    ///
+    /// # input
+    /// Returns the path of the input of a day in the exported project.
+    ///
+    /// ## Arguments
+    /// * `day` - The day of the input
+    ///
+    /// ## Returns
+    /// * `PathBuf` - The path, relative to the exported project
+    fn input(&self, day: u8) -> PathBuf {
+        match self {
+            ExportFormat::StandaloneCrate => PathBuf::from(format!("inputs/day_{:02}.txt", day)),
+            ExportFormat::Fspoettel => PathBuf::from(format!("data/inputs/{:02}.txt", day)),
+        }
+    }
+
+    ///
+    /// # puzzle
+    /// Returns the path of the puzzle description of a day in the exported project.
+    ///
+    /// ## Arguments
+    /// * `day` - The day of the puzzle
+    ///
+    /// ## Returns
+    /// * `PathBuf` - The path, relative to the exported project
+    fn puzzle(&self, day: u8) -> PathBuf {
+        match self {
+            ExportFormat::StandaloneCrate => PathBuf::from(format!("puzzles/day_{:02}.md", day)),
+            ExportFormat::Fspoettel => PathBuf::from(format!("data/puzzles/{:02}.md", day)),
+        }
+    }
+
+    ///
+    /// # inputs_folder
+    /// Returns the folder holding the inputs in the exported project.
+    ///
+    /// ## Returns
+    /// * `&str` - The folder, relative to the exported project
+    fn inputs_folder(&self) -> &'static str {
+        match self {
+            ExportFormat::StandaloneCrate => "inputs",
+            ExportFormat::Fspoettel => "data/inputs",
+        }
+    }
+}
+
+///
+/// # export
+/// Exports the solutions of a year to a self-contained Cargo project.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `config` - The configuration of the project
+/// * `year` - The year to export
+/// * `format` - The layout of the exported project
+/// * `out` - The folder of the exported project
+/// * `options` - What to include in the exported project
+///
+/// ## Returns
+/// * `Result<Vec<String>, String>` - The warnings raised by the export
+pub fn export(
+    caller: &Path,
+    config: &Config,
+    year: u16,
+    format: ExportFormat,
+    out: &Path,
+    options: &ExportOptions,
+) -> Result<Vec<String>, String> {
+    let layout = &config.layout;
+    check_out(caller, out, options.force)?;
+
+    let days = (1..=days_in_year(year))
+        .filter(|day| caller.join(layout.solution(year, *day)).exists())
+        .collect::<Vec<_>>();
+
+    if days.is_empty() {
+        return Err(format!(
+            "There is no solution to export for the year {}.",
+            year
+        ));
+    }
+
+    let mut warnings = Vec::new();
+    for day in &days {
+        let day = *day;
+        let solution = layout.solution(year, day);
+        let target = format.solution(day);
+        let code = read_to_string(caller.join(&solution))
+            .map_err(|e| format!("Failed to read {}: {}", solution.display(), e))?;
+
+        let mut code = rewrite_includes(
+            caller,
+            &solution,
+            &code,
+            &[(layout.input(year, day), format.input(day))],
+            &target,
+            out,
+        )?;
+
+        if code.contains("crate::") || code.contains("super::super") {
+            warnings.push(format!(
+                "{} uses other modules of the project, they are not exported",
+                solution.display()
+            ));
+        }
+
+        if format == ExportFormat::Fspoettel {
+            if code.contains("fn main(") {
+                warnings.push(format!(
+                    "{} already has a main function",
+                    solution.display()
+                ));
+            }
+
+            code.push_str(
+                "\nfn main() {\n    response_part_1();\n    println!();\n    response_part_2();\n}\n",
+            );
+        }
+
+        write_file(out, &target, &code)?;
+
+        // Inputs and puzzles, which must not be shared by default
+        let input = caller.join(layout.input(year, day));
+        if options.include_inputs && input.exists() {
+            copy_file(&input, out, &format.input(day))?;
+        } else if !out.join(format.input(day)).exists() {
+            write_file(out, &format.input(day), "")?;
+        }
+
+        let puzzle = caller.join(layout.puzzle(year, day));
+        if options.include_puzzles && puzzle.exists() {
+            copy_file(&puzzle, out, &format.puzzle(day))?;
+        }
+    }
+
+    write_file(
+        out,
+        Path::new("Cargo.toml"),
+        &format!(
+            "[package]\nname = \"advent-of-code-{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n",
+            year
+        ),
+    )?;
+    write_file(out, Path::new(".gitignore"), "/target\nCargo.lock\n")?;
+
+    let input_glob = match format {
+        ExportFormat::StandaloneCrate => "day_*.txt",
+        ExportFormat::Fspoettel => "*.txt",
+    };
+    write_file(
+        &out.join(format.inputs_folder()),
+        Path::new(".gitignore"),
+        &format!("# Puzzle inputs must not be shared\n{}\n", input_glob),
+    )?;
+
+    if format == ExportFormat::StandaloneCrate {
+        write_file(out, Path::new("src/main.rs"), &standalone_main(year, &days))?;
+    }
+    write_file(out, Path::new("README.md"), &readme(year, format))?;
+
+    Ok(warnings)
+}
+
+///
+/// # check_out
+/// Checks that a year can be exported to a folder: the folder must be outside of the project, and
+/// empty unless `force` is given.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `out` - The folder of the exported project
+/// * `force` - Whether a folder that is not empty is accepted
+///
+/// ## Returns
+/// * `Result<(), String>` - An error if the folder cannot be used
+fn check_out(caller: &Path, out: &Path, force: bool) -> Result<(), String> {
+    let project = caller
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve {}: {}", caller.display(), e))?;
+
+    if resolve(out)?.starts_with(&project) {
+        return Err(format!(
+            "{} is inside the project, export to a folder outside of it.",
+            out.display()
+        ));
+    }
+
+    let not_empty = out
+        .read_dir()
+        .is_ok_and(|mut entries| entries.next().is_some());
+    if not_empty && !force {
+        return Err(format!(
+            "{} is not empty, use --force to export into it anyway.",
+            out.display()
+        ));
+    }
+
+    Ok(())
+}
+
+///
+/// # resolve
+/// Returns the absolute path of a folder that may not exist yet, resolving its existing ancestor.
+///
+/// ## Arguments
+/// * `path` - The path to resolve
+///
+/// ## Returns
+/// * `Result<PathBuf, String>` - The absolute path
+fn resolve(path: &Path) -> Result<PathBuf, String> {
+    let path = std::path::absolute(path)
+        .map_err(|e| format!("Failed to resolve {}: {}", path.display(), e))?;
+    let path = normalize(&path);
+
+    let existing = path
+        .ancestors()
+        .find(|ancestor| ancestor.exists())
+        .unwrap_or(Path::new("/"));
+    let rest = path.strip_prefix(existing).unwrap_or(Path::new(""));
+
+    existing
+        .canonicalize()
+        .map(|existing| existing.join(rest))
+        .map_err(|e| format!("Failed to resolve {}: {}", existing.display(), e))
+}
+
+///
+/// # rewrite_includes
+/// Rewrites the paths of the `include_str!` and `include_bytes!` macros of an exported solution.
+/// The input of the day is mapped to its new location, every other included file (examples...)
+/// is copied to the same location in the exported project.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `solution` - The path of the solution, relative to the project root
+/// * `code` - The code of the solution
+/// * `mapped` - The files that have a new location: (project path, exported path)
+/// * `target` - The path of the exported solution, relative to the exported project
+/// * `out` - The folder of the exported project
+///
+/// ## Returns
+/// * `Result<String, String>` - The rewritten code
+pub fn rewrite_includes(
+    caller: &Path,
+    solution: &Path,
+    code: &str,
+    mapped: &[(PathBuf, PathBuf)],
+    target: &Path,
+    out: &Path,
+) -> Result<String, String> {
+    let solution_folder = solution.parent().unwrap_or(Path::new(""));
+    let target_folder = target.parent().unwrap_or(Path::new(""));
+
+    let mut rewritten = String::with_capacity(code.len());
+    let mut rest = code;
+
+    while let Some((start, macro_name)) = INCLUDE_MACROS
+        .iter()
+        .filter_map(|name| rest.find(name).map(|start| (start, *name)))
+        .min()
+    {
+        let path_start = start + macro_name.len();
+        let Some(path_len) = rest[path_start..].find('"') else {
+            break;
+        };
+
+        let included = &rest[path_start..path_start + path_len];
+        let project_path = normalize(&solution_folder.join(included));
+
+        let exported_path = match mapped.iter().find(|(from, _)| *from == project_path) {
+            Some((_, to)) => relative_path(target_folder, to),
+            None if Path::new(included).is_relative() && caller.join(&project_path).is_file() => {
+                copy_file(&caller.join(&project_path), out, &project_path)?;
+                relative_path(target_folder, &project_path)
+            }
+            // Left as is: absolute paths and files outside of the project
+            None => included.to_string(),
+        };
+
+        rewritten.push_str(&rest[..path_start]);
+        rewritten.push_str(&exported_path);
+        rest = &rest[path_start + path_len..];
+    }
+
+    rewritten.push_str(rest);
+    Ok(rewritten)
+}
+
+///
+/// # normalize
+/// Lexically removes the `.` and `..` components of a relative path.
+///
+/// ## Arguments
+/// * `path` - The path to normalize
+///
+/// ## Returns
+/// * `PathBuf` - The normalized path
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+
+    normalized
+}
+
+///
+/// # write_file
+/// Writes a file of the exported project, creating its folders.
+///
+/// ## Arguments
+/// * `out` - The folder of the exported project
+/// * `path` - The path of the file, relative to the exported project
+/// * `content` - The content of the file
+///
+/// ## Returns
+/// * `Result<(), String>` - The result of the operation
+pub fn write_file(out: &Path, path: &Path, content: &str) -> Result<(), String> {
+    let path = out.join(path);
+    if let Some(parent) = path.parent() {
+        create_folder(parent);
+    }
+
+    write(&path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+///
+/// # copy_file
+/// Copies a file into the exported project, creating its folders.
+///
+/// ## Arguments
+/// * `from` - The file to copy
+/// * `out` - The folder of the exported project
+/// * `path` - The path of the copy, relative to the exported project
+///
+/// ## Returns
+/// * `Result<(), String>` - The result of the operation
+pub fn copy_file(from: &Path, out: &Path, path: &Path) -> Result<(), String> {
+    let path = out.join(path);
+    if let Some(parent) = path.parent() {
+        create_folder(parent);
+    }
+
+    copy(from, &path)
+        .map(|_| ())
+        .map_err(|e| format!("Failed to copy {}: {}", from.display(), e))
+}
+
+///
+/// # standalone_main
+/// Returns the 'src/main.rs' file of a standalone crate.
+///
+/// ## Arguments
+/// * `year` - The exported year
+/// * `days` - The exported days
+///
+/// ## Returns
+/// * `String` - The content of the file
+fn standalone_main(year: u16, days: &[u8]) -> String {
+    let modules = days
+        .iter()
+        .map(|day| format!("mod day_{:02};\n", day))
+        .collect::<String>();
+    let entries = days
+        .iter()
+        .map(|day| {
+            format!(
+                "    ({}, day_{:02}::response_part_1, day_{:02}::response_part_2),\n",
+                day, day, day
+            )
+        })
+        .collect::<String>();
+
+    format!(
+        "//! Advent of Code {year}.\n\
+        //!\n\
+        //! `cargo run --release` runs every day, `cargo run --release -- <day>` a single one.\n\
+        {modules}\n\
+        const DAYS: [(u8, fn(), fn()); {count}] = [\n\
+        {entries}];\n\
+        \n\
+        fn main() {{\n    \
+            let selected = std::env::args().nth(1).and_then(|day| day.parse::<u8>().ok());\n\
+            \n    \
+            for (day, part_1, part_2) in DAYS {{\n        \
+                if selected.is_some_and(|selected| selected != day) {{\n            \
+                    continue;\n        \
+                }}\n\
+                \n        \
+                println!(\"Day {{}}\", day);\n        \
+                part_1();\n        \
+                part_2();\n    \
+            }}\n\
+        }}\n",
+        count = days.len()
+    )
+}
+
+///
+/// # readme
+/// Returns the README of the exported project.
+///
+/// ## Arguments
+/// * `year` - The exported year
+/// * `format` - The layout of the exported project
+///
+/// ## Returns
+/// * `String` - The content of the README
+fn readme(year: u16, format: ExportFormat) -> String {
+    let usage = match format {
+        ExportFormat::StandaloneCrate => {
+            "```bash\ncargo run --release        # every day\ncargo run --release -- 7   # a single day\n```"
+        }
+        ExportFormat::Fspoettel => "```bash\ncargo run --release --bin 07\n```",
+    };
+
+    format!(
+        "# Advent of Code {}\n\n\
+        Solutions of the [Advent of Code {}](https://adventofcode.com/{}).\n\n\
+        Puzzle inputs are not shared: put yours in the `{}` folder before running the solutions.\n\n\
+        {}\n",
+        year,
+        year,
+        year,
+        format.inputs_folder(),
+        usage
+    )
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_normalize() {
+    assert_eq!(
+        normalize(Path::new(
+            "src/bin/year_2016/../../../data/./inputs/day_01.txt"
+        )),
+        PathBuf::from("data/inputs/day_01.txt")
+    );
+}
+
+#[test]
+fn test_rewrite_includes() {
+    let code = "const INPUT: &str = include_str!(\"../../../data/year_2016/inputs/day_01.txt\");";
+    let rewritten = rewrite_includes(
+        Path::new("/nonexistent"),
+        Path::new("src/bin/year_2016/day_01.rs"),
+        code,
+        &[(
+            PathBuf::from("data/year_2016/inputs/day_01.txt"),
+            PathBuf::from("inputs/day_01.txt"),
+        )],
+        Path::new("src/day_01.rs"),
+        Path::new("/nonexistent/out"),
+    )
+    .unwrap();
+
+    assert_eq!(
+        rewritten,
+        "const INPUT: &str = include_str!(\"../inputs/day_01.txt\");"
+    );
+}
+
+#[test]
+fn test_export_out() {
+    let caller = crate::file_utils::temp_folder("export");
+    let config = Config::default();
+    let solution = caller.join(config.layout.solution(2016, 1));
+    create_folder(solution.parent().unwrap());
+    write(&solution, "fn main() {}\n").unwrap();
+
+    let options = ExportOptions {
+        include_inputs: false,
+        include_puzzles: false,
+        force: false,
+    };
+    let format = ExportFormat::StandaloneCrate;
+
+    // Inside of the project
+    assert!(export(&caller, &config, 2016, format, &caller, &options).is_err());
+    assert!(export(
+        &caller,
+        &config,
+        2016,
+        format,
+        &caller.join("out"),
+        &options
+    )
+    .is_err());
+
+    // Not empty, and the input already there is kept
+    let out = caller.with_extension("out");
+    let _ = std::fs::remove_dir_all(&out);
+    write_file(&out, &format.input(1), "real input").unwrap();
+    assert!(export(&caller, &config, 2016, format, &out, &options).is_err());
+
+    let options = ExportOptions {
+        force: true,
+        ..options
+    };
+    assert!(export(&caller, &config, 2016, format, &out, &options).is_ok());
+    assert_eq!(
+        read_to_string(out.join(format.input(1))).unwrap(),
+        "real input"
+    );
+    assert!(out.join("src/main.rs").exists());
+
+    std::fs::remove_dir_all(&out).unwrap();
+}
+
+/*
+ * End of file src/export.rs
+ */
@@ -28,36 +28,50 @@
 ///     │       └── day_25.txt
 ///     └── year_n/
 ///
+/// This is the default layout, the locations of the files of a day can be changed in the `[layout]`
+/// table of the `aoc.toml` file (see `src/config.rs`).
+///
+/// `aoc init --with-days` also creates every day stub, one example folder per day
+/// (`data/year_n/examples/day_01/`, ...), a `README.md` for the year and a `.gitignore` that keeps
+/// the puzzle inputs out of git.
+///
 /// /// Tom Planche <github.com/tomPlanche>
 // Imports  ==============================================================================  Imports
 use std::{
-    fs::{create_dir, write},
-    path::Path,
+    fs::{copy, create_dir_all, write},
+    path::{Path, PathBuf},
+    process::Command,
 };
+
+use crate::calendar::days_in_year;
+use crate::config::Config;
+use crate::http::AOC_URL;
+use crate::layout::{relative_path, Layout};
+use crate::template::{author, puzzle_title, render, render_day, TemplateName, TemplateVariables};
 // Variables  =========================================================================== Variables
-const AOC_URL: &str = "https://adventofcode.com/";
+/// What happened to a file when creating the files of a day.
+pub enum FileStatus {
+    /// The file didn't exist and has been created
+    Created,
+    /// The file already existed and has been left untouched
+    Skipped,
+    /// The file already existed and has been replaced, a copy was saved at the given path
+    Replaced(PathBuf),
+}
 
 // Functions  =========================================================================== Functions
+///
+/// # check_global_file_struct_integrity
+/// Checks if the project has the global file structure: a 'src' folder.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+///
+/// ## Returns
+/// * `bool` - If the file structure is valid or not
 pub fn check_global_file_struct_integrity(caller: &Path) -> bool {
     // Check if the 'src' folder exists
-    let src_folder = caller.join("src");
-    if !src_folder.exists() {
-        return false;
-    }
-
-    // Check if the 'src/bin' folder exists
-    let src_bin_folder = src_folder.join("bin");
-    if !src_bin_folder.exists() {
-        return false;
-    }
-
-    // Check if the 'data' folder exists
-    let data_folder = caller.join("data");
-    if !data_folder.exists() {
-        return false;
-    }
-
-    true
+    caller.join("src").exists()
 }
 
 ///
@@ -65,116 +79,187 @@ pub fn check_global_file_struct_integrity(caller: &Path) -> bool {
 /// Checks if the file structure is valid.
 ///
 /// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `layout` - The layout of the project
 /// * `year` - The year of the Advent of Code challenge
 ///
 /// ## Returns
 /// * `bool` - If the file structure is valid or not
-pub fn check_file_struct_integrity_year(caller: &Path, year: u16) -> bool {
-    // Check if the 'src/bin/year_n' folder exists
-    let src_year_folder = caller.join(format!("src/bin/year_{}", year));
-    if !src_year_folder.exists() {
-        return false;
-    }
-
-    // Check if the 'data/year_n' folder exists
-    let data_year_folder = caller.join(format!("data/year_{}", year));
-    if !data_year_folder.exists() {
-        return false;
-    }
-
-    // Check if the 'data/year_n/puzzles' folder exists
-    let data_year_puzzles_folder = data_year_folder.join("puzzles");
-    if !data_year_puzzles_folder.exists() {
-        return false;
-    }
-
-    // Check if the 'data/year_n/inputs' folder exists
-    let data_year_inputs_folder = data_year_folder.join("inputs");
-    if !data_year_inputs_folder.exists() {
-        return false;
-    }
-
-    true
+pub fn check_file_struct_integrity_year(caller: &Path, layout: &Layout, year: u16) -> bool {
+    // Check if the folders of the solutions, inputs and puzzles of the year exist
+    layout
+        .year_folders(year)
+        .iter()
+        .all(|folder| caller.join(folder).exists())
 }
 
 ///
 /// # create_files
 /// Creates the files needed for the Advent of Code challenges for the given day and year.
+/// Existing files are left untouched, unless `force` is set: the solution file is then replaced
+/// by a fresh one and the previous version is saved next to it with a `.bak` extension, numbered
+/// if an earlier backup exists.
 ///
 /// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `config` - The configuration of the project
 /// * `day` - The day of the Advent of Code challenge
 /// * `year` - The year of the Advent of Code challenge
+/// * `force` - If true, an existing solution file is replaced
 ///
 /// ## Returns
 /// * `()` - Nothing
-pub fn create_files(caller: &Path, day: u8, year: u16) {
+pub fn create_files(caller: &Path, config: &Config, day: u8, year: u16, force: bool) {
     // Check if the file structure is valid
     if !check_global_file_struct_integrity(caller)
-        || !check_file_struct_integrity_year(caller, year)
+        || !check_file_struct_integrity_year(caller, &config.layout, year)
     {
         println!("The file structure is not correct.\nPlease run `cargo aoc init` or `cargo aoc init --year desired_year` to create the folders and files needed for the Advent of Code challenges.");
         return;
     }
 
-    // Create the 'src/bin/year_n/day_n.rs' file
-    let src_year_day_file = caller.join(format!("src/bin/year_{}/day_{:02}.rs", year, day));
-    if !src_year_day_file.exists() {
-        std::fs::File::create(&src_year_day_file).expect("Failed to create file !");
+    let files = match create_day_files(caller, config, day, year, force) {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+
+    println!("Day {} of year {}:", day, year);
+    for (path, status) in files {
+        let path = path.strip_prefix(caller).unwrap_or(&path).display();
+
+        match status {
+            FileStatus::Created => println!("  created  {}", path),
+            FileStatus::Skipped => println!("  skipped  {} (already exists)", path),
+            FileStatus::Replaced(backup) => println!(
+                "  replaced {} (previous version saved to {})",
+                path,
+                backup.strip_prefix(caller).unwrap_or(&backup).display()
+            ),
+        }
     }
 
-    let content = format!(
-        "\
-        ///\n\
-        /// # day_{:02}.rs\n\
-        /// Code for the day {:02} of the Advent of Code challenge year {}\n\
-        ///\n\
-        \n\
-        // Imports  ==============================================================================  Imports\n\
-        \n\
-        // Variables  =========================================================================== Variables\n\
-        const INPUT: &str = include_str!(\"../../../data/year_{}/inputs/day_{:02}.txt\");\n\n\
-        // Functions  =========================================================================== Functions\n\
-        pub fn response_part_1() {{\n\
-            \tprintln!(\"Day {:02} - Part 1\");\n\
-        }}\n\
-        \n\
-        pub fn response_part_2() {{\n\
-            \tprintln!(\"Day {:02} - Part 2\");\n\
-        }}\n\
-        \n\
-        // Tests ==================================================================================== Tests\n\
-        \n\
-        \n
-        ",
-        day, day, year, year, day, day, day
-    );
+    let message = format!("Go to {AOC_URL}{}/day/{} to see the puzzle :).", year, day);
+    println!("{}", message);
+}
 
-    // Write the content to the file
-    write(&src_year_day_file, content).expect("Failed to write to file !");
-
-    // Prepare the 'data/year_n/puzzles/day_n.md' file
-    let data_year_puzzles_day_file =
-        caller.join(format!("data/year_{}/puzzles/day_{:02}.md", year, day));
-    // If the file doesn't exist
-    if !data_year_puzzles_day_file.exists() {
-        // Create it
-        std::fs::File::create(&data_year_puzzles_day_file).expect("Failed to create file !");
+///
+/// # create_day_files
+/// Creates the solution, puzzle and input files of the given day and year.
+/// The file structure of the year is expected to exist.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `config` - The configuration of the project
+/// * `day` - The day of the Advent of Code challenge
+/// * `year` - The year of the Advent of Code challenge
+/// * `force` - If true, an existing solution file is replaced after being backed up
+///
+/// ## Returns
+/// * `Result<Vec<(PathBuf, FileStatus)>, String>` - What happened to each file, an error if a
+///   template could not be read
+fn create_day_files(
+    caller: &Path,
+    config: &Config,
+    day: u8,
+    year: u16,
+    force: bool,
+) -> Result<Vec<(PathBuf, FileStatus)>, String> {
+    let layout = &config.layout;
+
+    let src_year_day_file = caller.join(layout.solution(year, day));
+    let data_year_puzzles_day_file = caller.join(layout.puzzle(year, day));
+    let data_year_inputs_day_file = caller.join(layout.input(year, day));
+    let variables = TemplateVariables {
+        day,
+        year,
+        title: puzzle_title(&data_year_puzzles_day_file, day),
+        input_path: relative_path(
+            layout.solution(year, day).parent().unwrap_or(Path::new("")),
+            &layout.input(year, day),
+        ),
+        author: config.author.clone().unwrap_or_else(author),
+        tests: String::new(),
+    };
+
+    // Render the templates first, so that nothing is touched if one cannot be read
+    let solution = render_day(caller, &variables)?;
+    let puzzle = render(&TemplateName::Puzzle.load(caller)?, &variables);
+
+    // Prepare the solution file
+    let src_year_day_status = if !src_year_day_file.exists() {
+        FileStatus::Created
+    } else if force {
+        // Keep a copy of the existing solution, never replacing a previous one
+        let backup = backup_path(&src_year_day_file);
+        copy(&src_year_day_file, &backup).expect("Failed to back up file !");
+
+        FileStatus::Replaced(backup)
+    } else {
+        FileStatus::Skipped
+    };
+
+    if !matches!(src_year_day_status, FileStatus::Skipped) {
+        // Write the content to the file
+        write(&src_year_day_file, solution).expect("Failed to write to file !");
     }
 
-    // Prepare the 'data/year_n/inputs/day_n.txt' file
-    let data_year_inputs_day_file =
-        caller.join(format!("data/year_{}/inputs/day_{:02}.txt", year, day));
-    // If the file doesn't exist
-    if !data_year_inputs_day_file.exists() {
-        // Create it
-        std::fs::File::create(&data_year_inputs_day_file).expect("Failed to create file !");
+    // Prepare the puzzle file
+    let data_year_puzzles_day_status =
+        create_file_from_template(&data_year_puzzles_day_file, &puzzle);
+
+    // Prepare the input file
+    let data_year_inputs_day_status = create_file_from_template(&data_year_inputs_day_file, "");
+
+    Ok(vec![
+        (src_year_day_file, src_year_day_status),
+        (data_year_puzzles_day_file, data_year_puzzles_day_status),
+        (data_year_inputs_day_file, data_year_inputs_day_status),
+    ])
+}
+
+///
+/// # backup_path
+/// Returns the first free backup path of a file: `day_01.rs.bak`, then `day_01.rs.bak.1`,
+/// `day_01.rs.bak.2`...
+///
+/// ## Arguments
+/// * `path` - The file to back up
+///
+/// ## Returns
+/// * `PathBuf` - The backup path, which does not exist yet
+pub fn backup_path(path: &Path) -> PathBuf {
+    let with_suffix = |suffix: String| {
+        let mut backup = path.as_os_str().to_owned();
+        backup.push(suffix);
+        PathBuf::from(backup)
+    };
+
+    std::iter::once(with_suffix(".bak".to_string()))
+        .chain((1..).map(|index| with_suffix(format!(".bak.{}", index))))
+        .find(|backup| !backup.exists())
+        .expect("Failed to find a free backup path !")
+}
+
+///
+/// # create_file_from_template
+/// Creates a file with the given rendered template, if it doesn't exist yet.
+///
+/// ## Arguments
+/// * `path` - The path of the file to create
+/// * `content` - The content of the file
+///
+/// ## Returns
+/// * `FileStatus` - Whether the file has been created or skipped
+fn create_file_from_template(path: &Path, content: &str) -> FileStatus {
+    if path.exists() {
+        return FileStatus::Skipped;
     }
 
-    let message = format!(
-        "Files for day {} of year {} created !\nGo to {AOC_URL}{}/day/{} to see the puzzle :).",
-        day, year, year, day
-    );
-    println!("{}", message);
+    write(path, content).expect("Failed to create file !");
+    FileStatus::Created
 }
 
 ///
@@ -190,8 +275,8 @@ pub fn create_files(caller: &Path, day: u8, year: u16) {
 pub fn create_folder(path: &Path) {
     // If the folder doesn't exist
     if !path.exists() {
-        // Create it
-        create_dir(path).expect("Failed to create folder !");
+        // Create it, with its parents
+        create_dir_all(path).expect("Failed to create folder !");
     }
 }
 
@@ -203,13 +288,19 @@ pub fn create_folder(path: &Path) {
 /// The folders and files are created in the folder from which the program was called and are
 /// structured like the one given in the file doc.
 ///
+/// Existing files are never touched, so it is safe to run it several times.
+///
 /// ## Arguments
 /// * `caller` - The folder from which the program was called
+/// * `config` - The configuration of the project
 /// * `year` - The year of the Advent of Code challenge
+/// * `with_days` - If true, the stubs and example folders of every day are created too
 ///
 /// ## Returns
 /// * `()` - Nothing
-pub fn init_folders_and_files(caller: &Path, year: u16) {
+pub fn init_folders_and_files(caller: &Path, config: &Config, year: u16, with_days: bool) {
+    let layout = &config.layout;
+
     println!(
         "Creating folders and files for the year {} @ {}\n 🎄 Happy coding !",
         year,
@@ -217,58 +308,133 @@ pub fn init_folders_and_files(caller: &Path, year: u16) {
     );
 
     // Create the 'src' folder, check if it already exists
-    let src_folder = caller.join("src");
-    if !src_folder.exists() {
-        create_dir(&src_folder).expect("Something went wrong creating the 'src' folder");
-    }
-
-    // Create the 'src/bin' folder, check if it already exists
-    let src_bin_folder = src_folder.join("bin");
-    create_folder(&src_bin_folder);
+    create_folder(&caller.join("src"));
 
-    // Create the 'year_n' folder, check if it already exists
-    let year_folder = src_bin_folder.join(format!("year_{}", year));
-    create_folder(&year_folder);
-
-    // Create the 'data' folder, check if it already exists
-    let data_folder = caller.join("data");
-    create_folder(&data_folder);
-
-    // Create the 'data/year_n' folder, check if it already exists
-    let data_year_folder = data_folder.join(format!("year_{}", year));
-    create_folder(&data_year_folder);
+    // Create the folders of the solutions, inputs and puzzles of the year
+    for folder in layout.year_folders(year) {
+        create_folder(&caller.join(folder));
+    }
 
-    // Create the 'data/year_n/puzzles' folder, check if it already exists
-    let data_year_puzzles_folder = data_year_folder.join("puzzles");
-    create_folder(&data_year_puzzles_folder);
+    // Keep the puzzle inputs out of git, as asked by the Advent of Code
+    if let Some(inputs_folder) = caller.join(layout.input(year, 1)).parent() {
+        create_file_if_missing(
+            &inputs_folder.join(".gitignore"),
+            &format!(
+                "# Puzzle inputs must not be shared\n{}\n",
+                layout.input_glob()
+            ),
+        );
+    }
 
-    // Create the 'data/year_n/inputs' folder, check if it already exists
-    let data_year_inputs_folder = data_year_folder.join("inputs");
-    create_folder(&data_year_inputs_folder);
+    // Create the README of the year
+    let readme = caller.join(layout.readme(year));
+    if let Some(readme_folder) = readme.parent() {
+        create_folder(readme_folder);
+    }
+    create_file_if_missing(&readme, &year_readme(layout, year));
+
+    if with_days {
+        let mut created = 0;
+        for day in 1..=days_in_year(year) {
+            // Create the examples folder of the day
+            create_folder(&caller.join(layout.examples(year, day)));
+
+            match create_day_files(caller, config, day, year, false) {
+                Ok(files) => {
+                    created += files
+                        .iter()
+                        .filter(|(_, status)| matches!(status, FileStatus::Created))
+                        .count()
+                }
+                Err(e) => {
+                    eprintln!("{}", e);
+                    break;
+                }
+            }
+        }
+
+        println!("{} files created for the year {}.", created, year);
+    }
 
     // run cargo init
     std::process::Command::new("cargo")
         .arg("init")
-        .current_dir(&caller)
+        .current_dir(caller)
         .output()
         .expect("Failed to run cargo init");
 }
 
+///
+/// # create_file_if_missing
+/// Creates a file with the given content, only if it doesn't exist yet.
+///
+/// ## Arguments
+/// * `path` - The path of the file to create
+/// * `content` - The content of the file
+///
+/// ## Returns
+/// * `()` - Nothing
+fn create_file_if_missing(path: &Path, content: &str) {
+    if !path.exists() {
+        write(path, content).expect("Failed to write to file !");
+    }
+}
+
+///
+/// # year_readme
+/// Returns the content of the 'README.md' file of the given year.
+///
+/// ## Arguments
+/// * `layout` - The layout of the project
+/// * `year` - The year of the Advent of Code challenge
+///
+/// ## Returns
+/// * `String` - The content of the README
+fn year_readme(layout: &Layout, year: u16) -> String {
+    let readme_folder = layout.readme(year).parent().map(Path::to_path_buf);
+
+    let days = (1..=days_in_year(year))
+        .map(|day| {
+            let solution = layout.solution(year, day);
+
+            format!(
+                "| {} | [puzzle]({AOC_URL}{}/day/{}) | [{}]({}) |\n",
+                day,
+                year,
+                day,
+                solution.file_name().unwrap_or_default().to_string_lossy(),
+                relative_path(readme_folder.as_deref().unwrap_or(Path::new("")), &solution)
+            )
+        })
+        .collect::<String>();
+
+    format!(
+        "# Advent of Code {}\n\n\
+        Solutions of the [Advent of Code {}]({AOC_URL}{}).\n\n\
+        | Day | Puzzle | Solution |\n\
+        |-----|--------|----------|\n\
+        {}",
+        year, year, year, days
+    )
+}
+
 ///
 /// # prepare_main_file
 /// Prepares the 'src/main.rs' file for the given day and year.
 ///
 /// ## Arguments
 /// * `caller` - The folder from which the program was called
+/// * `layout` - The layout of the project
 /// * `day` - The day of the Advent of Code challenge
 /// * `year` - The year of the Advent of Code challenge
+/// * `part_2` - If true, the second part is run too
 ///
 /// ## Returns
 /// * `()` - Nothing
-pub fn prepare_main_file(caller: &Path, day: u8, year: u16, part_2: bool) {
+pub fn prepare_main_file(caller: &Path, layout: &Layout, day: u8, year: u16, part_2: bool) {
     // Check if the file structure is valid
     if !check_global_file_struct_integrity(caller)
-        || !check_file_struct_integrity_year(caller, year)
+        || !check_file_struct_integrity_year(caller, layout, year)
     {
         println!("The file structure is not correct.\nPlease run `cargo aoc init` or `cargo aoc init --year desired_year` to create the folders and files needed for the Advent of Code challenges.");
         return;
@@ -287,7 +453,7 @@ pub fn prepare_main_file(caller: &Path, day: u8, year: u16, part_2: bool) {
         ///\n\
         \n\
         // Imports  ==============================================================================  Imports\n\
-        #[path = \"./bin/year_{}/day_{:02}.rs\"]\n\
+        #[path = \"./{}\"]\n\
         mod day;\n\
         \n\
         #[path = \"point.rs\"]\n
@@ -306,13 +472,128 @@ pub fn prepare_main_file(caller: &Path, day: u8, year: u16, part_2: bool) {
             \t{}response_part_2();\n\
         }}\n\
         ",
-        year, day, comment_part_2, comment_part_2
+        relative_path(Path::new("src"), &layout.solution(year, day)),
+        comment_part_2,
+        comment_part_2
     );
 
     // Write the content to the file
     write(&src_main_file, content).expect("Failed to write to file !");
 }
 
+///
+/// # open_in_editor
+/// Opens a file in the editor of the user, given by the `VISUAL` or `EDITOR` environment variable.
+///
+/// ## Arguments
+/// * `path` - The file to open
+///
+/// ## Returns
+/// * `Result<(), String>` - An error if no editor is set or it failed to start
+pub fn open_in_editor(path: &Path) -> Result<(), String> {
+    let editor = ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|variable| std::env::var(variable).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .ok_or("Set the VISUAL or EDITOR environment variable to open the solution.")?;
+
+    // The editor may come with arguments, e.g. `code --wait`
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or_default();
+
+    Command::new(program)
+        .args(words)
+        .arg(path)
+        .status()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to start {}: {}", program, e))
+}
+
+// Tests ==================================================================================== Tests
+#[cfg(test)]
+pub fn temp_folder(name: &str) -> PathBuf {
+    let folder = std::env::temp_dir().join(format!("aoc-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&folder);
+    create_dir_all(&folder).unwrap();
+
+    folder
+}
+
+#[test]
+fn test_init_folders_and_files() {
+    let caller = temp_folder("init");
+    let config = Config::default();
+    let layout = &config.layout;
+
+    init_folders_and_files(&caller, &config, 2025, true);
+    assert!(caller.join(layout.solution(2025, 12)).exists());
+    assert!(caller.join(layout.input(2025, 12)).exists());
+    assert!(caller.join(layout.examples(2025, 12)).is_dir());
+    assert!(!caller.join(layout.solution(2025, 13)).exists());
+
+    // A second run leaves every existing file untouched
+    let gitignore = caller
+        .join(layout.input(2025, 1))
+        .with_file_name(".gitignore");
+    let ignored = std::fs::read_to_string(&gitignore).unwrap();
+    write(caller.join(layout.solution(2025, 1)), "solved").unwrap();
+    write(caller.join(layout.input(2025, 1)), "1\n2\n").unwrap();
+    write(caller.join(layout.readme(2025)), "notes").unwrap();
+
+    init_folders_and_files(&caller, &config, 2025, true);
+    let read = |path: PathBuf| std::fs::read_to_string(caller.join(path)).unwrap();
+    assert_eq!(read(layout.solution(2025, 1)), "solved");
+    assert_eq!(read(layout.input(2025, 1)), "1\n2\n");
+    assert_eq!(read(layout.readme(2025)), "notes");
+    assert_eq!(std::fs::read_to_string(&gitignore).unwrap(), ignored);
+
+    // Without `--with-days`, only the folders of the year are created
+    init_folders_and_files(&caller, &config, 2024, false);
+    assert!(caller.join(layout.readme(2024)).exists());
+    assert!(!caller.join(layout.solution(2024, 1)).exists());
+}
+
+#[test]
+fn test_create_day_files() {
+    let caller = temp_folder("create");
+    let config = Config::default();
+    let solution = caller.join(config.layout.solution(2023, 1));
+    for folder in config.layout.year_folders(2023) {
+        create_folder(&caller.join(folder));
+    }
+    let read = |path: &Path| std::fs::read_to_string(path).unwrap();
+
+    let created = create_day_files(&caller, &config, 1, 2023, false).unwrap();
+    assert!(matches!(created[0].1, FileStatus::Created));
+    let template = read(&solution);
+
+    // Without `--force`, the solution is kept
+    write(&solution, "first").unwrap();
+    let skipped = create_day_files(&caller, &config, 1, 2023, false).unwrap();
+    assert!(matches!(skipped[0].1, FileStatus::Skipped));
+    assert_eq!(read(&solution), "first");
+
+    let FileStatus::Replaced(first_backup) =
+        &create_day_files(&caller, &config, 1, 2023, true).unwrap()[0].1
+    else {
+        panic!("The solution should be replaced");
+    };
+    assert_eq!(first_backup, &solution.with_extension("rs.bak"));
+    assert_eq!(read(first_backup), "first");
+    assert_eq!(read(&solution), template);
+
+    // A second `--force` keeps the first backup
+    write(&solution, "second").unwrap();
+    let FileStatus::Replaced(second_backup) =
+        &create_day_files(&caller, &config, 1, 2023, true).unwrap()[0].1
+    else {
+        panic!("The solution should be replaced");
+    };
+    assert_eq!(second_backup, &solution.with_extension("rs.bak.1"));
+    assert_eq!(read(first_backup), "first");
+    assert_eq!(read(second_backup), "second");
+}
+
 /*
  * End of file src/file_utils.rs
  */
@@ -0,0 +1,592 @@
+///
+/// # src/http.rs
+/// Contains the requests sent to the Advent of Code website.
+///
+/// Every network command goes through the `Client` of this module, which:
+/// - authenticates the requests with the session cookie and detects an expired session from the
+///   response, so that a login page is never saved as a puzzle input,
+/// - identifies itself with a User-Agent containing contact information, as requested by the
+///   Advent of Code maintainer,
+/// - waits a minimum delay between two requests, across invocations,
+/// - caches the cacheable GET responses on disk and revalidates them with ETag/Last-Modified.
+///
+/// The behaviour is configured in the `[http]` table of `aoc.toml`:
+/// ```toml
+/// [http]
+/// contact = "me@example.com"   # added to the User-Agent, or the AOC_CONTACT environment variable
+/// user_agent = "my-agent/1.0"  # replaces the whole User-Agent
+/// delay_ms = 1000              # minimum delay between two requests
+/// base_url = "http://localhost:3000/"  # another server, e.g. `aoc mock-server`
+/// ```
+///
+/// The `AOC_BASE_URL` environment variable takes precedence over `base_url`.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use chrono::Utc;
+use toml_edit::{value, DocumentMut};
+
+use std::{
+    env,
+    fs::{read_to_string, write},
+    io::Read,
+    path::PathBuf,
+    thread::sleep,
+    time::Duration,
+};
+
+use crate::config::get_string;
+use crate::file_utils::create_folder;
+
+// Variables  =========================================================================== Variables
+/// The Advent of Code website.
+pub const AOC_URL: &str = "https://adventofcode.com/";
+
+/// The message returned instead of a puzzle input when the session is missing or expired.
+const INPUT_LOGGED_OUT: &str = "Puzzle inputs differ by user";
+
+/// The error reported when the session is rejected by the website.
+pub const SESSION_EXPIRED: &str =
+    "The session is invalid or has expired, run `aoc auth login` with a new session cookie.";
+
+/// The environment variable replacing the base URL of the website.
+const BASE_URL_VARIABLE: &str = "AOC_BASE_URL";
+
+/// The environment variable holding the contact information of the User-Agent.
+const CONTACT_VARIABLE: &str = "AOC_CONTACT";
+
+/// The repository of this tool, part of the default User-Agent.
+const REPOSITORY: &str = "github.com/tomPlanche/aoc";
+
+/// The configuration of the requests, the `[http]` table of `aoc.toml`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpConfig {
+    /// The contact information added to the User-Agent
+    pub contact: Option<String>,
+    /// The User-Agent replacing the default one
+    pub user_agent: Option<String>,
+    /// The minimum delay between two requests
+    pub delay: Duration,
+    /// The root of the website, `AOC_URL` unless testing against another server
+    pub base_url: Option<String>,
+}
+
+/// The client every request goes through.
+pub struct Client {
+    /// The underlying HTTP agent, with the User-Agent set
+    agent: ureq::Agent,
+    /// The root of the website, ending with a `/`
+    base_url: String,
+    /// The session cookie, `None` for anonymous requests
+    session: Option<String>,
+    /// The minimum delay between two requests
+    delay: Duration,
+    /// The folder of the response cache and of the time of the last request
+    cache_folder: Option<PathBuf>,
+}
+
+/// A successful response.
+struct Response {
+    /// The status code, 304 when a cached response is still valid
+    status: u16,
+    /// The body
+    body: String,
+    /// The ETag header
+    etag: Option<String>,
+    /// The Last-Modified header
+    last_modified: Option<String>,
+}
+
+// Functions  =========================================================================== Functions
+impl Default for HttpConfig {
+    fn default() -> Self {
+        HttpConfig {
+            contact: None,
+            user_agent: None,
+            delay: Duration::from_millis(1000),
+            base_url: None,
+        }
+    }
+}
+
+impl HttpConfig {
+    ///
+    /// # base_url
+    /// Returns the root of the website the requests are sent to.
+    ///
+    /// ## Returns
+    /// * `String` - The URL, ending with a `/`
+    pub fn base_url(&self) -> String {
+        let base_url = env::var(BASE_URL_VARIABLE)
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+            .or_else(|| self.base_url.clone())
+            .unwrap_or_else(|| AOC_URL.to_string());
+
+        format!("{}/", base_url.trim().trim_end_matches('/'))
+    }
+
+    ///
+    /// # user_agent
+    /// Returns the User-Agent sent with every request.
+    ///
+    /// ## Returns
+    /// * `Option<String>` - The User-Agent, `None` if no contact information is configured
+    pub fn user_agent(&self) -> Option<String> {
+        if let Some(user_agent) = &self.user_agent {
+            return Some(user_agent.clone());
+        }
+
+        let contact = self.contact.clone().or_else(|| {
+            env::var(CONTACT_VARIABLE)
+                .ok()
+                .filter(|contact| !contact.trim().is_empty())
+        })?;
+
+        Some(format!(
+            "aoc-cli/{} (+{}; {})",
+            env!("CARGO_PKG_VERSION"),
+            REPOSITORY,
+            contact.trim()
+        ))
+    }
+}
+
+impl Client {
+    ///
+    /// # new
+    /// Creates a client.
+    /// Warns when no contact information is configured, as the Advent of Code maintainer asks
+    /// automated tools to identify themselves.
+    ///
+    /// ## Arguments
+    /// * `config` - The configuration of the requests
+    /// * `session` - The session cookie, `None` for anonymous requests
+    ///
+    /// ## Returns
+    /// * `Client` - The client
+    pub fn new(config: &HttpConfig, session: Option<String>) -> Client {
+        let user_agent = config.user_agent().unwrap_or_else(|| {
+            eprintln!(
+                "warning: no contact information in the User-Agent, set `contact` in the [http] \
+                table of aoc.toml or the {} environment variable.",
+                CONTACT_VARIABLE
+            );
+
+            format!("aoc-cli/{} (+{})", env!("CARGO_PKG_VERSION"), REPOSITORY)
+        });
+
+        Client {
+            agent: ureq::AgentBuilder::new().user_agent(&user_agent).build(),
+            base_url: config.base_url(),
+            session,
+            delay: config.delay,
+            cache_folder: cache_folder(),
+        }
+    }
+
+    ///
+    /// # get
+    /// Sends a GET request, without caching its response.
+    ///
+    /// ## Arguments
+    /// * `path` - The path of the page, relative to the website root, e.g. `2023/day/1/input`
+    ///
+    /// ## Returns
+    /// * `Result<String, String>` - The body of the response
+    pub fn get(&self, path: &str) -> Result<String, String> {
+        self.send(path, None, None).map(|response| response.body)
+    }
+
+    ///
+    /// # get_cached
+    /// Sends a GET request whose response is cached on disk.
+    /// A cached response younger than `max_age` is returned without any request, an older one is
+    /// revalidated with its ETag and Last-Modified headers.
+    ///
+    /// ## Arguments
+    /// * `path` - The path of the page, relative to the website root
+    /// * `max_age` - How long a cached response is used without revalidation
+    ///
+    /// ## Returns
+    /// * `Result<String, String>` - The body of the response
+    pub fn get_cached(&self, path: &str, max_age: Duration) -> Result<String, String> {
+        let Some(entry) = self.cache_entry(path) else {
+            return self.get(path);
+        };
+
+        let meta = read_to_string(entry.with_extension("toml"))
+            .ok()
+            .and_then(|meta| meta.parse::<DocumentMut>().ok());
+        let cached = meta.zip(read_to_string(entry.with_extension("body")).ok());
+
+        let (etag, last_modified) = match &cached {
+            Some((meta, body)) => {
+                let fetched_at = meta
+                    .get("fetched_at")
+                    .and_then(|item| item.as_integer())
+                    .unwrap_or_default();
+                let age = Utc::now().timestamp() - fetched_at;
+
+                if age >= 0 && (age as u64) < max_age.as_secs() {
+                    return Ok(body.clone());
+                }
+
+                (
+                    get_string(meta.as_table(), "etag").ok().flatten(),
+                    get_string(meta.as_table(), "last_modified").ok().flatten(),
+                )
+            }
+            None => (None, None),
+        };
+
+        let response = self.send(path, etag.as_deref(), last_modified.as_deref())?;
+        let body = match cached {
+            Some((_, body)) if response.status == 304 => body,
+            _ => response.body,
+        };
+
+        let mut meta = DocumentMut::new();
+        meta["url"] = value(format!("{}{}", self.base_url, path));
+        meta["fetched_at"] = value(Utc::now().timestamp());
+        if let Some(etag) = response.etag.or(etag) {
+            meta["etag"] = value(etag);
+        }
+        if let Some(last_modified) = response.last_modified.or(last_modified) {
+            meta["last_modified"] = value(last_modified);
+        }
+
+        if let Some(folder) = entry.parent() {
+            create_folder(folder);
+        }
+        // The cache is an optimisation, failing to write it is not an error
+        let _ = write(entry.with_extension("body"), &body);
+        let _ = write(entry.with_extension("toml"), meta.to_string());
+
+        Ok(body)
+    }
+
+    ///
+    /// # send
+    /// Sends a GET request, after waiting for the minimum delay since the last request.
+    ///
+    /// ## Arguments
+    /// * `path` - The path of the page, relative to the website root
+    /// * `etag` - The ETag of a cached response, to revalidate it
+    /// * `last_modified` - The Last-Modified date of a cached response, to revalidate it
+    ///
+    /// ## Returns
+    /// * `Result<Response, String>` - The response
+    fn send(
+        &self,
+        path: &str,
+        etag: Option<&str>,
+        last_modified: Option<&str>,
+    ) -> Result<Response, String> {
+        let url = format!("{}{}", self.base_url, path);
+
+        let mut request = self.agent.get(&url);
+        if let Some(session) = &self.session {
+            request = request.set("Cookie", &format!("session={}", session));
+        }
+        if let Some(etag) = etag {
+            request = request.set("If-None-Match", etag);
+        }
+        if let Some(last_modified) = last_modified {
+            request = request.set("If-Modified-Since", last_modified);
+        }
+
+        self.throttle();
+
+        self.receive(&url, request.call())
+    }
+
+    ///
+    /// # post
+    /// Sends a POST request with a form, after waiting for the minimum delay since the last
+    /// request. Used to submit answers, so the response is never cached.
+    ///
+    /// ## Arguments
+    /// * `path` - The path of the page, relative to the website root, e.g. `2023/day/1/answer`
+    /// * `form` - The URL-encoded form, e.g. `level=1&answer=1234`
+    ///
+    /// ## Returns
+    /// * `Result<String, String>` - The body of the response
+    pub fn post(&self, path: &str, form: &str) -> Result<String, String> {
+        let url = format!("{}{}", self.base_url, path);
+
+        let mut request = self
+            .agent
+            .post(&url)
+            .set("Content-Type", "application/x-www-form-urlencoded");
+        if let Some(session) = &self.session {
+            request = request.set("Cookie", &format!("session={}", session));
+        }
+
+        self.throttle();
+
+        self.receive(&url, request.send_string(form))
+            .map(|response| response.body)
+    }
+
+    ///
+    /// # receive
+    /// Reads the response of a request, checking that the session has not been rejected.
+    ///
+    /// ## Arguments
+    /// * `url` - The URL of the request, for the error messages
+    /// * `result` - The result of the request
+    ///
+    /// ## Returns
+    /// * `Result<Response, String>` - The response, an error for an unsuccessful status
+    fn receive(
+        &self,
+        url: &str,
+        result: Result<ureq::Response, ureq::Error>,
+    ) -> Result<Response, String> {
+        let response = match result {
+            Ok(response) => response,
+            Err(ureq::Error::Status(status, response)) => {
+                let body = read_body(response)?;
+                check_session(&body, self.session.is_some())?;
+
+                return Err(format!("{} returned the status {}.", url, status));
+            }
+            Err(e) => return Err(format!("Failed to reach {}: {}", self.base_url, e)),
+        };
+
+        let status = response.status();
+        let etag = response.header("ETag").map(str::to_string);
+        let last_modified = response.header("Last-Modified").map(str::to_string);
+
+        let body = read_body(response)?;
+        check_session(&body, self.session.is_some())?;
+
+        Ok(Response {
+            status,
+            body,
+            etag,
+            last_modified,
+        })
+    }
+
+    ///
+    /// # throttle
+    /// Waits until the minimum delay since the last request, possibly sent by another
+    /// invocation, has passed, then records the time of the upcoming request.
+    ///
+    /// ## Returns
+    /// * `()` - Nothing
+    fn throttle(&self) {
+        let Some(folder) = &self.cache_folder else {
+            return;
+        };
+        let last_request = folder.join("last_request");
+
+        let elapsed = read_to_string(&last_request)
+            .ok()
+            .and_then(|millis| millis.trim().parse::<i64>().ok())
+            .map(|millis| Utc::now().timestamp_millis() - millis);
+
+        if let Some(elapsed) = elapsed.filter(|elapsed| *elapsed >= 0) {
+            if let Some(wait) = self
+                .delay
+                .checked_sub(Duration::from_millis(elapsed as u64))
+            {
+                sleep(wait);
+            }
+        }
+
+        create_folder(folder);
+        let _ = write(&last_request, Utc::now().timestamp_millis().to_string());
+    }
+
+    ///
+    /// # cache_entry
+    /// Returns the path of the cache entry of a page, without extension.
+    /// The session is part of the key, as the pages differ by user.
+    ///
+    /// ## Arguments
+    /// * `path` - The path of the page, relative to the website root
+    ///
+    /// ## Returns
+    /// * `Option<PathBuf>` - The path, `None` if there is no cache folder
+    fn cache_entry(&self, path: &str) -> Option<PathBuf> {
+        let key = format!(
+            "{}|{}{}",
+            self.session.as_deref().unwrap_or_default(),
+            self.base_url,
+            path
+        );
+
+        Some(
+            self.cache_folder
+                .as_ref()?
+                .join("responses")
+                .join(format!("{:016x}", fnv1a(key.as_bytes()))),
+        )
+    }
+}
+
+///
+/// # cache_folder
+/// Returns the folder of the HTTP cache, `~/.cache/aoc/http` (or under `$XDG_CACHE_HOME`).
+/// It is kept out of the project, as the cached pages are personal.
+///
+/// ## Returns
+/// * `Option<PathBuf>` - The folder, `None` if the home folder is unknown
+fn cache_folder() -> Option<PathBuf> {
+    let folder = match env::var_os("XDG_CACHE_HOME").filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".cache"),
+    };
+
+    Some(folder.join("aoc").join("http"))
+}
+
+///
+/// # fnv1a
+/// Hashes bytes with the 64-bit FNV-1a function, which is stable across runs and versions.
+///
+/// ## Arguments
+/// * `bytes` - The bytes to hash
+///
+/// ## Returns
+/// * `u64` - The hash
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+///
+/// # read_body
+/// Reads the body of a response.
+///
+/// ## Arguments
+/// * `response` - The response to read
+///
+/// ## Returns
+/// * `Result<String, String>` - The body
+fn read_body(response: ureq::Response) -> Result<String, String> {
+    let mut body = String::new();
+
+    response
+        .into_reader()
+        .read_to_string(&mut body)
+        .map_err(|e| format!("Failed to read the response: {}", e))?;
+
+    Ok(body)
+}
+
+///
+/// # check_session
+/// Checks that the website accepted the session, from the body of its response: the logged out
+/// input message, or an HTML page that shows no user although the request had a session.
+///
+/// ## Arguments
+/// * `body` - The body of the response
+/// * `authenticated` - Whether the request was sent with a session
+///
+/// ## Returns
+/// * `Result<(), String>` - An error if the session was rejected
+pub fn check_session(body: &str, authenticated: bool) -> Result<(), String> {
+    let logged_out_page = authenticated && body.contains("<html") && username(body).is_none();
+
+    if body.contains(INPUT_LOGGED_OUT) || logged_out_page {
+        return Err(SESSION_EXPIRED.to_string());
+    }
+
+    Ok(())
+}
+
+///
+/// # username
+/// Returns the name of the logged in user, read from a page of the website.
+///
+/// ## Arguments
+/// * `page` - The HTML of the page
+///
+/// ## Returns
+/// * `Option<String>` - The name of the user, `None` if the page was served logged out
+pub fn username(page: &str) -> Option<String> {
+    let start = page.find("<div class=\"user\">")? + "<div class=\"user\">".len();
+    let end = page[start..]
+        .find('<')
+        .map_or(page.len(), |end| start + end);
+
+    Some(page[start..end].trim().to_string()).filter(|name| !name.is_empty())
+}
+
+// Tests ==================================================================================== Tests
+#[cfg(test)]
+pub fn test_client(base_url: &str, session: &str) -> Client {
+    Client::new(
+        &HttpConfig {
+            contact: Some("tests".to_string()),
+            delay: Duration::ZERO,
+            base_url: Some(base_url.to_string()),
+            ..HttpConfig::default()
+        },
+        Some(session.to_string()),
+    )
+}
+
+#[test]
+fn test_check_session() {
+    assert!(check_session("1721\n1979\n366\n", true).is_ok());
+    assert_eq!(
+        check_session(
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+            true
+        ),
+        Err(SESSION_EXPIRED.to_string())
+    );
+
+    let logged_in = "<!DOCTYPE html>\n<html><header><div class=\"user\">tomPlanche</div></header>\
+        <main><article><h2>--- Day 1: Trebuchet?! ---</h2></article></main></html>";
+    let logged_out = "<!DOCTYPE html>\n<html><header><div><a href=\"/auth/login\">[Log In]</a>\
+        </div></header><main><article><h2>--- Day 1: Trebuchet?! ---</h2></article></main></html>";
+
+    assert!(check_session(logged_in, true).is_ok());
+    assert_eq!(
+        check_session(logged_out, true),
+        Err(SESSION_EXPIRED.to_string())
+    );
+    // Anonymous requests are expected to be served logged out
+    assert!(check_session(logged_out, false).is_ok());
+}
+
+#[test]
+fn test_username() {
+    let page = "<header><div><div class=\"user\">tomPlanche \
+        <a href=\"/support\" class=\"supporter-badge\">AoC++</a> \
+        <span class=\"star-count\">42*</span></div></div></header>";
+
+    assert_eq!(username(page), Some("tomPlanche".to_string()));
+    assert_eq!(username("<div>[Log In]</div>"), None);
+}
+
+#[test]
+fn test_user_agent() {
+    let config = HttpConfig {
+        contact: Some("me@example.com".to_string()),
+        ..HttpConfig::default()
+    };
+    let user_agent = config.user_agent().unwrap();
+
+    assert!(user_agent.starts_with("aoc-cli/"));
+    assert!(user_agent.ends_with("; me@example.com)"));
+
+    let custom = HttpConfig {
+        user_agent: Some("my-agent/1.0".to_string()),
+        ..config
+    };
+    assert_eq!(custom.user_agent().as_deref(), Some("my-agent/1.0"));
+}
+
+/*
+ * End of file src/http.rs
+ */
@@ -0,0 +1,633 @@
+///
+/// # src/import.rs
+/// Contains the import of repositories written for other Advent of Code tools.
+///
+/// Supported layouts:
+/// - `cargo-aoc`: solutions in `src/dayN.rs` using the `#[aoc]` and `#[aoc_generator]`
+///   attributes, inputs in `input/YEAR/dayN.txt`
+/// - `fspoettel` (advent-of-code-rust template): solutions in `src/bin/NN.rs` with `part_one` and
+///   `part_two` functions, inputs in `data/inputs/NN.txt`, puzzles in `data/puzzles/NN.md` and
+///   examples in `data/examples/NN.txt`
+///
+/// The solutions are rewritten to expose the `response_part_1` and `response_part_2` functions
+/// expected by this tool, and every file is stored where the layout of the project expects it.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use clap::ValueEnum;
+
+use std::{
+    fs::{copy, read_dir, read_to_string, remove_file, write},
+    path::{Path, PathBuf},
+};
+
+use crate::calendar::FIRST_YEAR;
+use crate::config::Config;
+use crate::file_utils::create_folder;
+use crate::layout::relative_path;
+
+// Variables  =========================================================================== Variables
+/// The layouts of the other Advent of Code tools.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ImportFormat {
+    /// cargo-aoc: `src/dayN.rs` with `#[aoc]` attributes
+    CargoAoc,
+    /// fspoettel's advent-of-code-rust template: `src/bin/NN.rs`
+    Fspoettel,
+}
+
+/// What happened to the files of one imported day.
+pub struct ImportedDay {
+    pub day: u8,
+    /// The files written, relative to the project root
+    pub files: Vec<PathBuf>,
+    /// The files that already existed and were left untouched
+    pub skipped: Vec<PathBuf>,
+    /// Everything that needs a manual look
+    pub warnings: Vec<String>,
+}
+
+/// A solution function found in an imported file.
+struct SolutionFn {
+    name: String,
+    signature: String,
+}
+
+// Functions  =========================================================================== Functions
+///
+/// # import
+/// Imports the solutions, inputs, puzzles and examples of a repository of another tool.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `config` - The configuration of the project
+/// * `format` - The layout of the repository to import
+/// * `source` - The root of the repository to import
+/// * `year` - The year of the solutions, detected from the repository if not given
+/// * `move_files` - If true, the original files are removed once imported
+///
+/// ## Returns
+/// * `Result<(u16, Vec<ImportedDay>), String>` - The year and the report of every imported day
+pub fn import(
+    caller: &Path,
+    config: &Config,
+    format: ImportFormat,
+    source: &Path,
+    year: Option<u16>,
+    move_files: bool,
+) -> Result<(u16, Vec<ImportedDay>), String> {
+    let year = year
+        .or_else(|| detect_year(format, source))
+        .ok_or("Could not detect the year of the solutions, please pass --year.")?;
+
+    let layout = &config.layout;
+    for folder in layout.year_folders(year) {
+        create_folder(&caller.join(folder));
+    }
+
+    let mut report = Vec::new();
+    for (day, solution) in find_solutions(format, source)? {
+        let mut imported = ImportedDay {
+            day,
+            files: Vec::new(),
+            skipped: Vec::new(),
+            warnings: Vec::new(),
+        };
+
+        let solution_target = layout.solution(year, day);
+        let input_target = layout.input(year, day);
+        let examples_target = layout.examples(year, day);
+        let input_path = relative_path(
+            solution_target.parent().unwrap_or(Path::new("")),
+            &input_target,
+        );
+
+        // Data files: (source, target)
+        let mut data_files = Vec::new();
+        let examples_path = relative_path(
+            solution_target.parent().unwrap_or(Path::new("")),
+            &examples_target,
+        );
+
+        let source_code = read_to_string(&solution)
+            .map_err(|e| format!("Failed to read {}: {}", solution.display(), e))?;
+        let code = match format {
+            ImportFormat::CargoAoc => {
+                data_files.push((
+                    source.join(format!("input/{}/day{}.txt", year, day)),
+                    input_target,
+                ));
+
+                wrap_cargo_aoc(&source_code, &input_path, &mut imported.warnings)
+            }
+            ImportFormat::Fspoettel => {
+                data_files.push((
+                    source.join(format!("data/inputs/{:02}.txt", day)),
+                    input_target,
+                ));
+                data_files.push((
+                    source.join(format!("data/puzzles/{:02}.md", day)),
+                    layout.puzzle(year, day),
+                ));
+                data_files.extend(find_examples(source, day).into_iter().map(|example| {
+                    let target = examples_target.join(example.file_name().unwrap_or_default());
+                    (example, target)
+                }));
+
+                wrap_fspoettel(
+                    &source_code,
+                    day,
+                    &input_path,
+                    &examples_path,
+                    &mut imported.warnings,
+                )
+            }
+        };
+
+        // Write the solution
+        if caller.join(&solution_target).exists() {
+            imported.skipped.push(solution_target);
+        } else {
+            write(caller.join(&solution_target), code)
+                .map_err(|e| format!("Failed to write {}: {}", solution_target.display(), e))?;
+            imported.files.push(solution_target);
+
+            if move_files {
+                remove_file(&solution).map_err(|e| e.to_string())?;
+            }
+        }
+
+        // Copy the inputs, puzzles and examples
+        for (from, to) in data_files {
+            if !from.exists() {
+                continue;
+            }
+
+            if caller.join(&to).exists() {
+                imported.skipped.push(to);
+                continue;
+            }
+
+            if let Some(parent) = to.parent() {
+                create_folder(&caller.join(parent));
+            }
+            copy(&from, caller.join(&to))
+                .map_err(|e| format!("Failed to copy {}: {}", from.display(), e))?;
+            imported.files.push(to);
+
+            if move_files {
+                remove_file(&from).map_err(|e| e.to_string())?;
+            }
+        }
+
+        report.push(imported);
+    }
+
+    Ok((year, report))
+}
+
+///
+/// # print_report
+/// Prints the migration report of an import.
+///
+/// ## Arguments
+/// * `year` - The year of the imported solutions
+/// * `report` - The report of every imported day
+///
+/// ## Returns
+/// * `()` - Nothing
+pub fn print_report(year: u16, report: &[ImportedDay]) {
+    println!("Migration report for the year {}:", year);
+
+    for day in report {
+        println!("Day {}:", day.day);
+        for file in &day.files {
+            println!("  imported {}", file.display());
+        }
+        for file in &day.skipped {
+            println!("  skipped  {} (already exists)", file.display());
+        }
+        for warning in &day.warnings {
+            println!("  warning  {}", warning);
+        }
+    }
+
+    let warnings = report.iter().map(|day| day.warnings.len()).sum::<usize>();
+    println!("{} days imported, {} warnings.", report.len(), warnings);
+}
+
+///
+/// # detect_year
+/// Detects the year of the solutions of a repository.
+/// cargo-aoc declares it in `src/lib.rs` (`aoc_lib! { year = 2018 }`), the fspoettel template
+/// in the `AOC_YEAR` variable of `.cargo/config.toml`.
+///
+/// ## Arguments
+/// * `format` - The layout of the repository
+/// * `source` - The root of the repository
+///
+/// ## Returns
+/// * `Option<u16>` - The year, if found
+fn detect_year(format: ImportFormat, source: &Path) -> Option<u16> {
+    let (file, key) = match format {
+        ImportFormat::CargoAoc => ("src/lib.rs", "year"),
+        ImportFormat::Fspoettel => (".cargo/config.toml", "AOC_YEAR"),
+    };
+
+    let content = read_to_string(source.join(file)).ok()?;
+    content.lines().find_map(|line| {
+        let (_, value) = line.split_once(key)?;
+        let year = value
+            .trim_start()
+            .strip_prefix('=')?
+            .trim_start()
+            .trim_start_matches('"')
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect::<String>()
+            .parse::<u16>()
+            .ok()?;
+
+        (year >= FIRST_YEAR).then_some(year)
+    })
+}
+
+///
+/// # find_solutions
+/// Lists the solution files of a repository.
+///
+/// ## Arguments
+/// * `format` - The layout of the repository
+/// * `source` - The root of the repository
+///
+/// ## Returns
+/// * `Result<Vec<(u8, PathBuf)>, String>` - The day and path of every solution, sorted by day
+fn find_solutions(format: ImportFormat, source: &Path) -> Result<Vec<(u8, PathBuf)>, String> {
+    let folder = match format {
+        ImportFormat::CargoAoc => source.join("src"),
+        ImportFormat::Fspoettel => source.join("src/bin"),
+    };
+
+    let entries =
+        read_dir(&folder).map_err(|e| format!("Failed to read {}: {}", folder.display(), e))?;
+
+    let mut solutions = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter_map(|path| {
+            let stem = path.file_stem()?.to_str()?;
+            if path.extension()? != "rs" {
+                return None;
+            }
+
+            let day = match format {
+                ImportFormat::CargoAoc => stem.strip_prefix("day")?,
+                ImportFormat::Fspoettel => stem,
+            }
+            .parse::<u8>()
+            .ok()
+            .filter(|day| (1..=25).contains(day))?;
+
+            Some((day, path))
+        })
+        .collect::<Vec<_>>();
+
+    solutions.sort();
+    Ok(solutions)
+}
+
+///
+/// # find_examples
+/// Lists the example files of a day in a fspoettel repository (`data/examples/NN.txt` and
+/// `data/examples/NN-P.txt`).
+///
+/// ## Arguments
+/// * `source` - The root of the repository
+/// * `day` - The day of the examples
+///
+/// ## Returns
+/// * `Vec<PathBuf>` - The example files
+fn find_examples(source: &Path, day: u8) -> Vec<PathBuf> {
+    let prefix = format!("{:02}", day);
+
+    let mut examples = read_dir(source.join("data/examples"))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| {
+                    path.file_stem()
+                        .and_then(|stem| stem.to_str())
+                        .is_some_and(|stem| {
+                            stem == prefix
+                                || stem
+                                    .strip_prefix(&prefix)
+                                    .is_some_and(|rest| rest.starts_with('-'))
+                        })
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    examples.sort();
+    examples
+}
+
+///
+/// # find_fn
+/// Finds the function declared after the given line.
+///
+/// ## Arguments
+/// * `lines` - The lines of the source file
+/// * `from` - The index of the line to start from
+///
+/// ## Returns
+/// * `Option<SolutionFn>` - The name and signature of the function
+fn find_fn(lines: &[&str], from: usize) -> Option<SolutionFn> {
+    let start = (from..lines.len()).find(|i| lines[*i].contains("fn "))?;
+    let name = lines[start]
+        .split("fn ")
+        .nth(1)?
+        .split(|c: char| c == '(' || c == '<' || c.is_whitespace())
+        .next()?
+        .to_string();
+
+    let signature = lines[start..]
+        .iter()
+        .scan(false, |done, line| {
+            if *done {
+                return None;
+            }
+            *done = line.contains('{');
+            Some(line.split('{').next().unwrap_or(line).trim())
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Some(SolutionFn { name, signature })
+}
+
+///
+/// # print_answer
+/// Returns the statement printing the answer of a solution function call.
+/// Answers wrapped in an `Option` or a `Result` are unwrapped.
+///
+/// ## Arguments
+/// * `function` - The solution function
+/// * `call` - The expression calling the function
+///
+/// ## Returns
+/// * `String` - The statement
+fn print_answer(function: &SolutionFn, call: &str) -> String {
+    let returns = function.signature.split("->").nth(1).unwrap_or("").trim();
+
+    if returns.starts_with("Option") {
+        format!(
+            "match {} {{\n        Some(answer) => println!(\"{{}}\", answer),\n        None => println!(\"No answer\"),\n    }}",
+            call
+        )
+    } else if returns.starts_with("Result") {
+        format!(
+            "match {} {{\n        Ok(answer) => println!(\"{{}}\", answer),\n        Err(e) => println!(\"Error: {{:?}}\", e),\n    }}",
+            call
+        )
+    } else {
+        format!("println!(\"{{}}\", {});", call)
+    }
+}
+
+///
+/// # response_fn
+/// Returns the code of a `response_part_N` function.
+///
+/// ## Arguments
+/// * `part` - The part of the puzzle
+/// * `body` - The body of the function, `None` if the part isn't solved
+///
+/// ## Returns
+/// * `String` - The code of the function
+fn response_fn(part: u8, body: Option<String>) -> String {
+    format!(
+        "pub fn response_part_{}() {{\n    {}\n}}\n",
+        part,
+        body.unwrap_or_else(|| format!("println!(\"Day - Part {} not solved\");", part))
+    )
+}
+
+///
+/// # wrapper
+/// Returns the code appended to an imported solution: its input and the response functions.
+///
+/// ## Arguments
+/// * `input_path` - The path of the input, relative to the solution
+/// * `part_1` - The body of `response_part_1`
+/// * `part_2` - The body of `response_part_2`
+///
+/// ## Returns
+/// * `String` - The code of the wrapper
+fn wrapper(input_path: &str, part_1: Option<String>, part_2: Option<String>) -> String {
+    format!(
+        "\n// Advent of Code CLI  =============================================================== Advent of Code CLI\n\
+        const INPUT: &str = include_str!(\"{}\");\n\n{}\n{}",
+        input_path,
+        response_fn(1, part_1),
+        response_fn(2, part_2)
+    )
+}
+
+///
+/// # wrap_cargo_aoc
+/// Rewrites a cargo-aoc solution: the `#[aoc]` attributes are removed and the response
+/// functions call the generator and part functions.
+///
+/// ## Arguments
+/// * `code` - The code of the solution
+/// * `input_path` - The path of the input, relative to the solution
+/// * `warnings` - Collects what needs a manual look
+///
+/// ## Returns
+/// * `String` - The rewritten solution
+fn wrap_cargo_aoc(code: &str, input_path: &str, warnings: &mut Vec<String>) -> String {
+    let lines = code.lines().collect::<Vec<_>>();
+
+    let mut generator = None;
+    let mut parts: [Option<SolutionFn>; 2] = [None, None];
+    let mut kept = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("use aoc_runner_derive") || trimmed.starts_with("use aoc_runner") {
+            continue;
+        }
+
+        if trimmed.starts_with("#[aoc_generator(") {
+            generator = find_fn(&lines, i + 1);
+            continue;
+        }
+
+        if trimmed.starts_with("#[aoc(") {
+            let part = if trimmed.contains("part2") { 1 } else { 0 };
+
+            match (&parts[part], find_fn(&lines, i + 1)) {
+                (None, Some(function)) => parts[part] = Some(function),
+                (Some(_), Some(function)) => warnings.push(format!(
+                    "{} is an alternative implementation of part {}, only the first one is used",
+                    function.name,
+                    part + 1
+                )),
+                _ => {}
+            }
+            continue;
+        }
+
+        kept.push(*line);
+    }
+
+    let input = match &generator {
+        Some(generator) if generator.signature.contains("-> Result") => {
+            format!("&{}(INPUT).unwrap()", generator.name)
+        }
+        Some(generator) => format!("&{}(INPUT)", generator.name),
+        None => "INPUT".to_string(),
+    };
+
+    let [part_1, part_2] = parts.map(|part| {
+        part.map(|function| print_answer(&function, &format!("{}({})", function.name, input)))
+    });
+
+    if part_1.is_none() {
+        warnings.push("no #[aoc(dayN, part1)] function found".to_string());
+    }
+
+    format!(
+        "{}\n{}",
+        kept.join("\n"),
+        wrapper(input_path, part_1, part_2)
+    )
+}
+
+///
+/// # wrap_fspoettel
+/// Rewrites a fspoettel template solution: the `solution!` macro is removed, the example files
+/// are included directly and the response functions call `part_one` and `part_two`.
+///
+/// ## Arguments
+/// * `code` - The code of the solution
+/// * `day` - The day of the solution
+/// * `input_path` - The path of the input, relative to the solution
+/// * `examples_path` - The path of the examples folder, relative to the solution
+/// * `warnings` - Collects what needs a manual look
+///
+/// ## Returns
+/// * `String` - The rewritten solution
+fn wrap_fspoettel(
+    code: &str,
+    day: u8,
+    input_path: &str,
+    examples_path: &str,
+    warnings: &mut Vec<String>,
+) -> String {
+    let mut code = code
+        .lines()
+        .filter(|line| !line.trim().starts_with("advent_of_code::solution!"))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .replace(
+            "advent_of_code::template::read_file(\"examples\", DAY)",
+            &format!("include_str!(\"{}/{:02}.txt\")", examples_path, day),
+        );
+
+    // Examples of a given part: read_file_part("examples", DAY, 2)
+    for part in 1..=3 {
+        code = code.replace(
+            &format!(
+                "advent_of_code::template::read_file_part(\"examples\", DAY, {})",
+                part
+            ),
+            &format!(
+                "include_str!(\"{}/{:02}-{}.txt\")",
+                examples_path, day, part
+            ),
+        );
+    }
+
+    if code.contains("advent_of_code::") {
+        warnings.push("the solution still uses the advent_of_code template crate".to_string());
+    }
+
+    let lines = code.lines().collect::<Vec<_>>();
+    let find_part = |name: &str| {
+        let index = lines
+            .iter()
+            .position(|line| line.contains(&format!("fn {}(", name)))?;
+        find_fn(&lines, index)
+    };
+
+    let part_1 = find_part("part_one").map(|function| print_answer(&function, "part_one(INPUT)"));
+    let part_2 = find_part("part_two").map(|function| print_answer(&function, "part_two(INPUT)"));
+
+    if part_1.is_none() {
+        warnings.push("no part_one function found".to_string());
+    }
+
+    format!("{}\n{}", code, wrapper(input_path, part_1, part_2))
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_wrap_cargo_aoc() {
+    let code = "use aoc_runner_derive::{aoc, aoc_generator};\n\
+        \n\
+        #[aoc_generator(day1)]\n\
+        fn parse(input: &str) -> Vec<u32> {\n\
+            input.lines().map(|l| l.parse().unwrap()).collect()\n\
+        }\n\
+        \n\
+        #[aoc(day1, part1)]\n\
+        fn part1(input: &[u32]) -> u32 {\n\
+            input.iter().sum()\n\
+        }\n";
+    let mut warnings = Vec::new();
+
+    let wrapped = wrap_cargo_aoc(code, "../../input.txt", &mut warnings);
+
+    assert!(!wrapped.contains("aoc_runner_derive"));
+    assert!(!wrapped.contains("#[aoc"));
+    assert!(wrapped.contains("const INPUT: &str = include_str!(\"../../input.txt\");"));
+    assert!(wrapped.contains("println!(\"{}\", part1(&parse(INPUT)));"));
+    assert!(wrapped.contains("pub fn response_part_2()"));
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn test_wrap_fspoettel() {
+    let code = "advent_of_code::solution!(1);\n\
+        \n\
+        pub fn part_one(input: &str) -> Option<u32> {\n\
+            None\n\
+        }\n\
+        \n\
+        pub fn part_two(input: &str) -> Option<u32> {\n\
+            None\n\
+        }\n\
+        \n\
+        #[test]\n\
+        fn test_part_one() {\n\
+            let result = part_one(&advent_of_code::template::read_file(\"examples\", DAY));\n\
+            assert_eq!(result, None);\n\
+        }\n";
+    let mut warnings = Vec::new();
+
+    let wrapped = wrap_fspoettel(code, 1, "input.txt", "examples", &mut warnings);
+
+    assert!(!wrapped.contains("solution!"));
+    assert!(wrapped.contains("part_one(&include_str!(\"examples/01.txt\"))"));
+    assert!(wrapped.contains("match part_one(INPUT) {"));
+    assert!(wrapped.contains("match part_two(INPUT) {"));
+    assert!(warnings.is_empty());
+}
+
+/*
+ * End of file src/import.rs
+ */
@@ -0,0 +1,367 @@
+///
+/// # src/junit.rs
+/// Contains the JUnit XML reports written by `aoc verify --junit <path>` and
+/// `aoc test --junit <path>`, for CI servers to show the regressions.
+///
+/// `aoc verify` writes one test suite per year and one test case per day and part, `aoc test` one
+/// test suite per day and one test case per test of the solution, read from the output of
+/// `cargo test`:
+/// ```xml
+/// <testsuites name="aoc verify" tests="2" failures="1" errors="0" skipped="0" time="0.011">
+///   <testsuite name="2023" tests="2" failures="1" errors="0" skipped="0" time="0.011">
+///     <testcase classname="2023.day_01" name="part 1 [default]" time="0.005"/>
+///     <testcase classname="2023.day_01" name="part 2 [default]" time="0.006">
+///       <failure type="wrong" message="wrong, got 41, expected 42"></failure>
+///     </testcase>
+///   </testsuite>
+/// </testsuites>
+/// ```
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use std::{
+    fs::{create_dir_all, write},
+    path::Path,
+    time::Duration,
+};
+
+use crate::output::escape;
+
+// Variables  =========================================================================== Variables
+/// The line of the output of `cargo test` starting the output of a failed test.
+const FAILURE_START: &str = "---- ";
+
+/// The result of a test case.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CaseResult {
+    /// The test passed
+    Passed,
+    /// The test failed, with the type of the failure, its message and details
+    Failure(String, String, String),
+    /// The test could not run, with its message and details
+    Error(String, String),
+    /// The test did not run, with the reason
+    Skipped(String),
+}
+
+/// A test case of a report.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TestCase {
+    /// The group of the test, e.g. `2023.day_01`
+    pub class: String,
+    /// The name of the test
+    pub name: String,
+    /// The duration of the test, `None` if unknown
+    pub time: Option<Duration>,
+    /// The result of the test
+    pub result: CaseResult,
+}
+
+/// A test suite of a report.
+pub struct TestSuite {
+    /// The name of the suite
+    pub name: String,
+    /// The duration of the suite, the sum of its test cases if `None`
+    pub time: Option<Duration>,
+    /// The test cases
+    pub cases: Vec<TestCase>,
+}
+
+impl TestSuite {
+    ///
+    /// # is_failure
+    /// Returns whether a test case of the suite failed or could not run.
+    ///
+    /// ## Returns
+    /// * `bool` - Whether the suite failed
+    pub fn is_failure(&self) -> bool {
+        let [_, failures, errors, _] = counts(&self.cases);
+
+        failures + errors > 0
+    }
+}
+
+// Functions  =========================================================================== Functions
+///
+/// # write_report
+/// Writes a JUnit report, creating the folders of its path.
+///
+/// ## Arguments
+/// * `path` - The path of the report
+/// * `name` - The name of the report, e.g. `aoc verify`
+/// * `suites` - The test suites
+///
+/// ## Returns
+/// * `Result<(), String>` - An error if the report could not be written
+pub fn write_report(path: &Path, name: &str, suites: &[TestSuite]) -> Result<(), String> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+
+    write(path, render(name, suites))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+///
+/// # render
+/// Renders a JUnit report.
+///
+/// ## Arguments
+/// * `name` - The name of the report
+/// * `suites` - The test suites
+///
+/// ## Returns
+/// * `String` - The XML of the report
+pub fn render(name: &str, suites: &[TestSuite]) -> String {
+    let mut rendered = String::new();
+    let mut totals = [0; 4];
+    let mut total_time = Duration::ZERO;
+
+    for suite in suites {
+        let counts = counts(&suite.cases);
+        let time = suite.time.unwrap_or_else(|| {
+            suite
+                .cases
+                .iter()
+                .filter_map(|case| case.time)
+                .sum::<Duration>()
+        });
+        for (total, count) in totals.iter_mut().zip(counts) {
+            *total += count;
+        }
+        total_time += time;
+
+        rendered.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" \
+            time=\"{:.3}\">\n",
+            escape(&suite.name),
+            counts[0],
+            counts[1],
+            counts[2],
+            counts[3],
+            time.as_secs_f64()
+        ));
+
+        for case in &suite.cases {
+            rendered.push_str(&format!(
+                "    <testcase classname=\"{}\" name=\"{}\"",
+                escape(&case.class),
+                escape(&case.name)
+            ));
+            if let Some(time) = case.time {
+                rendered.push_str(&format!(" time=\"{:.3}\"", time.as_secs_f64()));
+            }
+
+            match &case.result {
+                CaseResult::Passed => rendered.push_str("/>\n"),
+                CaseResult::Failure(kind, message, details) => rendered.push_str(&format!(
+                    ">\n      <failure type=\"{}\" message=\"{}\">{}</failure>\n    </testcase>\n",
+                    escape(kind),
+                    escape(message),
+                    escape(details)
+                )),
+                CaseResult::Error(message, details) => rendered.push_str(&format!(
+                    ">\n      <error message=\"{}\">{}</error>\n    </testcase>\n",
+                    escape(message),
+                    escape(details)
+                )),
+                CaseResult::Skipped(reason) => rendered.push_str(&format!(
+                    ">\n      <skipped message=\"{}\"/>\n    </testcase>\n",
+                    escape(reason)
+                )),
+            }
+        }
+
+        rendered.push_str("  </testsuite>\n");
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+        <testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" \
+        time=\"{:.3}\">\n{}</testsuites>\n",
+        escape(name),
+        totals[0],
+        totals[1],
+        totals[2],
+        totals[3],
+        total_time.as_secs_f64(),
+        rendered
+    )
+}
+
+///
+/// # counts
+/// Counts the test cases of a suite by result.
+///
+/// ## Arguments
+/// * `cases` - The test cases
+///
+/// ## Returns
+/// * `[usize; 4]` - The number of tests, failures, errors and skipped tests
+fn counts(cases: &[TestCase]) -> [usize; 4] {
+    let count = |matches: fn(&CaseResult) -> bool| {
+        cases.iter().filter(|case| matches(&case.result)).count()
+    };
+
+    [
+        cases.len(),
+        count(|result| matches!(result, CaseResult::Failure(..))),
+        count(|result| matches!(result, CaseResult::Error(..))),
+        count(|result| matches!(result, CaseResult::Skipped(_))),
+    ]
+}
+
+///
+/// # parse_cargo_test
+/// Reads the test cases from the output of `cargo test`: the `test <name> ... ok` lines, and the
+/// output of the failed tests.
+///
+/// ## Arguments
+/// * `class` - The group of the tests, e.g. `2023.day_01`
+/// * `output` - The standard output of `cargo test`
+///
+/// ## Returns
+/// * `Vec<TestCase>` - The test cases, in the order they finished
+pub fn parse_cargo_test(class: &str, output: &str) -> Vec<TestCase> {
+    let mut cases = output
+        .lines()
+        .filter_map(|line| {
+            let (name, result) = line.strip_prefix("test ")?.split_once(" ... ")?;
+            let result = match result.trim() {
+                "ok" => CaseResult::Passed,
+                "FAILED" => CaseResult::Failure("panic".to_string(), String::new(), String::new()),
+                result if result.starts_with("ignored") => CaseResult::Skipped(
+                    result
+                        .split_once(", ")
+                        .map_or("ignored", |(_, reason)| reason)
+                        .to_string(),
+                ),
+                _ => return None,
+            };
+
+            Some(TestCase {
+                class: class.to_string(),
+                name: name.trim().to_string(),
+                time: None,
+                result,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // The output of each failed test, between its '---- <name> stdout ----' line and the next one
+    let mut failure: Option<(&str, Vec<&str>)> = None;
+    for line in output.lines().chain([FAILURE_START]) {
+        if line.starts_with(FAILURE_START) || line == "failures:" {
+            if let Some((name, details)) = failure.take() {
+                let details = details.join("\n").trim().to_string();
+                let message = details
+                    .lines()
+                    .find(|line| line.contains("panicked at"))
+                    .unwrap_or("the test failed")
+                    .to_string();
+
+                if let Some(case) = cases.iter_mut().find(|case| case.name == name) {
+                    case.result = CaseResult::Failure("panic".to_string(), message, details);
+                }
+            }
+
+            failure = line
+                .strip_prefix(FAILURE_START)
+                .and_then(|header| header.strip_suffix(" stdout ----"))
+                .map(|name| (name, Vec::new()));
+        } else if let Some((_, details)) = &mut failure {
+            details.push(line);
+        }
+    }
+
+    cases
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_render() {
+    let case = |name: &str, result| TestCase {
+        class: "2023.day_01".to_string(),
+        name: name.to_string(),
+        time: Some(Duration::from_millis(5)),
+        result,
+    };
+    let suites = [TestSuite {
+        name: "2023".to_string(),
+        time: None,
+        cases: vec![
+            case("part 1 [default]", CaseResult::Passed),
+            case(
+                "part 2 [default]",
+                CaseResult::Failure(
+                    "wrong".to_string(),
+                    "got \"41\", expected <42>".to_string(),
+                    "\x1b[31mred\x1b[0m".to_string(),
+                ),
+            ),
+        ],
+    }];
+
+    assert!(suites[0].is_failure());
+    assert_eq!(
+        render("aoc verify", &suites),
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <testsuites name=\"aoc verify\" tests=\"2\" failures=\"1\" errors=\"0\" skipped=\"0\" \
+         time=\"0.010\">\n  \
+         <testsuite name=\"2023\" tests=\"2\" failures=\"1\" errors=\"0\" skipped=\"0\" \
+         time=\"0.010\">\n    \
+         <testcase classname=\"2023.day_01\" name=\"part 1 [default]\" time=\"0.005\"/>\n    \
+         <testcase classname=\"2023.day_01\" name=\"part 2 [default]\" time=\"0.005\">\n      \
+         <failure type=\"wrong\" message=\"got &quot;41&quot;, expected &lt;42&gt;\">\
+         [31mred[0m</failure>\n    \
+         </testcase>\n  \
+         </testsuite>\n\
+         </testsuites>\n"
+    );
+}
+
+#[test]
+fn test_parse_cargo_test() {
+    let output = "\nrunning 3 tests\n\
+        test day::tests::test_part_1 ... ok\n\
+        test day::tests::test_slow ... ignored, too slow\n\
+        test day::tests::test_part_2 ... FAILED\n\
+        \n\
+        failures:\n\
+        \n\
+        ---- day::tests::test_part_2 stdout ----\n\
+        \n\
+        thread 'day::tests::test_part_2' panicked at src/bin/year_2023/day_01.rs:12:5:\n\
+        assertion `left == right` failed\n  left: 41\n right: 42\n\
+        \n\
+        failures:\n    day::tests::test_part_2\n\
+        \n\
+        test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out\n";
+
+    let cases = parse_cargo_test("2023.day_01", output);
+
+    assert_eq!(cases.len(), 3);
+    assert_eq!(cases[0].result, CaseResult::Passed);
+    assert_eq!(cases[1].result, CaseResult::Skipped("too slow".to_string()));
+    assert_eq!(
+        cases[2].result,
+        CaseResult::Failure(
+            "panic".to_string(),
+            "thread 'day::tests::test_part_2' panicked at src/bin/year_2023/day_01.rs:12:5:"
+                .to_string(),
+            "thread 'day::tests::test_part_2' panicked at src/bin/year_2023/day_01.rs:12:5:\n\
+             assertion `left == right` failed\n  left: 41\n right: 42"
+                .to_string()
+        )
+    );
+}
+
+/*
+ * End of file src/junit.rs
+ */
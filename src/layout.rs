@@ -0,0 +1,362 @@
+///
+/// # src/layout.rs
+/// Contains the directory layout of a project: where the solutions, inputs, puzzles and examples
+/// of every day are stored.
+///
+/// Each location is a path pattern relative to the project root, in which `{year}`, `{day}` and
+/// `{day:02}` (the day padded to two digits) are replaced. The default layout is the one
+/// described in `src/file_utils.rs`.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use std::path::{Component, Path, PathBuf};
+
+// Variables  =========================================================================== Variables
+/// The locations of the files of every day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Layout {
+    /// The solution file of a day
+    pub solution: String,
+    /// The input file of a day
+    pub input: String,
+    /// The puzzle description of a day
+    pub puzzle: String,
+    /// The folder containing the examples of a day
+    pub examples: String,
+    /// The README of a year
+    pub readme: String,
+    /// The accepted answers of a year
+    pub answers: String,
+}
+
+/// The placeholders a pattern can contain.
+const PLACEHOLDERS: [&str; 3] = ["{year}", "{day}", "{day:02}"];
+
+// Functions  =========================================================================== Functions
+impl Default for Layout {
+    fn default() -> Self {
+        Layout {
+            solution: "src/bin/year_{year}/day_{day:02}.rs".to_string(),
+            input: "data/year_{year}/inputs/day_{day:02}.txt".to_string(),
+            puzzle: "data/year_{year}/puzzles/day_{day:02}.md".to_string(),
+            examples: "data/year_{year}/examples/day_{day:02}".to_string(),
+            readme: "data/year_{year}/README.md".to_string(),
+            answers: "data/year_{year}/answers.toml".to_string(),
+        }
+    }
+}
+
+impl Layout {
+    ///
+    /// # validate
+    /// Checks that every pattern only uses known placeholders and that the patterns of the
+    /// files of a day contain the day.
+    ///
+    /// ## Returns
+    /// * `Result<(), String>` - An error describing the first invalid pattern
+    pub fn validate(&self) -> Result<(), String> {
+        let patterns = [
+            ("solution", &self.solution, true),
+            ("input", &self.input, true),
+            ("puzzle", &self.puzzle, true),
+            ("examples", &self.examples, true),
+            ("readme", &self.readme, false),
+            ("answers", &self.answers, false),
+        ];
+
+        for (name, pattern, per_day) in patterns {
+            let mut rest = pattern.as_str();
+            while let Some(start) = rest.find('{') {
+                let end = rest[start..]
+                    .find('}')
+                    .ok_or_else(|| format!("Unclosed placeholder in the '{}' layout.", name))?;
+
+                let placeholder = &rest[start..start + end + 1];
+                if !PLACEHOLDERS.contains(&placeholder) {
+                    return Err(format!(
+                        "Unknown placeholder {} in the '{}' layout, expected one of: {}.",
+                        placeholder,
+                        name,
+                        PLACEHOLDERS.join(", ")
+                    ));
+                }
+
+                rest = &rest[start + end + 1..];
+            }
+
+            if per_day && !pattern.contains("{day") {
+                return Err(format!("The '{}' layout should contain {{day}}.", name));
+            }
+
+            if Path::new(pattern).is_absolute()
+                || Path::new(pattern)
+                    .components()
+                    .any(|component| component == Component::ParentDir)
+            {
+                return Err(format!(
+                    "The '{}' layout should be relative to the project root.",
+                    name
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    ///
+    /// # solution
+    /// Returns the path of the solution file of a day, relative to the project root.
+    ///
+    /// ## Arguments
+    /// * `year` - The year of the Advent of Code challenge
+    /// * `day` - The day of the Advent of Code challenge
+    ///
+    /// ## Returns
+    /// * `PathBuf` - The path of the solution file
+    pub fn solution(&self, year: u16, day: u8) -> PathBuf {
+        expand(&self.solution, year, day)
+    }
+
+    ///
+    /// # input
+    /// Returns the path of the input file of a day, relative to the project root.
+    ///
+    /// ## Arguments
+    /// * `year` - The year of the Advent of Code challenge
+    /// * `day` - The day of the Advent of Code challenge
+    ///
+    /// ## Returns
+    /// * `PathBuf` - The path of the input file
+    pub fn input(&self, year: u16, day: u8) -> PathBuf {
+        expand(&self.input, year, day)
+    }
+
+    ///
+    /// # puzzle
+    /// Returns the path of the puzzle description of a day, relative to the project root.
+    ///
+    /// ## Arguments
+    /// * `year` - The year of the Advent of Code challenge
+    /// * `day` - The day of the Advent of Code challenge
+    ///
+    /// ## Returns
+    /// * `PathBuf` - The path of the puzzle description
+    pub fn puzzle(&self, year: u16, day: u8) -> PathBuf {
+        expand(&self.puzzle, year, day)
+    }
+
+    ///
+    /// # examples
+    /// Returns the path of the examples folder of a day, relative to the project root.
+    ///
+    /// ## Arguments
+    /// * `year` - The year of the Advent of Code challenge
+    /// * `day` - The day of the Advent of Code challenge
+    ///
+    /// ## Returns
+    /// * `PathBuf` - The path of the examples folder
+    pub fn examples(&self, year: u16, day: u8) -> PathBuf {
+        expand(&self.examples, year, day)
+    }
+
+    ///
+    /// # readme
+    /// Returns the path of the README of a year, relative to the project root.
+    ///
+    /// ## Arguments
+    /// * `year` - The year of the Advent of Code challenge
+    ///
+    /// ## Returns
+    /// * `PathBuf` - The path of the README
+    pub fn readme(&self, year: u16) -> PathBuf {
+        expand(&self.readme, year, 1)
+    }
+
+    ///
+    /// # answers
+    /// Returns the path of the accepted answers of a year, relative to the project root.
+    ///
+    /// ## Arguments
+    /// * `year` - The year of the Advent of Code challenge
+    ///
+    /// ## Returns
+    /// * `PathBuf` - The path of the answers file
+    pub fn answers(&self, year: u16) -> PathBuf {
+        expand(&self.answers, year, 1)
+    }
+
+    ///
+    /// # year_folders
+    /// Returns the folders holding the solutions, inputs and puzzles of a year.
+    ///
+    /// ## Arguments
+    /// * `year` - The year of the Advent of Code challenge
+    ///
+    /// ## Returns
+    /// * `Vec<PathBuf>` - The folders, relative to the project root
+    pub fn year_folders(&self, year: u16) -> Vec<PathBuf> {
+        let mut folders = Vec::new();
+
+        for file in [
+            self.solution(year, 1),
+            self.input(year, 1),
+            self.puzzle(year, 1),
+        ] {
+            if let Some(parent) = file.parent().filter(|p| !p.as_os_str().is_empty()) {
+                if !folders.contains(&parent.to_path_buf()) {
+                    folders.push(parent.to_path_buf());
+                }
+            }
+        }
+
+        folders
+    }
+
+    ///
+    /// # input_glob
+    /// Returns a gitignore pattern matching the input files of a year, relative to their folder.
+    ///
+    /// ## Returns
+    /// * `String` - The pattern, e.g. `day_*.txt`
+    pub fn input_glob(&self) -> String {
+        let file_name = self.input.rsplit('/').next().unwrap_or(&self.input);
+
+        PLACEHOLDERS
+            .iter()
+            .fold(file_name.to_string(), |glob, placeholder| {
+                glob.replace(placeholder, "*")
+            })
+    }
+}
+
+///
+/// # expand
+/// Replaces the placeholders of a pattern.
+///
+/// ## Arguments
+/// * `pattern` - The pattern to expand
+/// * `year` - The year of the Advent of Code challenge
+/// * `day` - The day of the Advent of Code challenge
+///
+/// ## Returns
+/// * `PathBuf` - The expanded path
+fn expand(pattern: &str, year: u16, day: u8) -> PathBuf {
+    PathBuf::from(
+        pattern
+            .replace("{year}", &year.to_string())
+            .replace("{day:02}", &format!("{:02}", day))
+            .replace("{day}", &day.to_string()),
+    )
+}
+
+///
+/// # relative_path
+/// Returns the path of `to` relative to the folder `from`, both being relative to the same root.
+/// The components are always separated by `/`, so that the path can be used in Rust attributes
+/// and macros such as `include_str!`.
+///
+/// ## Arguments
+/// * `from` - The folder the path should be relative to
+/// * `to` - The target path
+///
+/// ## Returns
+/// * `String` - The relative path
+pub fn relative_path(from: &Path, to: &Path) -> String {
+    let from = from
+        .components()
+        .filter(|c| *c != Component::CurDir)
+        .collect::<Vec<_>>();
+    let to = to
+        .components()
+        .filter(|c| *c != Component::CurDir)
+        .collect::<Vec<_>>();
+
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    let parts = std::iter::repeat_n("..".to_string(), from.len() - common)
+        .chain(
+            to[common..]
+                .iter()
+                .map(|c| c.as_os_str().to_string_lossy().to_string()),
+        )
+        .collect::<Vec<_>>();
+
+    parts.join("/")
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_default_layout() {
+    let layout = Layout::default();
+
+    assert!(layout.validate().is_ok());
+    assert_eq!(
+        layout.solution(2016, 7),
+        PathBuf::from("src/bin/year_2016/day_07.rs")
+    );
+    assert_eq!(
+        layout.input(2016, 7),
+        PathBuf::from("data/year_2016/inputs/day_07.txt")
+    );
+    assert_eq!(
+        layout.year_folders(2016),
+        vec![
+            PathBuf::from("src/bin/year_2016"),
+            PathBuf::from("data/year_2016/inputs"),
+            PathBuf::from("data/year_2016/puzzles"),
+        ]
+    );
+    assert_eq!(layout.input_glob(), "day_*.txt");
+}
+
+#[test]
+fn test_custom_layout() {
+    let layout = Layout {
+        solution: "src/{year}/day{day:02}.rs".to_string(),
+        input: "inputs/{year}/{day}.txt".to_string(),
+        ..Layout::default()
+    };
+
+    assert!(layout.validate().is_ok());
+    assert_eq!(layout.solution(2020, 3), PathBuf::from("src/2020/day03.rs"));
+    assert_eq!(layout.input(2020, 3), PathBuf::from("inputs/2020/3.txt"));
+
+    let unknown = Layout {
+        solution: "src/{month}/day{day}.rs".to_string(),
+        ..Layout::default()
+    };
+    assert!(unknown.validate().is_err());
+
+    let no_day = Layout {
+        input: "inputs/{year}.txt".to_string(),
+        ..Layout::default()
+    };
+    assert!(no_day.validate().is_err());
+
+    let outside = Layout {
+        input: "../inputs/{year}/{day}.txt".to_string(),
+        ..Layout::default()
+    };
+    assert!(outside.validate().is_err());
+}
+
+#[test]
+fn test_relative_path() {
+    assert_eq!(
+        relative_path(
+            Path::new("src/bin/year_2016"),
+            Path::new("data/year_2016/inputs/day_07.txt")
+        ),
+        "../../../data/year_2016/inputs/day_07.txt"
+    );
+    assert_eq!(
+        relative_path(Path::new("src"), Path::new("src/2016/day07.rs")),
+        "2016/day07.rs"
+    );
+}
+
+/*
+ * End of file src/layout.rs
+ */
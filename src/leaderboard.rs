@@ -0,0 +1,508 @@
+///
+/// # src/leaderboard.rs
+/// Contains the private leaderboards of the Advent of Code website.
+///
+/// A leaderboard is fetched as JSON from `/{year}/leaderboard/private/view/{id}.json`. The
+/// website asks not to request it more than once every 15 minutes, so the response is cached for
+/// that long.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use serde_json::{json, Value};
+
+use std::{collections::BTreeMap, time::Duration};
+
+use crate::calendar::{format_duration, unlock_time};
+use crate::http::Client;
+
+// Variables  =========================================================================== Variables
+/// How long a leaderboard is cached, as asked by the website.
+const MAX_AGE: Duration = Duration::from_secs(15 * 60);
+
+/// A private leaderboard of a year.
+pub struct Leaderboard {
+    /// The year of the event
+    pub year: u16,
+    /// The members, ranked by local score
+    pub members: Vec<Member>,
+}
+
+/// A member of a private leaderboard.
+pub struct Member {
+    /// The id of the member
+    pub id: u64,
+    /// The name of the member, `None` for anonymous users
+    pub name: Option<String>,
+    /// The local score of the member
+    pub local_score: u64,
+    /// The number of stars of the member
+    pub stars: u64,
+    /// When the last star was obtained, as a unix timestamp
+    pub last_star: i64,
+    /// When each part of each day was completed, as unix timestamps
+    pub completions: BTreeMap<u8, [Option<i64>; 2]>,
+}
+
+impl Member {
+    ///
+    /// # display_name
+    /// Returns the name of the member as shown by the website.
+    ///
+    /// ## Returns
+    /// * `String` - The name, or `(anonymous user #id)`
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    ///
+    /// # solve_time
+    /// Returns how long after the unlock a part of a day was completed.
+    ///
+    /// ## Arguments
+    /// * `year` - The year of the event
+    /// * `day` - The day of the puzzle
+    /// * `part` - The part of the puzzle, 1 or 2
+    ///
+    /// ## Returns
+    /// * `Option<Duration>` - The time since the unlock, `None` if the part is not completed
+    pub fn solve_time(&self, year: u16, day: u8, part: u8) -> Option<Duration> {
+        let completed = self.completions.get(&day)?[usize::from(part - 1)]?;
+        let elapsed = completed - unlock_time(year, day).timestamp();
+
+        Some(Duration::from_secs(elapsed.max(0) as u64))
+    }
+}
+
+// Functions  =========================================================================== Functions
+///
+/// # fetch
+/// Fetches a private leaderboard, or returns it from the cache if it is recent enough.
+///
+/// ## Arguments
+/// * `client` - The client authenticated with the session of a member of the leaderboard
+/// * `year` - The year of the event
+/// * `id` - The id of the leaderboard, the number at the end of its URL
+///
+/// ## Returns
+/// * `Result<Leaderboard, String>` - The leaderboard
+pub fn fetch(client: &Client, year: u16, id: u64) -> Result<Leaderboard, String> {
+    let json = client.get_cached(
+        &format!("{}/leaderboard/private/view/{}.json", year, id),
+        MAX_AGE,
+    )?;
+
+    parse(&json).map_err(|e| format!("Failed to read the leaderboard {}: {}", id, e))
+}
+
+///
+/// # parse
+/// Parses the JSON of a private leaderboard.
+///
+/// ## Arguments
+/// * `json` - The JSON sent by the website
+///
+/// ## Returns
+/// * `Result<Leaderboard, String>` - The leaderboard, with its members ranked
+pub fn parse(json: &str) -> Result<Leaderboard, String> {
+    // The website redirects to the login page when the session is not a member
+    let root: Value = serde_json::from_str(json).map_err(|_| {
+        "the response is not JSON, check that the session is a member of the leaderboard."
+            .to_string()
+    })?;
+
+    let year = integer(&root["event"])
+        .and_then(|year| u16::try_from(year).ok())
+        .ok_or("the year of the event is missing.")?;
+    let mut members = root["members"]
+        .as_object()
+        .ok_or("the members are missing.")?
+        .values()
+        .map(parse_member)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Same order as the website: ties go to whoever got their last star first
+    members.sort_by(|a, b| {
+        b.local_score
+            .cmp(&a.local_score)
+            .then(b.stars.cmp(&a.stars))
+            .then(a.last_star.cmp(&b.last_star))
+            .then(a.id.cmp(&b.id))
+    });
+
+    Ok(Leaderboard { year, members })
+}
+
+///
+/// # parse_member
+/// Parses a member of a private leaderboard.
+///
+/// ## Arguments
+/// * `member` - The JSON object of the member
+///
+/// ## Returns
+/// * `Result<Member, String>` - The member
+fn parse_member(member: &Value) -> Result<Member, String> {
+    let id = integer(&member["id"])
+        .and_then(|id| u64::try_from(id).ok())
+        .ok_or("a member has no id.")?;
+
+    let mut completions = BTreeMap::new();
+    if let Some(days) = member["completion_day_level"].as_object() {
+        for (day, parts) in days {
+            let Ok(day) = day.parse::<u8>() else {
+                continue;
+            };
+
+            completions.insert(
+                day,
+                [
+                    integer(&parts["1"]["get_star_ts"]),
+                    integer(&parts["2"]["get_star_ts"]),
+                ],
+            );
+        }
+    }
+
+    Ok(Member {
+        id,
+        name: member["name"].as_str().map(str::to_string),
+        local_score: integer(&member["local_score"]).unwrap_or_default().max(0) as u64,
+        stars: integer(&member["stars"]).unwrap_or_default().max(0) as u64,
+        last_star: integer(&member["last_star_ts"]).unwrap_or_default(),
+        completions,
+    })
+}
+
+///
+/// # integer
+/// Reads an integer that the website sends either as a number or as a string.
+///
+/// ## Arguments
+/// * `value` - The JSON value
+///
+/// ## Returns
+/// * `Option<i64>` - The integer, `None` if the value is missing or not an integer
+fn integer(value: &Value) -> Option<i64> {
+    match value {
+        Value::Number(number) => number.as_i64(),
+        Value::String(string) => string.parse().ok(),
+        _ => None,
+    }
+}
+
+///
+/// # render_table
+/// Renders the ranked table of a leaderboard: the local score and stars of every member, then
+/// how long after the unlock each of them completed every day.
+///
+/// ## Arguments
+/// * `leaderboard` - The leaderboard
+/// * `days` - The number of unlocked days
+///
+/// ## Returns
+/// * `String` - The table
+pub fn render_table(leaderboard: &Leaderboard, days: u8) -> String {
+    let rank_width = leaderboard.members.len().to_string().len() + 1;
+    let units = (1..=days)
+        .map(|day| char::from(b'0' + day % 10))
+        .collect::<String>();
+
+    // The day numbers are written vertically above the stars
+    let mut table = String::new();
+    if days >= 10 {
+        let tens = (1..=days)
+            .map(|day| {
+                if day < 10 {
+                    ' '
+                } else {
+                    char::from(b'0' + day / 10)
+                }
+            })
+            .collect::<String>();
+        table.push_str(&format!("{}{}\n", " ".repeat(rank_width + 15), tens));
+    }
+    table.push_str(&format!(
+        "{:>width$} {:>6} {:>5}  {}  Name\n",
+        "",
+        "Score",
+        "Stars",
+        units,
+        width = rank_width
+    ));
+
+    for (rank, member) in leaderboard.members.iter().enumerate() {
+        let stars = (1..=days)
+            .map(|day| match member.completions.get(&day) {
+                Some([_, Some(_)]) => '*',
+                Some([Some(_), None]) => '+',
+                _ => '.',
+            })
+            .collect::<String>();
+
+        table.push_str(&format!(
+            "{:>width$} {:>6} {:>5}  {}  {}\n",
+            format!("{})", rank + 1),
+            member.local_score,
+            member.stars,
+            stars,
+            member.display_name(),
+            width = rank_width
+        ));
+    }
+
+    // Only the days someone has started, to keep the lines short early in the event
+    let started = leaderboard
+        .members
+        .iter()
+        .filter_map(|member| member.completions.keys().max())
+        .max()
+        .map_or(0, |day| (*day).min(days));
+
+    table.push_str("\nCompletion times, since the unlock (`+` when only part 1 is done):\n");
+    table.push_str(&format!("{:>width$}", "", width = rank_width));
+    for day in 1..=started {
+        table.push_str(&format!(" {:>6}", day));
+    }
+    table.push_str("  Name\n");
+
+    for (rank, member) in leaderboard.members.iter().enumerate() {
+        table.push_str(&format!(
+            "{:>width$}",
+            format!("{})", rank + 1),
+            width = rank_width
+        ));
+
+        for day in 1..=started {
+            let cell = match (
+                member.solve_time(leaderboard.year, day, 1),
+                member.solve_time(leaderboard.year, day, 2),
+            ) {
+                (_, Some(time)) => format_short(time),
+                (Some(time), None) => format!("{}+", format_short(time)),
+                (None, None) => "-".to_string(),
+            };
+            table.push_str(&format!(" {:>6}", cell));
+        }
+
+        table.push_str(&format!("  {}\n", member.display_name()));
+    }
+
+    table
+}
+
+///
+/// # render_day
+/// Renders the order in which the members completed each part of a day.
+///
+/// ## Arguments
+/// * `leaderboard` - The leaderboard
+/// * `day` - The day of the puzzle
+///
+/// ## Returns
+/// * `String` - The rankings of both parts
+pub fn render_day(leaderboard: &Leaderboard, day: u8) -> String {
+    let mut rendered = format!("Day {} of {}\n", day, leaderboard.year);
+
+    for part in 1..=2 {
+        let mut finishers = leaderboard
+            .members
+            .iter()
+            .filter_map(|member| Some((member.solve_time(leaderboard.year, day, part)?, member)))
+            .collect::<Vec<_>>();
+        finishers.sort_by_key(|(time, member)| (*time, member.id));
+
+        rendered.push_str(&format!("\nPart {}\n", part));
+        if finishers.is_empty() {
+            rendered.push_str("  Nobody yet\n");
+        }
+
+        let rank_width = finishers.len().to_string().len() + 1;
+        for (rank, (time, member)) in finishers.iter().enumerate() {
+            rendered.push_str(&format!(
+                "  {:>width$} {:>12}  {}\n",
+                format!("{})", rank + 1),
+                format_duration(*time),
+                member.display_name(),
+                width = rank_width
+            ));
+        }
+    }
+
+    rendered
+}
+
+///
+/// # to_json
+/// Returns the members of a leaderboard as JSON, see src/output.rs.
+///
+/// ## Arguments
+/// * `leaderboard` - The leaderboard
+/// * `day` - Only keeps the completions of the given day
+///
+/// ## Returns
+/// * `Value` - The members in the order of the leaderboard, with the unix timestamps of their
+///   completions and the times since the unlocks in seconds
+pub fn to_json(leaderboard: &Leaderboard, day: Option<u8>) -> Value {
+    let year = leaderboard.year;
+
+    leaderboard
+        .members
+        .iter()
+        .enumerate()
+        .map(|(rank, member)| {
+            let completions = member
+                .completions
+                .iter()
+                .filter(|(completed, _)| day.is_none_or(|day| day == **completed))
+                .map(|(completed, parts)| {
+                    let parts = (1..=2)
+                        .zip(parts)
+                        .map(|(part, timestamp)| {
+                            json!({
+                                "part": part,
+                                "timestamp": timestamp,
+                                "seconds": member
+                                    .solve_time(year, *completed, part)
+                                    .map(|time| time.as_secs()),
+                            })
+                        })
+                        .collect::<Vec<_>>();
+
+                    (completed.to_string(), Value::from(parts))
+                })
+                .collect::<serde_json::Map<_, _>>();
+
+            json!({
+                "rank": rank + 1,
+                "id": member.id,
+                "name": member.name,
+                "local_score": member.local_score,
+                "stars": member.stars,
+                "last_star": member.last_star,
+                "completions": completions,
+            })
+        })
+        .collect()
+}
+
+///
+/// # format_short
+/// Formats a solve time for the narrow cells of the completion times.
+///
+/// ## Arguments
+/// * `time` - The time since the unlock
+///
+/// ## Returns
+/// * `String` - `H:MM` within the first day, the number of days after that, e.g. `3d`
+pub fn format_short(time: Duration) -> String {
+    let minutes = time.as_secs() / 60;
+
+    match minutes / (24 * 60) {
+        0 => format!("{}:{:02}", minutes / 60, minutes % 60),
+        days => format!("{}d", days),
+    }
+}
+
+// Tests ==================================================================================== Tests
+#[cfg(test)]
+pub const FIXTURE: &str = r#"{
+    "event": "2023",
+    "owner_id": 1,
+    "members": {
+        "1": {
+            "id": 1, "name": "Alice", "local_score": 10, "stars": 3, "last_star_ts": 1701500000,
+            "completion_day_level": {
+                "1": {"1": {"get_star_ts": 1701407100, "star_index": 0},
+                      "2": {"get_star_ts": 1701407400, "star_index": 1}},
+                "2": {"1": {"get_star_ts": "1701500000", "star_index": 2}}
+            }
+        },
+        "2": {
+            "id": 2, "name": null, "local_score": 10, "stars": 3, "last_star_ts": 1701493620,
+            "completion_day_level": {
+                "1": {"1": {"get_star_ts": 1701407000, "star_index": 0},
+                      "2": {"get_star_ts": 1701493600, "star_index": 1}},
+                "2": {"1": {"get_star_ts": 1701493620, "star_index": 2}}
+            }
+        },
+        "3": {"id": 3, "name": "Carol", "local_score": 0, "stars": 0, "last_star_ts": 0,
+              "completion_day_level": {}}
+    }
+}"#;
+
+#[test]
+fn test_parse() {
+    let leaderboard = parse(FIXTURE).unwrap();
+
+    assert_eq!(leaderboard.year, 2023);
+    // Same score and stars, the anonymous user got their last star first
+    assert_eq!(
+        leaderboard
+            .members
+            .iter()
+            .map(Member::display_name)
+            .collect::<Vec<_>>(),
+        ["(anonymous user #2)", "Alice", "Carol"]
+    );
+    // Day 1 of 2023 unlocked at 1701406800
+    assert_eq!(
+        leaderboard.members[1].solve_time(2023, 1, 2),
+        Some(Duration::from_secs(600))
+    );
+    assert_eq!(leaderboard.members[1].solve_time(2023, 2, 2), None);
+    assert!(parse("<html>Log in</html>").is_err());
+}
+
+#[test]
+fn test_to_json() {
+    let members = to_json(&parse(FIXTURE).unwrap(), Some(2));
+
+    assert_eq!(members[1]["name"], "Alice");
+    assert_eq!(
+        members[1]["completions"],
+        json!({ "2": [
+            { "part": 1, "timestamp": 1701500000, "seconds": 6800 },
+            { "part": 2, "timestamp": null, "seconds": null },
+        ] })
+    );
+    assert_eq!(members[2]["completions"], json!({}));
+}
+
+#[test]
+fn test_render() {
+    let leaderboard = parse(FIXTURE).unwrap();
+
+    assert_eq!(
+        render_table(&leaderboard, 2),
+        "    Score Stars  12  Name\n\
+         1)     10     3  *+  (anonymous user #2)\n\
+         2)     10     3  *+  Alice\n\
+         3)      0     0  ..  Carol\n\
+         \n\
+         Completion times, since the unlock (`+` when only part 1 is done):\n  \
+         \x20     1      2  Name\n\
+         1)     1d  0:07+  (anonymous user #2)\n\
+         2)   0:10  1:53+  Alice\n\
+         3)      -      -  Carol\n"
+    );
+    assert_eq!(
+        render_day(&leaderboard, 1),
+        "Day 1 of 2023\n\
+         \n\
+         Part 1\n  \
+         1)     00:03:20  (anonymous user #2)\n  \
+         2)     00:05:00  Alice\n\
+         \n\
+         Part 2\n  \
+         1)     00:10:00  Alice\n  \
+         2)  1d 00:06:40  (anonymous user #2)\n"
+    );
+}
+
+/*
+ * End of file src/leaderboard.rs
+ */
@@ -0,0 +1,306 @@
+///
+/// # src/ledger.rs
+/// Contains the ledger of the accepted answers of a year, stored in the `answers` file of the
+/// layout (`data/year_{year}/answers.toml` by default), one table per profile and day:
+/// ```toml
+/// [default.day_01]
+/// part_1 = "1234"
+/// part_2 = "5678"
+///
+/// [partner.day_01]
+/// part_1 = "4321"
+/// ```
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use toml_edit::{value, DocumentMut, Item, Table};
+
+use std::{
+    fs::{read_to_string, write},
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use crate::file_utils::create_folder;
+use crate::layout::Layout;
+use crate::profile::Profile;
+
+// Variables  =========================================================================== Variables
+/// The accepted answers of a year.
+pub struct Ledger {
+    /// The content of the answers file
+    doc: DocumentMut,
+    /// The path of the answers file
+    path: PathBuf,
+}
+
+// Functions  =========================================================================== Functions
+impl Ledger {
+    ///
+    /// # load
+    /// Loads the ledger of a year, an empty one if the answers file does not exist yet.
+    ///
+    /// ## Arguments
+    /// * `caller` - The folder from which the program was called
+    /// * `layout` - The layout of the project
+    /// * `year` - The year of the Advent of Code challenge
+    ///
+    /// ## Returns
+    /// * `Result<Ledger, String>` - The ledger
+    pub fn load(caller: &Path, layout: &Layout, year: u16) -> Result<Ledger, String> {
+        let path = caller.join(layout.answers(year));
+
+        Ok(Ledger {
+            doc: load(&path)?,
+            path,
+        })
+    }
+
+    ///
+    /// # answer
+    /// Returns the accepted answer of a part.
+    ///
+    /// ## Arguments
+    /// * `profile` - The profile of the answer
+    /// * `day` - The day of the Advent of Code challenge
+    /// * `part` - The part of the puzzle, 1 or 2
+    ///
+    /// ## Returns
+    /// * `Option<String>` - The accepted answer, `None` if there is none yet
+    pub fn answer(&self, profile: &Profile, day: u8, part: u8) -> Option<String> {
+        let answer = self
+            .doc
+            .get(profile.name())?
+            .get(day_key(day))?
+            .get(format!("part_{}", part))?;
+
+        match answer {
+            Item::Value(value) => value
+                .as_str()
+                .map(str::to_string)
+                .or_else(|| value.as_integer().map(|answer| answer.to_string())),
+            _ => None,
+        }
+    }
+
+    ///
+    /// # set_answer
+    /// Records the accepted answer of a part.
+    ///
+    /// ## Arguments
+    /// * `profile` - The profile of the answer
+    /// * `day` - The day of the Advent of Code challenge
+    /// * `part` - The part of the puzzle, 1 or 2
+    /// * `answer` - The accepted answer
+    ///
+    /// ## Returns
+    /// * `Result<(), String>` - An error if the answers file holds something else for the day
+    pub fn set_answer(
+        &mut self,
+        profile: &Profile,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<(), String> {
+        day_table(&mut self.doc, &self.path, profile, day)?[&format!("part_{}", part)] =
+            value(answer);
+
+        Ok(())
+    }
+
+    ///
+    /// # save
+    /// Writes the ledger to the answers file.
+    ///
+    /// ## Returns
+    /// * `Result<(), String>` - An error if the file could not be written
+    pub fn save(&self) -> Result<(), String> {
+        save(&self.doc, &self.path)
+    }
+}
+
+///
+/// # day_table
+/// Returns the table of a day of a profile, creating it if needed.
+/// The profile tables are implicit, so that the file only shows `[profile.day_NN]` headers.
+///
+/// ## Arguments
+/// * `doc` - The document holding one table per profile
+/// * `profile` - The profile
+/// * `path` - The path of the file of the document, for the errors
+/// * `profile` - The profile
+/// * `day` - The day of the Advent of Code challenge
+///
+/// ## Returns
+/// * `Result<&mut Table, String>` - The table of the day, an error if the file holds another
+///   value, such as an inline table, in its place
+pub fn day_table<'a>(
+    doc: &'a mut DocumentMut,
+    path: &Path,
+    profile: &Profile,
+    day: u8,
+) -> Result<&'a mut Table, String> {
+    let key = day_key(day);
+    let invalid = || {
+        format!(
+            "Invalid {}: '{}.{}' should be a [{}.{}] table.",
+            path.display(),
+            profile.name(),
+            key,
+            profile.name(),
+            key
+        )
+    };
+
+    doc.entry(profile.name())
+        .or_insert_with(|| {
+            let mut table = Table::new();
+            table.set_implicit(true);
+            Item::Table(table)
+        })
+        .as_table_mut()
+        .ok_or_else(invalid)?
+        .entry(&key)
+        .or_insert_with(|| Item::Table(Table::new()))
+        .as_table_mut()
+        .ok_or_else(invalid)
+}
+
+///
+/// # load_next_to_answers
+/// Reads a document stored next to the answers file of a year, e.g. its benchmarks.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `layout` - The layout of the project
+/// * `year` - The year of the Advent of Code challenge
+/// * `file` - The name of the file, in the folder of the answers file
+///
+/// ## Returns
+/// * `Result<(DocumentMut, PathBuf), String>` - The document and the path of its file
+pub fn load_next_to_answers(
+    caller: &Path,
+    layout: &Layout,
+    year: u16,
+    file: &str,
+) -> Result<(DocumentMut, PathBuf), String> {
+    let path = caller.join(layout.answers(year)).with_file_name(file);
+
+    Ok((load(&path)?, path))
+}
+
+///
+/// # load
+/// Reads a document with the data of a year, an empty one if its file does not exist yet.
+///
+/// ## Arguments
+/// * `path` - The path of the file
+///
+/// ## Returns
+/// * `Result<DocumentMut, String>` - The document, an error if the file is unreadable or invalid
+fn load(path: &Path) -> Result<DocumentMut, String> {
+    match read_to_string(path) {
+        Ok(content) => content
+            .parse::<DocumentMut>()
+            .map_err(|e| format!("Invalid {}: {}", path.display(), e)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(DocumentMut::new()),
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+///
+/// # save
+/// Writes a document next to the other data of the year, creating its folder.
+///
+/// ## Arguments
+/// * `doc` - The document
+/// * `path` - The path of the file
+///
+/// ## Returns
+/// * `Result<(), String>` - An error if the file could not be written
+pub fn save(doc: &DocumentMut, path: &Path) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        create_folder(parent);
+    }
+
+    write(path, doc.to_string()).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+///
+/// # day_key
+/// Returns the key of the table of a day.
+///
+/// ## Arguments
+/// * `day` - The day of the Advent of Code challenge
+///
+/// ## Returns
+/// * `String` - The key, e.g. `day_01`
+pub fn day_key(day: u8) -> String {
+    format!("day_{:02}", day)
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_answer() {
+    let ledger = Ledger {
+        doc: "[default.day_01]\npart_1 = \"1234\"\npart_2 = 5678\n\n[partner.day_01]\npart_1 = \"42\"\n"
+            .parse()
+            .unwrap(),
+        path: PathBuf::new(),
+    };
+    let partner = "partner".parse::<Profile>().unwrap();
+
+    assert_eq!(
+        ledger.answer(&Profile::default(), 1, 1).as_deref(),
+        Some("1234")
+    );
+    assert_eq!(
+        ledger.answer(&Profile::default(), 1, 2).as_deref(),
+        Some("5678")
+    );
+    assert_eq!(ledger.answer(&partner, 1, 1).as_deref(), Some("42"));
+    assert_eq!(ledger.answer(&partner, 1, 2), None);
+    assert_eq!(ledger.answer(&Profile::default(), 2, 1), None);
+}
+
+#[test]
+fn test_set_answer() {
+    let mut ledger = Ledger {
+        doc: DocumentMut::new(),
+        path: PathBuf::new(),
+    };
+
+    ledger.set_answer(&Profile::default(), 3, 1, "42").unwrap();
+    ledger
+        .set_answer(&Profile::default(), 3, 2, "6,4,5")
+        .unwrap();
+
+    assert_eq!(
+        ledger.doc.to_string(),
+        "[default.day_03]\npart_1 = \"42\"\npart_2 = \"6,4,5\"\n"
+    );
+
+    for doc in [
+        "default = { day_01 = { part_1 = \"1\" } }\n",
+        "[default]\nday_01 = { part_1 = \"1\" }\n",
+        "default.day_01 = \"x\"\n",
+    ] {
+        let mut ledger = Ledger {
+            doc: doc.parse().unwrap(),
+            path: PathBuf::from("answers.toml"),
+        };
+
+        assert_eq!(
+            ledger.set_answer(&Profile::default(), 1, 2, "42"),
+            Err(
+                "Invalid answers.toml: 'default.day_01' should be a [default.day_01] table.".into()
+            )
+        );
+    }
+}
+
+/*
+ * End of file src/ledger.rs
+ */
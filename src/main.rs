@@ -6,16 +6,69 @@
 /// Tom Planche <github.com/tomPlanche>
 ///
 // Imports  ==============================================================================  Imports
-use chrono::Datelike;
-use clap::{Parser, Subcommand};
-use toml_edit::{ArrayOfTables, DocumentMut, Table};
+use chrono::{DateTime, Datelike, FixedOffset, SecondsFormat};
+use clap::{Args, Parser, Subcommand};
+use serde_json::json;
 
-use std::{fs, path::PathBuf, process::Command};
+use std::{
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::{Duration, Instant},
+};
 
+mod analytics;
+mod auth;
+mod benchmarks;
+mod calendar;
+mod config;
+mod countdown;
+mod doctor;
+mod download;
+mod export;
 mod file_utils;
+mod http;
+mod import;
+mod junit;
+mod layout;
+mod leaderboard;
+mod ledger;
+mod mock;
+mod output;
+mod profile;
+mod puzzle;
+mod readme;
+mod runner;
+mod site;
+mod spec;
+mod submit;
+mod sync;
+mod template;
+mod timing;
+mod verify;
+mod watch;
+use crate::analytics::ReportFormat;
+use crate::auth::SessionSource;
+use crate::benchmarks::Benchmarks;
+use crate::config::Config;
+use crate::download::Download;
+use crate::export::{ExportFormat, ExportOptions};
 use crate::file_utils::{
     check_file_struct_integrity_year, check_global_file_struct_integrity, init_folders_and_files,
 };
+use crate::http::Client;
+use crate::import::ImportFormat;
+use crate::junit::{CaseResult, TestCase, TestSuite};
+use crate::ledger::Ledger;
+use crate::mock::MockServer;
+use crate::output::OutputFormat;
+use crate::profile::{profiles_with_input, Profile};
+use crate::runner::{format_bytes, update_cargo_toml, Limits, Outcome};
+use crate::spec::{DaySelection, DaySpec, YearSelection, YearSpec};
+use crate::submit::Verdict;
+use crate::template::TemplateName;
+use crate::timing::Timings;
+use crate::verify::Verification;
 
 // Variables  =========================================================================== Variables
 #[derive(Parser)]
@@ -30,6 +83,10 @@ struct Cli {
     /// Commands
     #[command(subcommand)]
     command: Option<Commands>,
+
+    /// The account profile to use, with its own session and inputs
+    #[arg(long, global = true, value_parser = parse_profile, default_value = "default", env = "AOC_PROFILE")]
+    profile: Profile,
 }
 
 /// Init subcommand
@@ -39,26 +96,44 @@ struct Cli {
 )]
 enum Commands {
     /// Init subcommand
-    /// Creates all the folders and files needed for the Advent of Code challenges of the given year.
-    /// The year is 2015 by default.
+    /// Creates all the folders and files needed for the Advent of Code challenges of the given years.
+    /// The year is the latest event by default.
     Init {
-        /// The year of the Advent of Code challenge
-        #[arg(short, long)]
-        year: Option<u16>,
+        /// The years of the Advent of Code challenge, e.g. `2015..2018` or `2016,2020`
+        #[arg(short, long, value_parser = parse_years, default_value = "current", env = "AOC_YEAR")]
+        year: YearSelection,
+
+        /// Also creates the files and example folders of every day
+        #[arg(long, default_value = "false")]
+        with_days: bool,
     },
 
     /// Create subcommand
-    /// Creates all needed files for the given day of the given year.
-    /// The year is by default the one in the .cargo/config.toml file.
-    /// The day is the current day by default.
+    /// Creates all needed files for the given days of the given years.
+    /// Existing files are never replaced, unless `--force` is passed.
+    /// The year is by default the latest event, or the `AOC_YEAR` environment variable.
+    /// The day is by default the latest unlocked one, or the `AOC_DAY` environment variable.
     Create {
-        /// The day of the Advent of Code challenge
-        #[arg(short, long, value_parser = parse_day, default_value = env!("AOC_DAY"))]
-        day: Option<u8>,
+        /// The days of the Advent of Code challenge, e.g. `1..=5` or `3,7,12`
+        #[arg(short, long, value_parser = parse_days, default_value = "latest", env = "AOC_DAY")]
+        day: DaySelection,
 
-        /// The year of the Advent of Code challenge
-        #[arg(short, long, value_parser = parse_year, default_value = env!("AOC_YEAR"))]
-        year: Option<u16>,
+        /// The years of the Advent of Code challenge, e.g. `2015..2018` or `2016,2020`
+        #[arg(short, long, value_parser = parse_years, default_value = "current", env = "AOC_YEAR")]
+        year: YearSelection,
+
+        /// Replaces existing solution files, saving a `.bak` copy of each first
+        #[arg(long, default_value = "false")]
+        force: bool,
+
+        /// Waits for the next puzzle to unlock, then creates its files and downloads its input
+        /// and description. The day and year are those of the next puzzle.
+        #[arg(long, default_value = "false")]
+        wait: bool,
+
+        /// Opens the solution file in the editor given by `VISUAL` or `EDITOR`
+        #[arg(long, default_value = "false")]
+        open: bool,
     },
 
     /// Solve subcommand
@@ -66,12 +141,12 @@ enum Commands {
     /// Compiles and runs the tests.
     Solve {
         /// The day of the Advent of Code challenge
-        #[arg(short, long, value_parser = parse_day, default_value = env!("AOC_DAY"))]
-        day: Option<u8>,
+        #[arg(short, long, value_parser = parse_day, default_value = "latest", env = "AOC_DAY")]
+        day: DaySpec,
 
         /// The year of the Advent of Code challenge
-        #[arg(short, long, value_parser = parse_year, default_value = env!("AOC_YEAR"))]
-        year: Option<u16>,
+        #[arg(short, long, value_parser = parse_year, default_value = "current", env = "AOC_YEAR")]
+        year: YearSpec,
 
         /// Problem part 2 argument
         /// If passed, the 'src/main.rs' file will be changed to run both parts of the given day.
@@ -80,112 +155,661 @@ enum Commands {
     },
 
     /// Test subcommand
-    /// Runs the tests for the given day and year
+    /// Runs the tests for the given days and years
     Test {
+        /// The days of the Advent of Code challenge, e.g. `1..=5` or `3,7,12`
+        #[arg(short, long, value_parser = parse_days, default_value = "latest", env = "AOC_DAY")]
+        day: DaySelection,
+
+        /// The years of the Advent of Code challenge, e.g. `2015..2018` or `2016,2020`
+        #[arg(short, long, value_parser = parse_years, default_value = "current", env = "AOC_YEAR")]
+        year: YearSelection,
+
+        /// Writes a JUnit XML report to the given file, for CI servers
+        #[arg(long, value_name = "PATH")]
+        junit: Option<PathBuf>,
+    },
+
+    /// Download subcommand
+    /// Downloads the inputs of the given days for the selected profile, and their puzzle
+    /// descriptions.
+    /// An input already downloaded is never fetched again.
+    Download {
+        /// The days of the Advent of Code challenge, e.g. `1..=5` or `3,7,12`
+        #[arg(short, long, value_parser = parse_days, default_value = "latest", env = "AOC_DAY")]
+        day: DaySelection,
+
+        /// The years of the Advent of Code challenge, e.g. `2015..2018` or `2016,2020`
+        #[arg(short, long, value_parser = parse_years, default_value = "current", env = "AOC_YEAR")]
+        year: YearSelection,
+
+        /// Downloads every unlocked day of the years that is not downloaded yet
+        #[arg(long, default_value = "false")]
+        all: bool,
+
+        /// The minimum delay between two requests in milliseconds, `delay_ms` of aoc.toml by default
+        #[arg(long)]
+        delay: Option<u64>,
+    },
+
+    /// Leaderboard subcommand
+    /// Shows a private leaderboard, fetched at most once every 15 minutes.
+    Leaderboard {
+        /// The id of the private leaderboard, the number at the end of its URL
+        id: u64,
+
+        /// The year of the Advent of Code challenge
+        #[arg(short, long, value_parser = parse_year, default_value = "current", env = "AOC_YEAR")]
+        year: YearSpec,
+
+        /// Shows the order in which the members completed both parts of the given day instead
+        #[arg(short, long, value_parser = parse_day, conflicts_with = "report")]
+        day: Option<DaySpec>,
+
+        /// Shows the analytics of every member instead: the time between both parts of each day,
+        /// the median solve time, the longest streak and the days solved after 24h
+        #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "text")]
+        report: Option<ReportFormat>,
+
+        /// Writes the report to the given file instead of the standard output
+        #[arg(short, long, requires = "report")]
+        out: Option<PathBuf>,
+
+        /// The format of the output, `json` for a versioned document to parse
+        #[arg(long, value_enum, default_value = "text", conflicts_with = "report")]
+        format: OutputFormat,
+    },
+
+    /// Run subcommand
+    /// Compiles the solutions of the given days in release mode and runs their parts.
+    Run {
+        /// The days of the Advent of Code challenge, e.g. `1..=5` or `3,7,12`
+        #[arg(short, long, value_parser = parse_days, default_value = "latest", env = "AOC_DAY")]
+        day: DaySelection,
+
+        /// The years of the Advent of Code challenge, e.g. `2015..2018` or `2016,2020`
+        #[arg(short, long, value_parser = parse_years, default_value = "current", env = "AOC_YEAR")]
+        year: YearSelection,
+
+        /// Only runs the given part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Runs the solution on the input of every profile instead of the selected one
+        #[arg(long, default_value = "false")]
+        all_profiles: bool,
+
+        /// The format of the output, `json` for a versioned document to parse
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+
+        /// Counts the allocations of each part, compiling the solution with the `count-alloc`
+        /// feature
+        #[arg(long, default_value = "false")]
+        alloc: bool,
+
+        #[command(flatten)]
+        limits: LimitArgs,
+    },
+
+    /// Bench subcommand
+    /// Compiles the solutions of the given days in release mode and runs their parts several
+    /// times, showing the fastest, median and mean durations.
+    Bench {
+        /// The days of the Advent of Code challenge, e.g. `1..=5` or `3,7,12`
+        #[arg(short, long, value_parser = parse_days, default_value = "latest", env = "AOC_DAY")]
+        day: DaySelection,
+
+        /// The years of the Advent of Code challenge, e.g. `2015..2018` or `2016,2020`
+        #[arg(short, long, value_parser = parse_years, default_value = "current", env = "AOC_YEAR")]
+        year: YearSelection,
+
+        /// Only benchmarks the given part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// The number of runs of each part
+        #[arg(short, long, default_value = "10", value_parser = clap::value_parser!(u32).range(1..))]
+        runs: u32,
+
+        /// The format of the output, `json` for a versioned document to parse
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+
+        /// Counts the allocations of each part, compiling the solution with the `count-alloc`
+        /// feature
+        #[arg(long, default_value = "false")]
+        alloc: bool,
+
+        #[command(flatten)]
+        limits: LimitArgs,
+    },
+
+    /// Watch subcommand
+    /// Watches the solutions of the given days, their inputs, their examples and the shared
+    /// modules, and runs the tests and the solutions concerned again on every change.
+    Watch {
+        /// The days of the Advent of Code challenge, e.g. `1..=5` or `3,7,12`
+        #[arg(short, long, value_parser = parse_days, default_value = "latest", env = "AOC_DAY")]
+        day: DaySelection,
+
+        /// The years of the Advent of Code challenge, e.g. `2015..2018` or `2016,2020`
+        #[arg(short, long, value_parser = parse_years, default_value = "current", env = "AOC_YEAR")]
+        year: YearSelection,
+    },
+
+    /// Submit subcommand
+    /// Submits the answer of a part, the one printed by the solution if not given. An accepted
+    /// answer is recorded in the answers file, with the time taken to solve the part.
+    Submit {
+        /// The answer to submit, the last line printed by the solution if not given
+        answer: Option<String>,
+
         /// The day of the Advent of Code challenge
-        #[arg(short, long, value_parser = parse_day, default_value = env!("AOC_DAY"))]
-        day: Option<u8>,
+        #[arg(short, long, value_parser = parse_day, default_value = "latest", env = "AOC_DAY")]
+        day: DaySpec,
+
+        /// The year of the Advent of Code challenge
+        #[arg(short, long, value_parser = parse_year, default_value = "current", env = "AOC_YEAR")]
+        year: YearSpec,
+
+        /// The part to submit, the first one without an accepted answer by default
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+    },
 
+    /// Status subcommand
+    /// Shows the progress of a year: the accepted answers of every unlocked day and the time
+    /// taken to solve each part since `aoc create`.
+    Status {
         /// The year of the Advent of Code challenge
-        #[arg(short, long, value_parser = parse_year, default_value = env!("AOC_YEAR"))]
-        year: Option<u16>,
+        #[arg(short, long, value_parser = parse_year, default_value = "current", env = "AOC_YEAR")]
+        year: YearSpec,
+
+        /// The format of the output, `json` for a versioned document to parse
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+
+    /// Sync subcommand
+    /// Reads the stars of the logged in user from the website and backfills the answers file of
+    /// every year with the answers of the solved puzzles.
+    Sync {
+        /// Only synchronises the given years, e.g. `2015..2018` or `2016,2020`
+        #[arg(short, long, value_parser = parse_years)]
+        year: Option<YearSelection>,
+    },
+
+    /// Verify subcommand
+    /// Runs the solutions of the given days and compares their answers with the accepted ones,
+    /// stored in the answers file of the year.
+    Verify {
+        /// The days of the Advent of Code challenge, e.g. `1..=5` or `3,7,12`
+        #[arg(short, long, value_parser = parse_days, default_value = "latest", env = "AOC_DAY")]
+        day: DaySelection,
+
+        /// The years of the Advent of Code challenge, e.g. `2015..2018` or `2016,2020`
+        #[arg(short, long, value_parser = parse_years, default_value = "current", env = "AOC_YEAR")]
+        year: YearSelection,
+
+        /// Verifies the answers on the input of every profile instead of the selected one
+        #[arg(long, default_value = "false")]
+        all_profiles: bool,
+
+        /// The format of the output, `json` for a versioned document to parse
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+
+        /// Writes a JUnit XML report to the given file, for CI servers
+        #[arg(long, value_name = "PATH")]
+        junit: Option<PathBuf>,
+
+        #[command(flatten)]
+        limits: LimitArgs,
+    },
+
+    /// Import subcommand
+    /// Imports the solutions, inputs and puzzles of a repository written for another tool.
+    /// The solutions are wrapped into the `response_part_1`/`response_part_2` functions.
+    Import {
+        /// The layout of the repository to import
+        #[arg(long, value_enum)]
+        from: ImportFormat,
+
+        /// The root of the repository to import, the current folder by default
+        #[arg(long)]
+        path: Option<PathBuf>,
+
+        /// The year of the solutions, detected from the repository by default
+        #[arg(short, long, value_parser = parse_year)]
+        year: Option<YearSpec>,
+
+        /// Moves the files instead of copying them
+        #[arg(long = "move", default_value = "false")]
+        move_files: bool,
+    },
+
+    /// Export subcommand
+    /// Exports the solutions of a year to a self-contained Cargo project, which builds and runs
+    /// without this tool. Puzzle inputs and descriptions are left out by default.
+    Export {
+        /// The year to export
+        #[arg(short, long, value_parser = parse_year, default_value = "current", env = "AOC_YEAR")]
+        year: YearSpec,
+
+        /// The layout of the exported project
+        #[arg(short, long, value_enum, default_value = "standalone-crate")]
+        format: ExportFormat,
+
+        /// The folder of the exported project
+        #[arg(short, long)]
+        out: PathBuf,
+
+        /// Copies the puzzle inputs into the exported project
+        #[arg(long, default_value = "false")]
+        include_inputs: bool,
+
+        /// Copies the puzzle descriptions into the exported project
+        #[arg(long, default_value = "false")]
+        include_puzzles: bool,
+
+        /// Exports into a folder that is not empty
+        #[arg(long, default_value = "false")]
+        force: bool,
+    },
+
+    /// Auth subcommand
+    /// Manages the session cookie used to authenticate on the Advent of Code website.
+    /// The `AOC_SESSION` environment variable takes precedence over the stored session.
+    Auth {
+        #[command(subcommand)]
+        action: AuthAction,
+    },
+
+    /// Readme subcommand
+    /// Regenerates the progress section of the README of the project, between its
+    /// `<!-- aoc:progress:start -->` and `<!-- aoc:progress:end -->` markers, and the star badge
+    /// of every year. The runtimes are the ones of the latest `aoc bench` of each day.
+    Readme {
+        /// Only shows the given years, e.g. `2015..2018` or `2016,2020`, every year with a
+        /// solution by default
+        #[arg(short, long, value_parser = parse_years)]
+        year: Option<YearSelection>,
+
+        /// The folder the badges are written to, relative to the project root
+        #[arg(long, default_value = "badges")]
+        badges: PathBuf,
+    },
+
+    /// Site subcommand
+    /// Renders the stored puzzles, the solutions with syntax highlighting, the latest benchmarks
+    /// and the stars of every day into a static site. Inputs are never published.
+    Site {
+        /// Only shows the given years, e.g. `2015..2018` or `2016,2020`, every year with a
+        /// solution or a puzzle by default
+        #[arg(short, long, value_parser = parse_years)]
+        year: Option<YearSelection>,
+
+        /// The folder the site is written to
+        #[arg(short, long, default_value = "public")]
+        out: PathBuf,
+    },
+
+    /// Doctor subcommand
+    /// Checks the toolchain, the configuration, the structure of the project and the session.
+    Doctor {
+        /// The format of the output, `json` for a versioned document to parse
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+    },
+
+    /// Mock server subcommand
+    /// Serves a mock of the Advent of Code website from a fixtures folder, to test the network
+    /// commands offline. Point them at it with `AOC_BASE_URL=http://localhost:<port>/`.
+    MockServer {
+        /// The fixtures folder, see the documentation of 'src/mock.rs' for its layout
+        #[arg(long, default_value = "fixtures")]
+        fixtures: PathBuf,
+
+        /// The port to listen on
+        #[arg(short, long, default_value = "3000")]
+        port: u16,
+
+        /// The number of seconds to wait after a wrong answer
+        #[arg(long, default_value = "60")]
+        cooldown: u64,
+    },
+
+    /// Template subcommand
+    /// Inspects or restores the templates used to generate the files of a day.
+    /// User templates live in the '.aoc/templates' folder and override the built-in ones.
+    Template {
+        #[command(subcommand)]
+        action: TemplateAction,
+    },
+}
+
+/// The limits of the runs of the solutions, overriding the `[run]` table of `aoc.toml`
+#[derive(Args)]
+struct LimitArgs {
+    /// Kills a part running longer than the given number of seconds, 0 to disable the timeout
+    #[arg(long, value_parser = parse_timeout)]
+    timeout: Option<Duration>,
+
+    /// Limits the memory of a part to the given number of MiB, 0 to disable the limit (Linux only)
+    #[arg(long, value_parser = parse_memory_limit)]
+    memory_limit: Option<u64>,
+}
+
+impl LimitArgs {
+    ///
+    /// # apply
+    /// Overrides the configured limits with the given arguments.
+    ///
+    /// ## Arguments
+    /// * `limits` - The limits of the configuration
+    ///
+    /// ## Returns
+    /// * `Limits` - The limits of the runs
+    fn apply(&self, mut limits: Limits) -> Limits {
+        if let Some(timeout) = self.timeout {
+            limits.timeout = Some(timeout).filter(|timeout| !timeout.is_zero());
+        }
+        if let Some(memory) = self.memory_limit {
+            limits.memory = Some(memory).filter(|memory| *memory > 0);
+        }
+
+        limits
+    }
+}
+
+/// Auth actions
+#[derive(Subcommand)]
+enum AuthAction {
+    /// Checks and stores the session cookie, in a file only readable by the user
+    Login {
+        /// The value of the 'session' cookie of adventofcode.com, read from the standard input
+        /// if not given
+        session: Option<String>,
+    },
+
+    /// Checks the session against the website and shows the logged in user
+    Status,
+
+    /// Deletes the stored session cookie
+    Logout,
+}
+
+/// Template actions
+#[derive(Subcommand)]
+enum TemplateAction {
+    /// Prints the template currently used, the user one if it exists or the built-in one
+    Show {
+        /// The template to print
+        #[arg(value_enum, default_value = "day")]
+        name: TemplateName,
+    },
+
+    /// Restores the built-in template in the '.aoc/templates' folder, saving a customized one
+    /// to a `.bak` file first
+    Reset {
+        /// The template to restore, all of them if not given
+        #[arg(value_enum)]
+        name: Option<TemplateName>,
     },
 }
 // Functions  =========================================================================== Functions
 ///
 /// # parse_day
 /// Parses the day argument.
-/// The day should be between 1 and 25.
+/// The day should be between 1 and 25, or one of the relative specifications
+/// `today`, `latest`, `next`, `prev`, `+N` and `-N`.
 ///
 /// ## Arguments
 /// * `s` - The string to parse
 ///
 /// ## Returns
-/// * `Result<u8, std::num::ParseIntError>` - The parsed day
-fn parse_day(s: &str) -> Result<u8, String> {
-    let day = s.parse::<u8>().unwrap();
-    if day < 1 || day > 25 {
-        return Err("The day should be between 1 and 25.".parse().unwrap());
-    }
-    Ok(day)
+/// * `Result<DaySpec, String>` - The parsed day specification
+fn parse_day(s: &str) -> Result<DaySpec, String> {
+    s.parse::<DaySpec>()
 }
 
 ///
 /// # parse_year
 /// Parses the year argument.
-/// The day should be between 2015 and the current year.
+/// The year should be between 2015 and the current year, or one of the relative
+/// specifications `current` and `last`.
 ///
 /// ## Arguments
 /// * `s` - The string to parse
 ///
 /// ## Returns
-/// * `Result<u16, std::num::ParseIntError>` - The parsed year
-fn parse_year(s: &str) -> Result<u16, String> {
-    let day = s.parse::<u16>().unwrap();
-    if day < 2015 || day > get_current_year() {
-        return Err("The year should be between 2015 and the current year"
-            .parse()
-            .unwrap());
-    }
-    Ok(day)
+/// * `Result<YearSpec, String>` - The parsed year specification
+fn parse_year(s: &str) -> Result<YearSpec, String> {
+    let year = s.parse::<YearSpec>()?;
+    check_year(&year)?;
+
+    Ok(year)
 }
 
 ///
-/// # get_current_year
-/// Returns the current year.
+/// # check_year
+/// Checks that a fixed year is between 2015 and the current year.
 ///
 /// ## Arguments
-/// * `()` - Nothing
+/// * `year` - The year
 ///
 /// ## Returns
-/// * `u16` - The current year
-fn get_current_year() -> u16 {
-    let now = chrono::Utc::now();
+/// * `Result<(), String>` - An error if the year is out of bounds
+fn check_year(year: &YearSpec) -> Result<(), String> {
+    if let YearSpec::Year(year) = year {
+        if !(calendar::FIRST_YEAR..=get_current_year(&calendar::now())).contains(year) {
+            return Err("The year should be between 2015 and the current year".to_string());
+        }
+    }
 
-    now.year() as u16
+    Ok(())
+}
+
+///
+/// # parse_days
+/// Parses a selection of days: a comma separated list of days and ranges of days,
+/// e.g. `1..=5`, `3,7,12` or `prev..=latest`.
+///
+/// ## Arguments
+/// * `s` - The string to parse
+///
+/// ## Returns
+/// * `Result<DaySelection, String>` - The parsed selection
+fn parse_days(s: &str) -> Result<DaySelection, String> {
+    s.parse::<DaySelection>()
 }
 
 ///
-/// # update_cargo_toml
+/// # parse_years
+/// Parses a selection of years: a comma separated list of years and ranges of years,
+/// e.g. `2015..2018` or `2016,last`.
 ///
-/// Updates the 'Cargo.toml' file by removing the existing [[bin]] sections and maybe adding a new one for the given day and year.
+/// ## Arguments
+/// * `s` - The string to parse
+///
+/// ## Returns
+/// * `Result<YearSelection, String>` - The parsed selection
+fn parse_years(s: &str) -> Result<YearSelection, String> {
+    let years = s.parse::<YearSelection>()?;
+    years.specs().try_for_each(check_year)?;
+
+    Ok(years)
+}
+
+///
+/// # parse_profile
+/// Parses the profile argument.
+/// The profile name should only contain letters, digits, '-' and '_'.
 ///
 /// ## Arguments
+/// * `s` - The string to parse
 ///
-/// * `caller` - The path of the caller
-/// * `day` - The day of the Advent of Code challenge
+/// ## Returns
+/// * `Result<Profile, String>` - The parsed profile
+fn parse_profile(s: &str) -> Result<Profile, String> {
+    s.parse::<Profile>()
+}
+
+///
+/// # selected_profiles
+/// Returns the profiles a day should be run on.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `config` - The configuration of the project
 /// * `year` - The year of the Advent of Code challenge
-/// * `add_bin` - If true, a new [[bin]] section will be added for the given day and year
+/// * `day` - The day of the Advent of Code challenge
+/// * `profile` - The selected profile
+/// * `all_profiles` - Whether every profile having an input should be used
 ///
 /// ## Returns
-/// * `Result<(), Box<dyn std::error::Error>>` - The result of the operation
-fn update_cargo_toml(
-    caller: &PathBuf,
-    day: u8,
+/// * `Vec<Profile>` - The profiles
+fn selected_profiles(
+    caller: &Path,
+    config: &Config,
     year: u16,
-    add_bin: bool,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let cargo_toml_path = caller.join("Cargo.toml");
-    let toml_content = fs::read_to_string(&cargo_toml_path)?;
-    let mut doc = toml_content.parse::<DocumentMut>()?;
-
-    // Remove existing [[bin]] sections
-    doc.remove("bin");
+    day: u8,
+    profile: &Profile,
+    all_profiles: bool,
+) -> Vec<Profile> {
+    if all_profiles {
+        profiles_with_input(caller, &config.layout, year, day)
+    } else {
+        vec![profile.clone()]
+    }
+}
 
-    if add_bin {
-        let desired_name = format!("day_{:02}_year_{}", day, year);
-        let mut bin_array = ArrayOfTables::new();
-        let mut bin_table = Table::new();
+///
+/// # download_day
+/// Downloads the puzzle description and the input of a day for the given profile, and prints
+/// what happened to each file.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `config` - The configuration of the project
+/// * `profile` - The profile whose session and input folder are used
+/// * `year` - The year of the Advent of Code challenge
+/// * `day` - The day of the Advent of Code challenge
+///
+/// ## Returns
+/// * `()` - Nothing
+fn download_day(caller: &Path, config: &Config, profile: &Profile, year: u16, day: u8) {
+    let session = match auth::session(profile) {
+        Ok((session, _)) => session,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+    let client = Client::new(&config.http, Some(session));
 
-        bin_table.insert("name", toml_edit::value(desired_name));
-        bin_table.insert("path", toml_edit::value("src/main.rs"));
-        bin_array.push(bin_table);
+    let downloads = [
+        download::download_puzzle(caller, config, &client, year, day),
+        download::download_input(caller, config, &client, profile, year, day),
+    ];
 
-        doc["bin"] = toml_edit::Item::ArrayOfTables(bin_array);
+    for download in downloads {
+        match download {
+            Ok(Download::Fetched(path)) => println!("Downloaded {}", path.display()),
+            Ok(Download::Skipped(path)) => {
+                println!("Skipped {}, already downloaded", path.display())
+            }
+            Err(e) => eprintln!("{}", e),
+        }
     }
+}
 
-    fs::write(&cargo_toml_path, doc.to_string())?;
-    Ok(())
+///
+/// # resolve_day_and_year
+/// Resolves the day and year specifications against the puzzle calendar.
+///
+/// ## Arguments
+/// * `day` - The day specification
+/// * `year` - The year specification
+///
+/// ## Returns
+/// * `Result<(u8, u16), String>` - The resolved day and year
+fn resolve_day_and_year(day: &DaySpec, year: &YearSpec) -> Result<(u8, u16), String> {
+    let now = calendar::now();
+    let year = year.resolve(&now)?;
+    let day = day.resolve(year, &now)?;
+
+    Ok((day, year))
+}
+
+///
+/// # resolve_selection
+/// Resolves the day and year selections against the puzzle calendar.
+///
+/// ## Arguments
+/// * `days` - The day selection
+/// * `years` - The year selection
+///
+/// ## Returns
+/// * `Result<Vec<(u16, Vec<u8>)>, String>` - The selected days of every selected year
+fn resolve_selection(
+    days: &DaySelection,
+    years: &YearSelection,
+) -> Result<Vec<(u16, Vec<u8>)>, String> {
+    let now = calendar::now();
+
+    years
+        .resolve(&now)?
+        .into_iter()
+        .map(|year| Ok((year, days.resolve(year, &now)?)))
+        .collect()
+}
+
+///
+/// # parse_timeout
+/// Parses the `--timeout` argument, a number of seconds.
+///
+/// ## Arguments
+/// * `s` - The argument
+///
+/// ## Returns
+/// * `Result<Duration, String>` - The timeout, zero to disable it
+fn parse_timeout(s: &str) -> Result<Duration, String> {
+    let seconds = s
+        .parse::<f64>()
+        .map_err(|_| format!("'{}' is not a number of seconds", s))?;
+
+    Duration::try_from_secs_f64(seconds).map_err(|e| format!("invalid timeout '{}': {}", s, e))
+}
+
+///
+/// # parse_memory_limit
+/// Parses the `--memory-limit` argument, a number of MiB.
+///
+/// ## Arguments
+/// * `s` - The argument
+///
+/// ## Returns
+/// * `Result<u64, String>` - The limit in bytes, zero to disable it
+fn parse_memory_limit(s: &str) -> Result<u64, String> {
+    s.parse::<u64>()
+        .map_err(|_| format!("'{}' is not a number of MiB", s))?
+        .checked_mul(1024 * 1024)
+        .ok_or_else(|| format!("the memory limit '{}' is too large", s))
+}
+
+///
+/// # get_current_year
+/// Returns the current year, in the unlock timezone like the resolution of the year specs.
+///
+/// ## Arguments
+/// * `now` - The current time, in the unlock timezone
+///
+/// ## Returns
+/// * `u16` - The current year
+fn get_current_year(now: &DateTime<FixedOffset>) -> u16 {
+    now.year() as u16
 }
 
 ///
@@ -199,9 +823,9 @@ fn update_cargo_toml(
 ///
 /// ## Returns
 /// * `()` - Nothing
-fn compile_solution(caller: &PathBuf, day: u8, year: u16) {
+fn compile_solution(caller: &Path, day: u8, year: u16) {
     // Add [[bin]] section
-    if let Err(e) = update_cargo_toml(caller, day, year, true) {
+    if let Err(e) = update_cargo_toml(caller, day, year, true, false) {
         eprintln!("Failed to update Cargo.toml: {}", e);
         return;
     }
@@ -223,7 +847,7 @@ fn compile_solution(caller: &PathBuf, day: u8, year: u16) {
     }
 
     // Remove [[bin]] section after compilation
-    if let Err(e) = update_cargo_toml(caller, day, year, false) {
+    if let Err(e) = update_cargo_toml(caller, day, year, false, false) {
         eprintln!("Failed to clean up Cargo.toml: {}", e);
     }
 }
@@ -240,25 +864,54 @@ fn compile_solution(caller: &PathBuf, day: u8, year: u16) {
 /// * `caller` - The path to the project root directory
 /// * `day` - The day of the Advent of Code challenge
 /// * `year` - The year of the Advent of Code challenge
-fn run_tests(caller: &PathBuf, day: u8, year: u16) {
+///
+/// ## Returns
+/// * `Option<TestSuite>` - The results of the tests, `None` if they could not run
+fn run_tests(caller: &Path, day: u8, year: u16) -> Option<TestSuite> {
     // Add [[bin]] section
-    if let Err(e) = update_cargo_toml(caller, day, year, true) {
+    if let Err(e) = update_cargo_toml(caller, day, year, true, false) {
         eprintln!("Failed to update Cargo.toml: {}", e);
-        return;
+        return None;
     }
 
-    // Run the test command
-    let mut test_command = Command::new("cargo");
-    test_command
+    // Run the tests of the binary of the day only: a bare name would be a filter on the test names,
+    // matching none of them. The output is printed as it comes and kept for the JUnit reports.
+    let start = Instant::now();
+    let result = Command::new("cargo")
         .current_dir(caller)
         .arg("test")
         .arg("--release")
-        .arg(format!("day_{:02}_year_{}", day, year));
+        .arg("--bin")
+        .arg(format!("day_{:02}_year_{}", day, year))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .and_then(|mut child| {
+            let mut stdout = String::new();
+            if let Some(output) = child.stdout.take() {
+                for line in BufReader::new(output).lines().map_while(Result::ok) {
+                    println!("{}", line);
+                    stdout.push_str(&line);
+                    stdout.push('\n');
+                }
+            }
 
-    // Execute the test command
-    let status = test_command
-        .status()
-        .expect("Failed to execute test command");
+            child.wait().map(|status| (status, stdout))
+        });
+    let duration = start.elapsed();
+
+    // Remove [[bin]] section after testing
+    if let Err(e) = update_cargo_toml(caller, day, year, false, false) {
+        eprintln!("Failed to clean up Cargo.toml: {}", e);
+    }
+
+    let (status, stdout) = match result {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("Failed to execute test command: {}", e);
+            return None;
+        }
+    };
 
     if !status.success() {
         println!("Tests failed");
@@ -266,11 +919,27 @@ fn run_tests(caller: &PathBuf, day: u8, year: u16) {
         println!("All tests passed!");
     }
 
-    // Remove [[bin]] section after testing
-    if let Err(e) = update_cargo_toml(caller, day, year, false) {
-        eprintln!("Failed to clean up Cargo.toml: {}", e);
+    let class = format!("{}.day_{:02}", year, day);
+    let mut cases = junit::parse_cargo_test(&class, &stdout);
+    if cases.is_empty() && !status.success() {
+        cases.push(TestCase {
+            class,
+            name: "build".to_string(),
+            time: None,
+            result: CaseResult::Error(
+                "the tests could not be compiled".to_string(),
+                "See the output of cargo test.".to_string(),
+            ),
+        });
     }
+
+    Some(TestSuite {
+        name: format!("{} day {:02}", year, day),
+        time: Some(duration),
+        cases,
+    })
 }
+
 // Main  ====================================================================================  Main
 fn main() {
     // Get the arguments
@@ -279,65 +948,1332 @@ fn main() {
     // Folder caller - the folder from which the program was called
     let caller = std::env::current_dir().unwrap();
 
-    match &cli.command {
-        Some(Commands::Init { year }) => {
-            // If the 'year' argument is passed
-            if let Some(year) = year {
-                // Create the folders and files
-                init_folders_and_files(&caller, *year);
-            } else {
-                // Create the folders and files
-                init_folders_and_files(&caller, env!("AOC_YEAR").parse::<u16>().unwrap());
+    // The doctor reports an invalid configuration instead of stopping on it
+    if let Some(Commands::Doctor { format }) = &cli.command {
+        let checks = doctor::check(&caller, &cli.profile);
+
+        match format {
+            OutputFormat::Text => {
+                for check in &checks {
+                    println!("{:>7}  {}: {}", check.status, check.name, check.detail);
+                }
             }
+            OutputFormat::Json => print!(
+                "{}",
+                output::document(
+                    "doctor",
+                    json!({ "checks": checks.iter().map(doctor::Check::to_json).collect::<Vec<_>>() })
+                )
+            ),
         }
-        Some(Commands::Create { day, year }) => {
-            let day = day.unwrap_or(env!("AOC_DAY").parse::<u8>().unwrap());
-            let year = year.unwrap_or(env!("AOC_YEAR").parse::<u16>().unwrap());
 
-            if !check_global_file_struct_integrity(&caller)
-                || !check_file_struct_integrity_year(&caller, year)
-            {
-                println!("The file structure is not correct.\nPlease run `cargo aoc init` or `cargo aoc init --year desired_year` to create the folders and files needed for the Advent of Code challenges.");
-                return;
-            }
+        if checks
+            .iter()
+            .any(|check| check.status == doctor::Status::Error)
+        {
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Configuration of the project, from the optional 'aoc.toml' file
+    let config = match Config::load(&caller) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{}", e);
+            return;
+        }
+    };
+
+    match &cli.command {
+        Some(Commands::Init { year, with_days }) => {
+            let years = match year.resolve(&calendar::now()) {
+                Ok(years) => years,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
 
             // Create the folders and files
-            file_utils::create_files(&caller, day, year);
+            for year in years {
+                init_folders_and_files(&caller, &config, year, *with_days);
+            }
+        }
+        Some(Commands::Create {
+            day,
+            year,
+            force,
+            wait,
+            open,
+        }) => {
+            let selection = if *wait {
+                let (year, day) = calendar::next_unlock(&calendar::now());
+
+                if !check_global_file_struct_integrity(&caller)
+                    || !check_file_struct_integrity_year(&caller, &config.layout, year)
+                {
+                    println!("The file structure is not correct.\nPlease run `cargo aoc init` or `cargo aoc init --year desired_year` to create the folders and files needed for the Advent of Code challenges.");
+                    return;
+                }
+
+                countdown::wait_for_unlock(year, day);
+                download_day(&caller, &config, &cli.profile, year, day);
+
+                vec![(year, vec![day])]
+            } else {
+                match resolve_selection(day, year) {
+                    Ok(selection) => selection,
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        return;
+                    }
+                }
+            };
+
+            for (year, days) in selection {
+                if !check_global_file_struct_integrity(&caller)
+                    || !check_file_struct_integrity_year(&caller, &config.layout, year)
+                {
+                    println!("The file structure is not correct.\nPlease run `cargo aoc init` or `cargo aoc init --year desired_year` to create the folders and files needed for the Advent of Code challenges.");
+                    return;
+                }
+
+                // Create the folders and files
+                for day in &days {
+                    file_utils::create_files(&caller, &config, *day, year, *force);
+                }
+
+                // Start the clock of the days, stopped by `aoc submit`
+                match Timings::load(&caller, &config.layout, year) {
+                    Ok(mut timings) => {
+                        let now = calendar::now();
+                        let started = days
+                            .iter()
+                            .map(|day| timings.start(&cli.profile, *day, &now))
+                            .collect::<Result<Vec<_>, String>>();
+
+                        match started {
+                            Ok(started) if started.contains(&true) => {
+                                if let Err(e) = timings.save() {
+                                    eprintln!("{}", e);
+                                }
+                            }
+                            Ok(_) => {}
+                            Err(e) => eprintln!("{}", e),
+                        }
+                    }
+                    Err(e) => eprintln!("{}", e),
+                }
+
+                if let (true, Some(day)) = (*open, days.last()) {
+                    let solution = caller.join(config.layout.solution(year, *day));
+                    if let Err(e) = file_utils::open_in_editor(&solution) {
+                        eprintln!("{}", e);
+                    }
+                }
+            }
         }
         Some(Commands::Solve { day, year, part_2 }) => {
-            let day = day.unwrap_or(env!("AOC_DAY").parse::<u8>().unwrap());
-            let year = year.unwrap_or(env!("AOC_YEAR").parse::<u16>().unwrap());
+            let (day, year) = match resolve_day_and_year(day, year) {
+                Ok(resolved) => resolved,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
 
             if !check_global_file_struct_integrity(&caller)
-                || !check_file_struct_integrity_year(&caller, year)
+                || !check_file_struct_integrity_year(&caller, &config.layout, year)
             {
                 println!("The file structure is not correct.\nPlease run `cargo aoc init` or `cargo aoc init --year desired_year` to create the folders and files needed for the Advent of Code challenges.");
                 return;
             }
 
             // Prepare the 'src/main.rs' file
-            file_utils::prepare_main_file(&caller, day, year, *part_2);
+            file_utils::prepare_main_file(&caller, &config.layout, day, year, *part_2);
 
             // Compile the solution
             compile_solution(&caller, day, year);
         }
-        Some(Commands::Test { day, year }) => {
-            let day = day.unwrap_or(env!("AOC_DAY").parse::<u8>().unwrap());
-            let year = year.unwrap_or(env!("AOC_YEAR").parse::<u16>().unwrap());
+        Some(Commands::Test { day, year, junit }) => {
+            let selection = match resolve_selection(day, year) {
+                Ok(selection) => selection,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+
+            let mut suites = Vec::new();
+            let mut failed = false;
+            for (year, days) in selection {
+                if !check_global_file_struct_integrity(&caller)
+                    || !check_file_struct_integrity_year(&caller, &config.layout, year)
+                {
+                    println!("The file structure is not correct.\nPlease run `cargo aoc init` or `cargo aoc init --year desired_year` to create the folders and files needed for the Advent of Code challenges.");
+                    return;
+                }
+
+                for day in days {
+                    // Prepare the main.rs file
+                    file_utils::prepare_main_file(&caller, &config.layout, day, year, false);
+
+                    // Run the tests, a day whose tests could not run fails the command
+                    match run_tests(&caller, day, year) {
+                        Some(suite) => {
+                            failed |= suite.is_failure();
+                            suites.push(suite);
+                        }
+                        None => failed = true,
+                    }
+                }
+            }
+
+            if let Some(path) = junit {
+                match junit::write_report(path, "aoc test", &suites) {
+                    Ok(()) => println!("JUnit report written to {}", path.display()),
+                    Err(e) => eprintln!("{}", e),
+                }
+            }
+
+            if failed {
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Download {
+            day,
+            year,
+            all,
+            delay,
+        }) => {
+            let mut config = config;
+            if let Some(delay) = delay {
+                config.http.delay = Duration::from_millis(*delay);
+            }
+
+            if !*all {
+                let selection = match resolve_selection(day, year) {
+                    Ok(selection) => selection,
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        return;
+                    }
+                };
+
+                for (year, days) in selection {
+                    for day in days {
+                        download_day(&caller, &config, &cli.profile, year, day);
+                    }
+                }
+                return;
+            }
+
+            // Every unlocked day is downloaded, so no day is resolved
+            let now = calendar::now();
+            let years = match year.resolve(&now) {
+                Ok(years) => years,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+
+            let session = match auth::session(&cli.profile) {
+                Ok((session, _)) => session,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+            let client = Client::new(&config.http, Some(session));
+
+            let mut failed = false;
+            for year in years {
+                let mut summary = download::Summary::default();
+                let downloaded = download::download_year(
+                    &caller,
+                    &config,
+                    &client,
+                    &cli.profile,
+                    year,
+                    calendar::unlocked_days(year, &now),
+                    &mut summary,
+                );
+
+                if let Err(e) = &downloaded {
+                    eprintln!("{}", e);
+                }
+                println!(
+                    "{}: {} fetched, {} skipped, {} failed",
+                    year,
+                    summary.fetched,
+                    summary.skipped,
+                    summary.failed.len()
+                );
+
+                failed |= downloaded.is_err() || !summary.failed.is_empty();
+                if downloaded.is_err() {
+                    break;
+                }
+            }
+
+            if failed {
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Leaderboard {
+            id,
+            year,
+            day,
+            report,
+            out,
+            format,
+        }) => {
+            let now = calendar::now();
+            let year = match year.resolve(&now) {
+                Ok(year) => year,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+            let day = match day.as_ref().map(|day| day.resolve(year, &now)).transpose() {
+                Ok(day) => day,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+
+            let session = match auth::session(&cli.profile) {
+                Ok((session, _)) => session,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+            let client = Client::new(&config.http, Some(session));
+
+            let leaderboard = match leaderboard::fetch(&client, year, *id) {
+                Ok(leaderboard) => leaderboard,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+
+            let days = calendar::unlocked_days(year, &now);
+
+            match (day, report) {
+                (_, None) if *format == OutputFormat::Json => print!(
+                    "{}",
+                    output::document(
+                        "leaderboard",
+                        json!({
+                            "id": id,
+                            "year": year,
+                            "day": day,
+                            "members": leaderboard::to_json(&leaderboard, day),
+                        })
+                    )
+                ),
+                (Some(day), _) => print!("{}", leaderboard::render_day(&leaderboard, day)),
+                (None, Some(format)) => {
+                    let reports = analytics::report(&leaderboard, days);
+                    let rendered = analytics::render(&reports, days, *format);
+
+                    match out {
+                        Some(out) => match std::fs::write(out, rendered) {
+                            Ok(()) => println!("Report written to {}", out.display()),
+                            Err(e) => eprintln!("Failed to write {}: {}", out.display(), e),
+                        },
+                        None => print!("{}", rendered),
+                    }
+                }
+                (None, None) => print!("{}", leaderboard::render_table(&leaderboard, days)),
+            }
+        }
+        Some(Commands::Run {
+            day,
+            year,
+            part,
+            all_profiles,
+            format,
+            alloc,
+            limits,
+        }) => {
+            let limits = limits.apply(config.run);
+            let selection = match resolve_selection(day, year) {
+                Ok(selection) => selection,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+            let text = *format == OutputFormat::Text;
+
+            let parts = part.map_or(vec![1, 2], |part| vec![part]);
+            let mut days = Vec::new();
+            for (year, day) in selection
+                .into_iter()
+                .flat_map(|(year, days)| days.into_iter().map(move |day| (year, day)))
+            {
+                let mut runs = Vec::new();
+                for profile in
+                    selected_profiles(&caller, &config, year, day, &cli.profile, *all_profiles)
+                {
+                    if text {
+                        println!("Day {:02} of {} - profile {}", day, year, profile);
+                    }
+
+                    let binary = match runner::build(&caller, &config, year, day, &profile, *alloc)
+                    {
+                        Ok(binary) => binary,
+                        Err(e) => {
+                            eprintln!("{}", e);
+                            runs.push(
+                                json!({ "profile": profile.name(), "error": e, "parts": [] }),
+                            );
+                            continue;
+                        }
+                    };
+
+                    let mut part_runs = Vec::new();
+                    for part in &parts {
+                        match runner::run_part(&caller, &binary, *part, &limits) {
+                            Ok(run) if text => {
+                                print!("{}", run.output);
+                                print!("Part {} done in {:.2?}", part, run.duration);
+                                if let Some(peak) = run.peak_memory {
+                                    print!(", peak memory {}", format_bytes(peak));
+                                }
+                                if let Some(allocations) = run.allocations {
+                                    print!(
+                                        ", {} allocations of {}",
+                                        allocations.count,
+                                        format_bytes(allocations.bytes)
+                                    );
+                                }
+                                println!();
+                                if run.outcome != Outcome::Success {
+                                    println!("Part {} {}", part, run.outcome);
+                                }
+                            }
+                            Ok(run) => part_runs.push(output::part_run(*part, &run)),
+                            Err(e) => {
+                                eprintln!("{}", e);
+                                part_runs.push(json!({ "part": part, "error": e }));
+                            }
+                        }
+                    }
+                    runs.push(
+                        json!({ "profile": profile.name(), "error": null, "parts": part_runs }),
+                    );
+                }
+                days.push(json!({ "year": year, "day": day, "runs": runs }));
+            }
+
+            if !text {
+                print!("{}", output::document("run", json!({ "days": days })));
+            }
+        }
+        Some(Commands::Bench {
+            day,
+            year,
+            part,
+            runs,
+            format,
+            alloc,
+            limits,
+        }) => {
+            let selection = match resolve_selection(day, year) {
+                Ok(selection) => selection,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+            let limits = limits.apply(config.run);
+            let text = *format == OutputFormat::Text;
+
+            let mut failed = false;
+            let mut days = Vec::new();
+            for (year, selected_days) in selection {
+                let mut benchmarks = match Benchmarks::load(&caller, &config.layout, year) {
+                    Ok(benchmarks) => benchmarks,
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        return;
+                    }
+                };
+
+                for day in selected_days {
+                    let binary =
+                        match runner::build(&caller, &config, year, day, &cli.profile, *alloc) {
+                            Ok(binary) => binary,
+                            Err(e) => {
+                                eprintln!("{}", e);
+                                failed = true;
+                                days.push(json!({
+                                    "year": year,
+                                    "day": day,
+                                    "error": e,
+                                    "parts": [],
+                                }));
+                                continue;
+                            }
+                        };
+
+                    if text {
+                        println!(
+                            "Day {:02} of {} - profile {}, {} run(s) per part",
+                            day, year, cli.profile, runs
+                        );
+                        println!(
+                            "Part  {:>10}  {:>10}  {:>10}  {:>10}  {:>8}  {:>10}",
+                            "Min", "Median", "Mean", "Peak RSS", "Allocs", "Allocated"
+                        );
+                    }
+
+                    let mut benches = Vec::new();
+                    for part in part.map_or(vec![1, 2], |part| vec![part]) {
+                        let mut durations = Vec::new();
+                        let mut outcome = Outcome::Success;
+                        let mut peak_memory = None;
+                        let mut allocations = None;
+
+                        for _ in 0..*runs {
+                            match runner::run_part(&caller, &binary, part, &limits) {
+                                Ok(run) if run.outcome == Outcome::Success => {
+                                    durations.push(run.duration);
+                                    peak_memory = peak_memory.max(run.peak_memory);
+                                    allocations = run.allocations;
+                                }
+                                Ok(run) => {
+                                    outcome = run.outcome;
+                                    break;
+                                }
+                                Err(e) => {
+                                    eprintln!("{}", e);
+                                    outcome = Outcome::Failed;
+                                    break;
+                                }
+                            }
+                        }
+
+                        if outcome != Outcome::Success {
+                            failed = true;
+                            if text {
+                                println!("{:>4}  {}", part, outcome);
+                            }
+                            benches.push(json!({
+                                "part": part,
+                                "outcome": output::outcome(&outcome),
+                                "runs": durations.len(),
+                                "min_ns": null,
+                                "median_ns": null,
+                                "mean_ns": null,
+                                "peak_memory_bytes": peak_memory,
+                                "allocations": null,
+                            }));
+                            continue;
+                        }
+
+                        durations.sort();
+                        let (min, median, mean) = (
+                            durations[0],
+                            durations[durations.len() / 2],
+                            durations.iter().sum::<Duration>() / durations.len() as u32,
+                        );
+                        if let Err(e) =
+                            benchmarks.record(&cli.profile, day, part, median, peak_memory)
+                        {
+                            eprintln!("{}", e);
+                        }
+
+                        if text {
+                            println!(
+                                "{:>4}  {:>10}  {:>10}  {:>10}  {:>10}  {:>8}  {:>10}",
+                                part,
+                                format!("{:.2?}", min),
+                                format!("{:.2?}", median),
+                                format!("{:.2?}", mean),
+                                peak_memory.map_or("-".to_string(), format_bytes),
+                                allocations.map_or("-".to_string(), |allocations| allocations
+                                    .count
+                                    .to_string()),
+                                allocations.map_or("-".to_string(), |allocations| format_bytes(
+                                    allocations.bytes
+                                ))
+                            );
+                        }
+                        benches.push(json!({
+                            "part": part,
+                            "outcome": output::outcome(&outcome),
+                            "runs": durations.len(),
+                            "min_ns": output::nanoseconds(min),
+                            "median_ns": output::nanoseconds(median),
+                            "mean_ns": output::nanoseconds(mean),
+                            "peak_memory_bytes": peak_memory,
+                            "allocations": allocations.as_ref().map(output::allocations),
+                        }));
+                    }
+
+                    days.push(json!({
+                        "year": year,
+                        "day": day,
+                        "error": null,
+                        "parts": benches,
+                    }));
+                }
+
+                // Kept for `aoc readme` and `aoc site`
+                if let Err(e) = benchmarks.save() {
+                    eprintln!("{}", e);
+                }
+            }
+
+            if !text {
+                print!(
+                    "{}",
+                    output::document(
+                        "bench",
+                        json!({ "profile": cli.profile.name(), "days": days })
+                    )
+                );
+            }
+
+            if failed {
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Watch { day, year }) => {
+            let selection = match resolve_selection(day, year) {
+                Ok(selection) => selection,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
 
             if !check_global_file_struct_integrity(&caller)
-                || !check_file_struct_integrity_year(&caller, year)
+                || selection.iter().any(|(year, _)| {
+                    !check_file_struct_integrity_year(&caller, &config.layout, *year)
+                })
             {
                 println!("The file structure is not correct.\nPlease run `cargo aoc init` or `cargo aoc init --year desired_year` to create the folders and files needed for the Advent of Code challenges.");
                 return;
             }
 
-            // Prepare the main.rs file
-            file_utils::prepare_main_file(&caller, day, year, false);
+            let days = selection
+                .into_iter()
+                .flat_map(|(year, days)| days.into_iter().map(move |day| (year, day)))
+                .collect::<Vec<_>>();
+            watch::watch(
+                &caller,
+                &config.layout,
+                &cli.profile,
+                &days,
+                |days, changed| {
+                    // Only the latest run is shown
+                    print!("\x1b[2J\x1b[H");
+                    for path in changed {
+                        println!("Changed {}", path.display());
+                    }
+
+                    for (year, day) in days.iter().copied() {
+                        println!(
+                            "\nDay {:02} of {} - {}\n",
+                            day,
+                            year,
+                            calendar::now().format("%H:%M:%S")
+                        );
+
+                        file_utils::prepare_main_file(&caller, &config.layout, day, year, false);
+                        run_tests(&caller, day, year);
+                        println!();
+
+                        match runner::build(&caller, &config, year, day, &cli.profile, false) {
+                            Ok(binary) => {
+                                for part in [1, 2] {
+                                    match runner::run_part(&caller, &binary, part, &config.run) {
+                                        Ok(run) if run.outcome == Outcome::Success => println!(
+                                            "Part {}: {} ({:.2?})",
+                                            part,
+                                            run.answer.unwrap_or_default(),
+                                            run.duration
+                                        ),
+                                        Ok(run) => {
+                                            println!(
+                                                "Part {} {}\n{}",
+                                                part, run.outcome, run.output
+                                            )
+                                        }
+                                        Err(e) => eprintln!("{}", e),
+                                    }
+                                }
+                            }
+                            Err(e) => eprintln!("{}", e),
+                        }
+                    }
+
+                    println!("\nWatching for changes, press Ctrl-C to stop.");
+                },
+            );
+        }
+        Some(Commands::Submit {
+            answer,
+            day,
+            year,
+            part,
+        }) => {
+            let (day, year) = match resolve_day_and_year(day, year) {
+                Ok(resolved) => resolved,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+            let (mut ledger, mut timings) = match (
+                Ledger::load(&caller, &config.layout, year),
+                Timings::load(&caller, &config.layout, year),
+            ) {
+                (Ok(ledger), Ok(timings)) => (ledger, timings),
+                (Err(e), _) | (_, Err(e)) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+
+            let part = part.unwrap_or(match ledger.answer(&cli.profile, day, 1) {
+                Some(_) => 2,
+                None => 1,
+            });
+            if let Some(accepted) = ledger.answer(&cli.profile, day, part) {
+                println!(
+                    "Part {} of day {:02} of {} is already solved, its answer was {}.",
+                    part, day, year, accepted
+                );
+                return;
+            }
+
+            let answer = match answer {
+                Some(answer) => answer.trim().to_string(),
+                None => {
+                    let run = runner::build(&caller, &config, year, day, &cli.profile, false)
+                        .and_then(|binary| runner::run_part(&caller, &binary, part, &config.run));
+
+                    match run {
+                        Ok(run) if run.outcome == Outcome::Success && run.answer.is_some() => {
+                            run.answer.unwrap_or_default()
+                        }
+                        Ok(run) => {
+                            eprintln!("Part {} printed no answer\n{}", part, run.output);
+                            std::process::exit(1);
+                        }
+                        Err(e) => {
+                            eprintln!("{}", e);
+                            std::process::exit(1);
+                        }
+                    }
+                }
+            };
+
+            let session = match auth::session(&cli.profile) {
+                Ok((session, _)) => session,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+            let client = Client::new(&config.http, Some(session));
+
+            println!(
+                "Submitting {} for part {} of day {:02} of {}",
+                answer, part, day, year
+            );
+            match submit::submit(&client, year, day, part, &answer) {
+                Ok(Verdict::Right) => {
+                    println!("That's the right answer!");
+
+                    // The answer is recorded even if the timings cannot be
+                    let recorded = ledger
+                        .set_answer(&cli.profile, day, part, &answer)
+                        .and_then(|_| ledger.save());
+                    let timed = timings
+                        .finish(&cli.profile, day, part, &calendar::now())
+                        .and_then(|_| timings.save());
+                    for e in [recorded, timed].into_iter().filter_map(Result::err) {
+                        eprintln!("{}", e);
+                    }
+                    if let Some(elapsed) = timings.elapsed(&cli.profile, day, part) {
+                        println!(
+                            "Part {} solved in {}",
+                            part,
+                            calendar::format_duration(elapsed)
+                        );
+                    }
+
+                    // The description of the second part is only shown once the first is solved
+                    if part == 1 {
+                        match download::download_puzzle(&caller, &config, &client, year, day) {
+                            Ok(Download::Fetched(path)) => {
+                                println!("Downloaded {}", path.display())
+                            }
+                            Ok(Download::Skipped(_)) => {}
+                            Err(e) => eprintln!("{}", e),
+                        }
+                    }
+                }
+                Ok(Verdict::Wrong(hint)) => {
+                    match hint {
+                        Some(hint) => println!("That's not the right answer, {}.", hint),
+                        None => println!("That's not the right answer."),
+                    }
+                    std::process::exit(1);
+                }
+                Ok(Verdict::TooRecently(message)) | Ok(Verdict::Unknown(message)) => {
+                    println!("{}", message);
+                    std::process::exit(1);
+                }
+                Ok(Verdict::WrongLevel) => {
+                    println!(
+                        "The website expects no answer for part {}: it is already solved, or the \
+                        first part is not.",
+                        part
+                    );
+                    std::process::exit(1);
+                }
+                Err(e) => {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Commands::Status { year, format }) => {
+            let now = calendar::now();
+            let year = match year.resolve(&now) {
+                Ok(year) => year,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+            let (ledger, timings) = match (
+                Ledger::load(&caller, &config.layout, year),
+                Timings::load(&caller, &config.layout, year),
+            ) {
+                (Ok(ledger), Ok(timings)) => (ledger, timings),
+                (Err(e), _) | (_, Err(e)) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+
+            let text = *format == OutputFormat::Text;
+            if text {
+                println!("{} - profile {}", year, cli.profile);
+                println!("Day  Stars  {:>12}  {:>12}", "Part 1", "Part 2");
+            }
+
+            let mut days = Vec::new();
+            for day in 1..=calendar::unlocked_days(year, &now) {
+                let solved = [1, 2].map(|part| ledger.answer(&cli.profile, day, part).is_some());
+                let stars = solved.iter().filter(|solved| **solved).count();
 
-            // Run the tests
-            run_tests(&caller, day, year);
+                if !text {
+                    let parts = [1, 2].map(|part| {
+                        json!({
+                            "part": part,
+                            "solved": solved[usize::from(part - 1)],
+                            "elapsed_seconds": timings
+                                .elapsed(&cli.profile, day, part)
+                                .map(|elapsed| elapsed.as_secs()),
+                        })
+                    });
+                    days.push(json!({
+                        "day": day,
+                        "stars": stars,
+                        "started": timings
+                            .started(&cli.profile, day)
+                            .map(|started| started.to_rfc3339_opts(SecondsFormat::Secs, false)),
+                        "parts": parts,
+                    }));
+                    continue;
+                }
+
+                // The time taken, or whether the part is solved when it has not been timed
+                let times = [1, 2].map(|part| {
+                    match (
+                        timings.elapsed(&cli.profile, day, part),
+                        solved[usize::from(part - 1)],
+                    ) {
+                        (Some(elapsed), _) => calendar::format_duration(elapsed),
+                        (None, true) => "solved".to_string(),
+                        (None, false) => "-".to_string(),
+                    }
+                });
+
+                let mut line = format!(
+                    " {:02}  {:<5}  {:>12}  {:>12}",
+                    day,
+                    "*".repeat(stars),
+                    times[0],
+                    times[1]
+                );
+                if let (Some(started), false) = (timings.started(&cli.profile, day), solved[1]) {
+                    if let Ok(elapsed) = (now - started).to_std() {
+                        line.push_str(&format!(
+                            "  (started {} ago)",
+                            calendar::format_duration(elapsed)
+                        ));
+                    }
+                }
+
+                println!("{}", line.trim_end());
+            }
+
+            if !text {
+                print!(
+                    "{}",
+                    output::document(
+                        "status",
+                        json!({ "year": year, "profile": cli.profile.name(), "days": days })
+                    )
+                );
+            }
+        }
+        Some(Commands::Sync { year }) => {
+            let years = match year.as_ref().map(|year| year.resolve(&calendar::now())) {
+                Some(Ok(years)) => Some(years),
+                Some(Err(e)) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+                None => None,
+            };
+
+            let session = match auth::session(&cli.profile) {
+                Ok((session, _)) => session,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+            let client = Client::new(&config.http, Some(session));
+
+            let events = match sync::events(&client) {
+                Ok(events) => events,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                }
+            };
+            println!(
+                "{} stars in total",
+                events.iter().map(|(_, stars)| stars).sum::<u32>()
+            );
+
+            for (year, stars) in events {
+                if stars == 0 || years.as_ref().is_some_and(|years| !years.contains(&year)) {
+                    continue;
+                }
+
+                match sync::sync_year(&caller, &config, &client, &cli.profile, year, stars) {
+                    Ok(sync) => {
+                        println!(
+                            "{}: {} stars, {} answer(s) backfilled",
+                            sync.year,
+                            sync.stars,
+                            sync.backfilled.len()
+                        );
+                        for (day, part, known, answer) in sync.conflicts {
+                            println!(
+                                "{} day {:02} part {} [{}]: the answers file has {}, the website {}",
+                                year, day, part, cli.profile, known, answer
+                            );
+                        }
+                    }
+                    Err(e) => eprintln!("{}: {}", year, e),
+                }
+            }
+        }
+        Some(Commands::Verify {
+            day,
+            year,
+            all_profiles,
+            format,
+            junit,
+            limits,
+        }) => {
+            let limits = limits.apply(config.run);
+            let selection = match resolve_selection(day, year) {
+                Ok(selection) => selection,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+            let text = *format == OutputFormat::Text;
+
+            let mut verifications = Vec::new();
+            for (year, days) in selection {
+                let ledger = match Ledger::load(&caller, &config.layout, year) {
+                    Ok(ledger) => ledger,
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        return;
+                    }
+                };
+
+                for day in days {
+                    for profile in
+                        selected_profiles(&caller, &config, year, day, &cli.profile, *all_profiles)
+                    {
+                        let day_verifications =
+                            match runner::build(&caller, &config, year, day, &profile, false) {
+                                Ok(binary) => [1, 2]
+                                    .map(|part| {
+                                        Verification::part(
+                                            year,
+                                            day,
+                                            part,
+                                            &profile,
+                                            runner::run_part(&caller, &binary, part, &limits),
+                                            ledger.answer(&profile, day, part),
+                                        )
+                                    })
+                                    .into(),
+                                Err(e) => vec![Verification::error(year, day, None, &profile, e)],
+                            };
+
+                        for verification in day_verifications {
+                            if text {
+                                println!("{}", verification);
+                            }
+                            verifications.push(verification);
+                        }
+                    }
+                }
+            }
+
+            let failures = verifications
+                .iter()
+                .filter(|verification| verification.is_failure())
+                .count();
+            if text && failures > 0 {
+                println!("{} verification(s) failed", failures);
+            }
+
+            if let Some(path) = junit {
+                // One suite per year
+                let mut suites = Vec::<TestSuite>::new();
+                for verification in &verifications {
+                    let name = verification.year.to_string();
+                    if suites.last().is_none_or(|suite| suite.name != name) {
+                        suites.push(TestSuite {
+                            name,
+                            time: None,
+                            cases: Vec::new(),
+                        });
+                    }
+                    if let Some(suite) = suites.last_mut() {
+                        suite.cases.push(verification.to_test_case());
+                    }
+                }
+
+                match junit::write_report(path, "aoc verify", &suites) {
+                    Ok(()) if text => println!("JUnit report written to {}", path.display()),
+                    Ok(()) => {}
+                    Err(e) => eprintln!("{}", e),
+                }
+            }
+            if !text {
+                print!(
+                    "{}",
+                    output::document(
+                        "verify",
+                        json!({
+                            "results": verifications
+                                .iter()
+                                .map(Verification::to_json)
+                                .collect::<Vec<_>>(),
+                            "failures": failures,
+                        })
+                    )
+                );
+            }
+
+            if failures > 0 {
+                std::process::exit(1);
+            }
         }
+        Some(Commands::Import {
+            from,
+            path,
+            year,
+            move_files,
+        }) => {
+            let year = match year.map(|year| year.resolve(&calendar::now())).transpose() {
+                Ok(year) => year,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+            let source = path.clone().unwrap_or_else(|| caller.clone());
+
+            match import::import(&caller, &config, *from, &source, year, *move_files) {
+                Ok((year, report)) => import::print_report(year, &report),
+                Err(e) => eprintln!("{}", e),
+            }
+        }
+        Some(Commands::Export {
+            year,
+            format,
+            out,
+            include_inputs,
+            include_puzzles,
+            force,
+        }) => {
+            let year = match year.resolve(&calendar::now()) {
+                Ok(year) => year,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+            let options = ExportOptions {
+                include_inputs: *include_inputs,
+                include_puzzles: *include_puzzles,
+                force: *force,
+            };
+
+            match export::export(&caller, &config, year, *format, out, &options) {
+                Ok(warnings) => {
+                    for warning in warnings {
+                        println!("warning: {}", warning);
+                    }
+                    println!("Year {} exported to {}", year, out.display());
+                }
+                Err(e) => eprintln!("{}", e),
+            }
+        }
+        Some(Commands::Auth { action }) => match action {
+            AuthAction::Login { session } => {
+                let session = match session {
+                    Some(session) => session.clone(),
+                    None => {
+                        println!("Paste the value of the 'session' cookie of adventofcode.com:");
+
+                        let mut line = String::new();
+                        if let Err(e) = std::io::stdin().read_line(&mut line) {
+                            eprintln!("Failed to read the session: {}", e);
+                            return;
+                        }
+
+                        line
+                    }
+                };
+
+                let session = match auth::normalize_session(&session) {
+                    Ok(session) => session,
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        return;
+                    }
+                };
+
+                match Client::new(&config.http, Some(session.clone()))
+                    .get("settings")
+                    .map(|page| http::username(&page))
+                {
+                    Ok(Some(username)) => println!("Logged in as {}", username),
+                    Ok(None) => {
+                        eprintln!("{}", http::SESSION_EXPIRED);
+                        return;
+                    }
+                    Err(e) if e == http::SESSION_EXPIRED => {
+                        eprintln!("{}", e);
+                        return;
+                    }
+                    Err(e) => println!("warning: the session could not be checked: {}", e),
+                }
+
+                match auth::save_session(&cli.profile, &session) {
+                    Ok(path) => println!("Session saved in {}", path.display()),
+                    Err(e) => eprintln!("{}", e),
+                }
+            }
+            AuthAction::Status => {
+                let (session, source) = match auth::session(&cli.profile) {
+                    Ok(session) => session,
+                    Err(e) => {
+                        eprintln!("{}", e);
+                        return;
+                    }
+                };
+
+                match source {
+                    SessionSource::Environment => {
+                        println!("Session read from {}", auth::SESSION_VARIABLE)
+                    }
+                    SessionSource::File(path) => println!("Session read from {}", path.display()),
+                }
+
+                match Client::new(&config.http, Some(session))
+                    .get("settings")
+                    .map(|page| http::username(&page))
+                {
+                    Ok(Some(username)) => println!("Logged in as {}", username),
+                    Ok(None) => eprintln!("{}", http::SESSION_EXPIRED),
+                    Err(e) => eprintln!("{}", e),
+                }
+            }
+            AuthAction::Logout => {
+                match auth::remove_session(&cli.profile) {
+                    Ok(Some(path)) => println!(
+                        "Removed the session of the profile '{}' from {}",
+                        cli.profile,
+                        path.display()
+                    ),
+                    Ok(None) => println!("No session stored for the profile '{}'.", cli.profile),
+                    Err(e) => eprintln!("{}", e),
+                }
+
+                if cli.profile.is_default() && std::env::var_os(auth::SESSION_VARIABLE).is_some() {
+                    println!(
+                        "The {} environment variable is still set.",
+                        auth::SESSION_VARIABLE
+                    );
+                }
+            }
+        },
+        Some(Commands::Readme { year, badges }) => {
+            let progress = match readme::progress(
+                &caller,
+                &config,
+                &cli.profile,
+                year.as_ref(),
+                &calendar::now(),
+                false,
+            ) {
+                Ok(progress) => progress,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+
+            if let Err(e) = std::fs::create_dir_all(caller.join(badges)) {
+                eprintln!("Failed to create {}: {}", badges.display(), e);
+                return;
+            }
+            for (year, rows) in &progress {
+                let stars = rows.iter().map(|row| u32::from(row.stars)).sum();
+                let path = badges.join(format!("{}.svg", year));
+
+                if let Err(e) = std::fs::write(caller.join(&path), readme::badge(*year, stars)) {
+                    eprintln!("Failed to write {}: {}", path.display(), e);
+                    return;
+                }
+            }
+
+            let path = caller.join("README.md");
+            let content = match std::fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+                Err(e) => {
+                    eprintln!("Failed to read README.md: {}", e);
+                    return;
+                }
+            };
+            let section = readme::render_section(&progress, badges);
+            let content = match readme::update_section(&content, &section) {
+                Ok(content) => content,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+
+            match std::fs::write(&path, content) {
+                Ok(()) => println!(
+                    "README.md updated with {} year(s), badges written to {}",
+                    progress.len(),
+                    badges.display()
+                ),
+                Err(e) => eprintln!("Failed to write README.md: {}", e),
+            }
+        }
+        Some(Commands::Site { year, out }) => {
+            let progress = match readme::progress(
+                &caller,
+                &config,
+                &cli.profile,
+                year.as_ref(),
+                &calendar::now(),
+                true,
+            ) {
+                Ok(progress) => progress,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return;
+                }
+            };
+
+            match site::write_site(&caller, &config, &progress, &caller.join(out)) {
+                Ok(pages) => println!(
+                    "{} page(s) of {} year(s) written to {}",
+                    pages,
+                    progress.len(),
+                    out.display()
+                ),
+                Err(e) => eprintln!("{}", e),
+            }
+        }
+        Some(Commands::Doctor { .. }) => {}
+        Some(Commands::MockServer {
+            fixtures,
+            port,
+            cooldown,
+        }) => {
+            let mut server = MockServer::new(caller.join(fixtures), Duration::from_secs(*cooldown));
+
+            if let Err(e) = server.serve(*port) {
+                eprintln!("{}", e);
+            }
+        }
+        Some(Commands::Template { action }) => match action {
+            TemplateAction::Show { name } => {
+                if name.path(&caller).exists() {
+                    eprintln!("// {}", name.path(&caller).display());
+                } else {
+                    eprintln!("// built-in {}", name.file_name());
+                }
+
+                match name.load(&caller) {
+                    Ok(template) => print!("{}", template),
+                    Err(e) => eprintln!("{}", e),
+                }
+            }
+            TemplateAction::Reset { name } => {
+                let names = name.map_or(TemplateName::all().to_vec(), |name| vec![name]);
+
+                for name in names {
+                    match name.reset(&caller) {
+                        Ok((path, Some(backup))) => println!(
+                            "Restored {} (customized version saved to {})",
+                            path.display(),
+                            backup.display()
+                        ),
+                        Ok((path, None)) => println!("Restored {}", path.display()),
+                        Err(e) => eprintln!("Failed to restore {}: {}", name.file_name(), e),
+                    }
+                }
+            }
+        },
         None => {
             println!("No command passed");
         }
@@ -347,25 +2283,63 @@ fn main() {
 // Tests ==================================================================================== Tests
 #[test]
 fn test_parse_day() {
-    assert_eq!(parse_day("1").unwrap(), 1);
-    assert_eq!(parse_day("25").unwrap(), 25);
+    assert_eq!(parse_day("1").unwrap(), DaySpec::Day(1));
+    assert_eq!(parse_day("25").unwrap(), DaySpec::Day(25));
     assert_eq!(
         parse_day("26").unwrap_err(),
         "The day should be between 1 and 25."
     );
+    assert_eq!(parse_day("latest").unwrap(), DaySpec::Latest);
+    assert_eq!(parse_day("+1").unwrap(), DaySpec::Offset(1));
+    assert!(parse_day("foo").is_err());
+    assert!(parse_day("-").is_err());
 }
 
 #[test]
 fn test_parse_year() {
-    assert_eq!(parse_year("2015").unwrap(), 2015);
-    assert_eq!(parse_year("2023").unwrap(), 2023);
+    assert_eq!(parse_year("2015").unwrap(), YearSpec::Year(2015));
+    assert_eq!(parse_year("2023").unwrap(), YearSpec::Year(2023));
+    assert_eq!(
+        parse_year("2014").unwrap_err(),
+        "The year should be between 2015 and the current year"
+    );
+    assert_eq!(
+        parse_year(&(get_current_year(&calendar::now()) + 1).to_string()).unwrap_err(),
+        "The year should be between 2015 and the current year"
+    );
+    assert_eq!(parse_year("current").unwrap(), YearSpec::Current);
+    assert_eq!(parse_year("last").unwrap(), YearSpec::Last);
+    assert!(parse_year("foo").is_err());
+}
+
+#[test]
+fn test_parse_selections() {
+    assert!(parse_days("1..=5").is_ok());
+    assert!(parse_days("3,7,12").is_ok());
+    assert!(parse_days("1..=26").is_err());
+    assert!(parse_years("2015..2018").is_ok());
+    assert!(parse_years("2016,last").is_ok());
     assert_eq!(
-        parse_year("2024").unwrap_err(),
+        parse_years("2014..=2016").unwrap_err(),
         "The year should be between 2015 and the current year"
     );
 }
 
+#[test]
+fn test_parse_limits() {
+    assert_eq!(parse_timeout("1.5"), Ok(Duration::from_millis(1500)));
+    assert_eq!(parse_timeout("0"), Ok(Duration::ZERO));
+    assert!(parse_timeout("inf").is_err());
+    assert!(parse_timeout("1e30").is_err());
+    assert!(parse_timeout("-1").is_err());
+
+    assert_eq!(parse_memory_limit("512"), Ok(512 * 1024 * 1024));
+    assert!(parse_memory_limit("18446744073709551615").is_err());
+}
+
 #[test]
 fn test_get_current_year() {
-    assert_eq!(get_current_year(), 2023);
+    assert_eq!(get_current_year(&calendar::at(2023, 12, 1, 0)), 2023);
+    // New Year's Eve in the unlock timezone, already New Year's Day in UTC
+    assert_eq!(get_current_year(&calendar::at(2023, 12, 31, 22)), 2023);
 }
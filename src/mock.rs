@@ -0,0 +1,742 @@
+///
+/// # src/mock.rs
+/// Contains a local mock of the Advent of Code website, to develop and test the network
+/// commands offline. Point them at it with `AOC_BASE_URL=http://localhost:3000/`.
+///
+/// The pages are served from a fixtures folder:
+/// ```text
+/// fixtures/
+/// ├── session                 // the expected session cookie, any one is accepted if missing
+/// ├── 2023/
+/// │   ├── index.html          // any other page: <path>/index.html
+/// │   ├── 1/
+/// │   │   ├── puzzle.html     // /2023/day/1, a generated page if missing
+/// │   │   ├── input.txt       // /2023/day/1/input
+/// │   │   └── answers.toml    // part_1 = "142", part_2 = "281", checked by /2023/day/1/answer
+/// │   └── leaderboard/
+/// │       └── 123456.json     // /2023/leaderboard/private/view/123456.json
+/// └── ...
+/// ```
+///
+/// `{{user}}` in an HTML fixture is replaced by the name of the user when the request is logged in,
+/// and removed otherwise, e.g. `<div class="user">{{user}}</div>`.
+///
+/// The answers are judged like the website does: right, wrong, too high or too low, and a new
+/// answer sent too soon after a wrong one is rate-limited.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use toml_edit::DocumentMut;
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs::read_to_string,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use crate::config::get_string;
+use crate::http::fnv1a;
+
+// Variables  =========================================================================== Variables
+/// The name of the mocked user, shown on every page when logged in.
+const MOCK_USER: &str = "mock-user";
+
+/// The state of the mock server.
+pub struct MockServer {
+    /// The fixtures folder
+    fixtures: PathBuf,
+    /// The expected session cookie, `None` to accept any
+    session: Option<String>,
+    /// The delay to wait after a wrong answer
+    cooldown: Duration,
+    /// The time of the last wrong answer of every puzzle
+    last_wrong: HashMap<(u16, u8), Instant>,
+    /// The solved parts: (year, day, part)
+    solved: HashSet<(u16, u8, u8)>,
+}
+
+/// A request received by the server.
+struct Request {
+    /// The method, `GET` or `POST`
+    method: String,
+    /// The path, without query string
+    path: String,
+    /// The session cookie
+    session: Option<String>,
+    /// The ETag of the cached response of the client
+    if_none_match: Option<String>,
+    /// The body, for the `POST` requests
+    body: String,
+}
+
+/// A response sent by the server.
+struct Reply {
+    /// The status code
+    status: u16,
+    /// The content type
+    content_type: &'static str,
+    /// The body
+    body: String,
+}
+
+// Functions  =========================================================================== Functions
+impl MockServer {
+    ///
+    /// # new
+    /// Creates a mock server.
+    ///
+    /// ## Arguments
+    /// * `fixtures` - The fixtures folder
+    /// * `cooldown` - The delay to wait after a wrong answer
+    ///
+    /// ## Returns
+    /// * `MockServer` - The server
+    pub fn new(fixtures: PathBuf, cooldown: Duration) -> MockServer {
+        let session = read_to_string(fixtures.join("session"))
+            .ok()
+            .map(|session| session.trim().to_string())
+            .filter(|session| !session.is_empty());
+
+        MockServer {
+            fixtures,
+            session,
+            cooldown,
+            last_wrong: HashMap::new(),
+            solved: HashSet::new(),
+        }
+    }
+
+    ///
+    /// # serve
+    /// Serves the requests, one at a time, until the process is stopped.
+    ///
+    /// ## Arguments
+    /// * `port` - The port to listen on, on localhost
+    ///
+    /// ## Returns
+    /// * `Result<(), String>` - An error if the port could not be bound
+    pub fn serve(&mut self, port: u16) -> Result<(), String> {
+        let listener = TcpListener::bind(("127.0.0.1", port))
+            .map_err(|e| format!("Failed to listen on port {}: {}", port, e))?;
+
+        println!(
+            "Mock Advent of Code server listening on http://localhost:{}/ (fixtures: {})",
+            port,
+            self.fixtures.display()
+        );
+        self.listen(listener);
+
+        Ok(())
+    }
+
+    ///
+    /// # listen
+    /// Answers the connections of a listener, one at a time, until the process is stopped.
+    ///
+    /// ## Arguments
+    /// * `listener` - The bound listener
+    ///
+    /// ## Returns
+    /// * `()` - Nothing, it never returns while the listener accepts connections
+    fn listen(&mut self, listener: TcpListener) {
+        for stream in listener.incoming().flatten() {
+            if let Err(e) = self.answer_connection(stream) {
+                eprintln!("{}", e);
+            }
+        }
+    }
+
+    ///
+    /// # answer_connection
+    /// Reads a request from a connection and writes its response.
+    ///
+    /// ## Arguments
+    /// * `stream` - The connection
+    ///
+    /// ## Returns
+    /// * `Result<(), String>` - An error if the connection failed
+    fn answer_connection(&mut self, mut stream: TcpStream) -> Result<(), String> {
+        let request = read_request(&stream)?;
+        let reply = self.handle(&request);
+
+        let etag = format!("\"{:016x}\"", fnv1a(reply.body.as_bytes()));
+        let (status, body) = match &request.if_none_match {
+            Some(if_none_match) if reply.status == 200 && *if_none_match == etag => (304, ""),
+            _ => (reply.status, reply.body.as_str()),
+        };
+
+        println!("{} {} -> {}", request.method, request.path, status);
+
+        let response = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nETag: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            reason(status),
+            reply.content_type,
+            body.len(),
+            etag,
+            body
+        );
+
+        stream
+            .write_all(response.as_bytes())
+            .map_err(|e| format!("Failed to send the response: {}", e))
+    }
+
+    ///
+    /// # handle
+    /// Returns the response to a request.
+    ///
+    /// ## Arguments
+    /// * `request` - The request
+    ///
+    /// ## Returns
+    /// * `Reply` - The response
+    fn handle(&mut self, request: &Request) -> Reply {
+        let logged_in = match (&self.session, &request.session) {
+            (Some(expected), Some(session)) => expected == session,
+            (None, Some(session)) => !session.is_empty(),
+            (_, None) => false,
+        };
+
+        let segments = request
+            .path
+            .trim_matches('/')
+            .split('/')
+            .collect::<Vec<_>>();
+
+        match (request.method.as_str(), segments.as_slice()) {
+            ("GET", ["settings"]) if logged_in => html(200, &page("<p>Settings</p>", true)),
+            ("GET", ["settings"]) => html(302, &page("<p>[Log In]</p>", false)),
+            ("GET", [year, "day", day]) => match puzzle_id(year, day) {
+                Some((year, day)) => self.puzzle(year, day, logged_in),
+                None => not_found(),
+            },
+            ("GET", [year, "day", day, "input"]) => match puzzle_id(year, day) {
+                Some(_) if !logged_in => Reply {
+                    status: 400,
+                    content_type: "text/plain",
+                    body:
+                        "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+                            .to_string(),
+                },
+                Some((year, day)) => self.fixture(&format!("{}/{}/input.txt", year, day)),
+                None => not_found(),
+            },
+            ("POST", [year, "day", day, "answer"]) => match puzzle_id(year, day) {
+                Some(_) if !logged_in => html(400, &page("<p>Please log in.</p>", false)),
+                Some((year, day)) => {
+                    let verdict = self.judge(year, day, &request.body);
+                    html(
+                        200,
+                        &page(&format!("<article><p>{}</p></article>", verdict), true),
+                    )
+                }
+                None => not_found(),
+            },
+            ("GET", [year, "leaderboard", "private", "view", file]) if logged_in => {
+                let mut reply = self.fixture(&format!("{}/leaderboard/{}", year, file));
+                reply.content_type = "application/json";
+                reply
+            }
+            ("GET", [_, "leaderboard", "private", "view", _]) => html(302, &page("", false)),
+            ("GET", _) => {
+                let mut reply =
+                    self.fixture(&format!("{}/index.html", request.path.trim_matches('/')));
+                reply.body = with_user(&reply.body, logged_in);
+                reply
+            }
+            _ => Reply {
+                status: 405,
+                content_type: "text/plain",
+                body: "Method not allowed\n".to_string(),
+            },
+        }
+    }
+
+    ///
+    /// # puzzle
+    /// Returns the page of a puzzle: the fixture if it exists, a generated one otherwise.
+    /// The second part is only shown once the first one has been solved.
+    ///
+    /// ## Arguments
+    /// * `year` - The year of the puzzle
+    /// * `day` - The day of the puzzle
+    /// * `logged_in` - Whether the request has a valid session
+    ///
+    /// ## Returns
+    /// * `Reply` - The response
+    fn puzzle(&self, year: u16, day: u8, logged_in: bool) -> Reply {
+        if let Ok(fixture) =
+            read_to_string(self.fixtures.join(format!("{}/{}/puzzle.html", year, day)))
+        {
+            return html(200, &with_user(&fixture, logged_in));
+        }
+        if !self.fixtures.join(format!("{}/{}", year, day)).is_dir() {
+            return not_found();
+        }
+
+        let mut content = format!(
+            "<article class=\"day-desc\"><h2>--- Day {}: Mock Puzzle ---</h2>\
+            <p>The mocked puzzle of the day {} of {}.</p></article>",
+            day, day, year
+        );
+
+        if logged_in && self.solved.contains(&(year, day, 1)) {
+            if let Some(answer) = self.expected(year, day, 1) {
+                content.push_str(&format!(
+                    "<p>Your puzzle answer was <code>{}</code>.</p>",
+                    answer
+                ));
+            }
+            content.push_str(
+                "<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>\
+                <p>The second part of the mocked puzzle.</p></article>",
+            );
+        }
+        if logged_in && self.solved.contains(&(year, day, 2)) {
+            if let Some(answer) = self.expected(year, day, 2) {
+                content.push_str(&format!(
+                    "<p>Your puzzle answer was <code>{}</code>.</p>",
+                    answer
+                ));
+            }
+        }
+
+        html(200, &page(&content, logged_in))
+    }
+
+    ///
+    /// # judge
+    /// Judges an answer sent to `/YEAR/day/N/answer`, with the messages of the website.
+    ///
+    /// ## Arguments
+    /// * `year` - The year of the puzzle
+    /// * `day` - The day of the puzzle
+    /// * `form` - The url-encoded body: `level=1&answer=42`
+    ///
+    /// ## Returns
+    /// * `String` - The verdict
+    fn judge(&mut self, year: u16, day: u8, form: &str) -> String {
+        let field = |name: &str| {
+            form.split('&')
+                .filter_map(|pair| pair.split_once('='))
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| decode(value).trim().to_string())
+        };
+        let part = field("level")
+            .and_then(|level| level.parse::<u8>().ok())
+            .unwrap_or(1);
+        let answer = field("answer").unwrap_or_default();
+
+        if let Some(left) = self
+            .last_wrong
+            .get(&(year, day))
+            .and_then(|last| self.cooldown.checked_sub(last.elapsed()))
+        {
+            return format!(
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                before trying again.  You have {}s left to wait.",
+                left.as_secs().max(1)
+            );
+        }
+
+        if self.solved.contains(&(year, day, part))
+            || (part == 2 && !self.solved.contains(&(year, day, 1)))
+        {
+            return "You don't seem to be solving the right level.  Did you already complete it?"
+                .to_string();
+        }
+
+        let Some(expected) = self.expected(year, day, part) else {
+            return "You don't seem to be solving the right level.  Did you already complete it?"
+                .to_string();
+        };
+
+        if answer == expected {
+            self.solved.insert((year, day, part));
+            return "That's the right answer!  You are one gold star closer to saving Christmas."
+                .to_string();
+        }
+
+        self.last_wrong.insert((year, day), Instant::now());
+
+        let hint = match (answer.parse::<i64>(), expected.parse::<i64>()) {
+            (Ok(answer), Ok(expected)) if answer > expected => "  your answer is too high.",
+            (Ok(answer), Ok(expected)) if answer < expected => "  your answer is too low.",
+            _ => "",
+        };
+
+        format!(
+            "That's not the right answer;{}  Please wait {}s before trying again.",
+            hint,
+            self.cooldown.as_secs()
+        )
+    }
+
+    ///
+    /// # expected
+    /// Returns the expected answer of a part, from the `answers.toml` fixture of the puzzle.
+    ///
+    /// ## Arguments
+    /// * `year` - The year of the puzzle
+    /// * `day` - The day of the puzzle
+    /// * `part` - The part, 1 or 2
+    ///
+    /// ## Returns
+    /// * `Option<String>` - The expected answer
+    fn expected(&self, year: u16, day: u8, part: u8) -> Option<String> {
+        let answers = read_to_string(self.fixtures.join(format!("{}/{}/answers.toml", year, day)))
+            .ok()?
+            .parse::<DocumentMut>()
+            .ok()?;
+
+        get_string(answers.as_table(), &format!("part_{}", part)).ok()?
+    }
+
+    ///
+    /// # fixture
+    /// Returns a fixture file.
+    ///
+    /// ## Arguments
+    /// * `path` - The path of the fixture, relative to the fixtures folder
+    ///
+    /// ## Returns
+    /// * `Reply` - The fixture, or a 404 response
+    fn fixture(&self, path: &str) -> Reply {
+        if path.split('/').any(|segment| segment == "..") {
+            return not_found();
+        }
+
+        match read_to_string(self.fixtures.join(path)) {
+            Ok(body) => Reply {
+                status: 200,
+                content_type: if path.ends_with(".html") {
+                    "text/html"
+                } else {
+                    "text/plain"
+                },
+                body,
+            },
+            Err(_) => not_found(),
+        }
+    }
+}
+
+///
+/// # read_request
+/// Reads a request from a connection.
+///
+/// ## Arguments
+/// * `stream` - The connection
+///
+/// ## Returns
+/// * `Result<Request, String>` - The request
+fn read_request(stream: &TcpStream) -> Result<Request, String> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+
+    reader
+        .read_line(&mut line)
+        .map_err(|e| format!("Failed to read the request: {}", e))?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts
+        .next()
+        .unwrap_or_default()
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .to_string();
+
+    let mut session = None;
+    let mut if_none_match = None;
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        reader
+            .read_line(&mut header)
+            .map_err(|e| format!("Failed to read the request: {}", e))?;
+
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        let Some((name, value)) = header.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+
+        match name.to_ascii_lowercase().as_str() {
+            "cookie" => {
+                session = value
+                    .split(';')
+                    .filter_map(|cookie| cookie.trim().strip_prefix("session="))
+                    .map(str::to_string)
+                    .next()
+            }
+            "if-none-match" => if_none_match = Some(value.to_string()),
+            "content-length" => content_length = value.parse().unwrap_or_default(),
+            _ => {}
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|e| format!("Failed to read the request: {}", e))?;
+
+    Ok(Request {
+        method,
+        path,
+        session,
+        if_none_match,
+        body: String::from_utf8_lossy(&body).to_string(),
+    })
+}
+
+///
+/// # puzzle_id
+/// Parses the year and day segments of a puzzle path.
+///
+/// ## Arguments
+/// * `year` - The year segment
+/// * `day` - The day segment
+///
+/// ## Returns
+/// * `Option<(u16, u8)>` - The year and day, `None` if they are not numbers
+fn puzzle_id(year: &str, day: &str) -> Option<(u16, u8)> {
+    Some((year.parse().ok()?, day.parse().ok()?))
+}
+
+///
+/// # page
+/// Wraps content in a page of the website, with the user shown when logged in.
+///
+/// ## Arguments
+/// * `content` - The content of the page
+/// * `logged_in` - Whether the user is logged in
+///
+/// ## Returns
+/// * `String` - The page
+fn page(content: &str, logged_in: bool) -> String {
+    let user = if logged_in {
+        format!(
+            "<div class=\"user\">{} <span class=\"star-count\">0*</span></div>",
+            MOCK_USER
+        )
+    } else {
+        "<div><a href=\"/auth/login\">[Log In]</a></div>".to_string()
+    };
+
+    format!(
+        "<!DOCTYPE html>\n<html><head><title>Advent of Code (mock)</title></head>\
+        <body><header>{}</header><main>{}</main></body></html>\n",
+        user, content
+    )
+}
+
+///
+/// # with_user
+/// Replaces the `{{user}}` placeholder of a fixture by the name of the user, when logged in.
+///
+/// ## Arguments
+/// * `fixture` - The content of the fixture
+/// * `logged_in` - Whether the user is logged in
+///
+/// ## Returns
+/// * `String` - The content, with the placeholder replaced
+fn with_user(fixture: &str, logged_in: bool) -> String {
+    fixture.replace("{{user}}", if logged_in { MOCK_USER } else { "" })
+}
+
+///
+/// # html
+/// Returns an HTML response.
+///
+/// ## Arguments
+/// * `status` - The status code
+/// * `body` - The page
+///
+/// ## Returns
+/// * `Reply` - The response
+fn html(status: u16, body: &str) -> Reply {
+    Reply {
+        status,
+        content_type: "text/html",
+        body: body.to_string(),
+    }
+}
+
+///
+/// # not_found
+/// Returns the 404 response.
+///
+/// ## Returns
+/// * `Reply` - The response
+fn not_found() -> Reply {
+    Reply {
+        status: 404,
+        content_type: "text/plain",
+        body: "404 Not Found\n".to_string(),
+    }
+}
+
+///
+/// # decode
+/// Decodes a value of a URL-encoded form.
+///
+/// ## Arguments
+/// * `value` - The encoded value
+///
+/// ## Returns
+/// * `String` - The decoded value
+fn decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+            (b'+', _) => decoded.push(b' '),
+            (byte, _) => decoded.push(byte),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+///
+/// # reason
+/// Returns the reason phrase of a status code.
+///
+/// ## Arguments
+/// * `status` - The status code
+///
+/// ## Returns
+/// * `&str` - The reason phrase
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        302 => "Found",
+        304 => "Not Modified",
+        400 => "Bad Request",
+        404 => "Not Found",
+        _ => "Method Not Allowed",
+    }
+}
+
+// Tests ==================================================================================== Tests
+#[cfg(test)]
+pub fn fixtures(name: &str) -> PathBuf {
+    let fixtures = std::env::temp_dir().join(format!("aoc-mock-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(fixtures.join("2023/1")).unwrap();
+    std::fs::write(fixtures.join("session"), "abc123\n").unwrap();
+    std::fs::write(fixtures.join("2023/1/input.txt"), "1\n2\n").unwrap();
+    std::fs::write(
+        fixtures.join("2023/1/answers.toml"),
+        "part_1 = \"142\"\npart_2 = \"281\"\n",
+    )
+    .unwrap();
+
+    fixtures
+}
+
+#[cfg(test)]
+pub fn start(fixtures: PathBuf) -> String {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+
+    let mut server = MockServer::new(fixtures, Duration::from_secs(60));
+    std::thread::spawn(move || server.listen(listener));
+
+    url
+}
+
+#[cfg(test)]
+fn request(method: &str, path: &str, session: Option<&str>, body: &str) -> Request {
+    Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        session: session.map(str::to_string),
+        if_none_match: None,
+        body: body.to_string(),
+    }
+}
+
+#[test]
+fn test_session_check() {
+    let mut server = MockServer::new(fixtures("session"), Duration::from_secs(60));
+
+    let input = server.handle(&request("GET", "/2023/day/1/input", Some("abc123"), ""));
+    assert_eq!((input.status, input.body.as_str()), (200, "1\n2\n"));
+
+    let logged_out = server.handle(&request("GET", "/2023/day/1/input", Some("other"), ""));
+    assert_eq!(logged_out.status, 400);
+    assert!(logged_out.body.contains("Puzzle inputs differ by user"));
+
+    let settings = server.handle(&request("GET", "/settings", Some("abc123"), ""));
+    assert!(settings.body.contains("<div class=\"user\">mock-user"));
+
+    // Every page served logged out is detected, not only the input
+    for path in ["/2023/day/1", "/settings"] {
+        let page = server.handle(&request("GET", path, Some("other"), ""));
+        assert!(crate::http::check_session(&page.body, true).is_err());
+    }
+}
+
+#[test]
+fn test_judge() {
+    let mut server = MockServer::new(fixtures("judge"), Duration::from_secs(60));
+
+    assert!(server
+        .judge(2023, 1, "level=2&answer=281")
+        .contains("right level"));
+    assert!(server
+        .judge(2023, 1, "level=1&answer=200")
+        .contains("too high"));
+    assert!(server
+        .judge(2023, 1, "level=1&answer=142")
+        .contains("too recently"));
+
+    assert_eq!(decode("6%2C4+5"), "6,4 5");
+
+    server.last_wrong.clear();
+    assert!(server
+        .judge(2023, 1, "level=1&answer=100")
+        .contains("too low"));
+
+    server.last_wrong.clear();
+    assert!(server
+        .judge(2023, 1, "level=1&answer=142")
+        .contains("right answer"));
+    assert!(server
+        .judge(2023, 1, "level=1&answer=142")
+        .contains("already complete"));
+
+    let puzzle = server.handle(&request("GET", "/2023/day/1", Some("abc123"), ""));
+    assert!(puzzle
+        .body
+        .contains("Your puzzle answer was <code>142</code>"));
+    assert!(puzzle.body.contains("--- Part Two ---"));
+}
+
+/*
+ * End of file src/mock.rs
+ */
@@ -0,0 +1,201 @@
+///
+/// # src/output.rs
+/// Contains the machine-readable output of the commands, selected with `--format json`.
+///
+/// Every command prints a single JSON document, with the version of its schema and the name of the
+/// command. The version is incremented on every breaking change of a schema; new fields may be
+/// added without one.
+/// ```json
+/// { "schema_version": 1, "command": "verify", "results": [...], "failures": 0 }
+/// ```
+///
+/// The durations of the runs are in nanoseconds, the solve times in seconds, the memory in bytes
+/// and the times of the leaderboards are unix timestamps.
+///
+/// The escaping of the XML reports and of the HTML pages of the site is shared here too.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use clap::ValueEnum;
+use serde_json::{json, Value};
+
+use std::time::Duration;
+
+use crate::runner::{Allocations, Outcome, PartRun};
+
+// Variables  =========================================================================== Variables
+/// The version of the schema of the JSON documents.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The formats the output of a command can be printed in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Lines to read in the terminal
+    Text,
+    /// A single JSON document, see src/output.rs
+    Json,
+}
+
+// Functions  =========================================================================== Functions
+///
+/// # document
+/// Builds the JSON document printed by a command.
+///
+/// ## Arguments
+/// * `command` - The name of the command
+/// * `data` - The fields of the document, an object
+///
+/// ## Returns
+/// * `String` - The document, pretty-printed and ending with a line break
+pub fn document(command: &str, data: Value) -> String {
+    let mut document = json!({
+        "schema_version": SCHEMA_VERSION,
+        "command": command,
+    });
+    if let (Some(document), Value::Object(data)) = (document.as_object_mut(), data) {
+        document.extend(data);
+    }
+
+    serde_json::to_string_pretty(&document).unwrap_or_default() + "\n"
+}
+
+///
+/// # part_run
+/// Returns the JSON object of the execution of a part.
+///
+/// ## Arguments
+/// * `part` - The part, 1 or 2
+/// * `run` - The execution of the part
+///
+/// ## Returns
+/// * `Value` - The object
+pub fn part_run(part: u8, run: &PartRun) -> Value {
+    json!({
+        "part": part,
+        "answer": run.answer,
+        "outcome": outcome(&run.outcome),
+        "duration_ns": nanoseconds(run.duration),
+        "peak_memory_bytes": run.peak_memory,
+        "allocations": run.allocations.as_ref().map(allocations),
+        "output": run.output,
+    })
+}
+
+///
+/// # outcome
+/// Returns the JSON object of how the execution of a part ended, e.g.
+/// `{ "status": "timed_out", "timeout_ms": 1000 }`.
+///
+/// ## Arguments
+/// * `outcome` - How the execution ended
+///
+/// ## Returns
+/// * `Value` - The object
+pub fn outcome(outcome: &Outcome) -> Value {
+    match outcome {
+        Outcome::Success => json!({ "status": "success" }),
+        Outcome::Failed => json!({ "status": "failed" }),
+        Outcome::TimedOut(timeout) => {
+            json!({ "status": "timed_out", "timeout_ms": timeout.as_millis() as u64 })
+        }
+        Outcome::OutOfMemory(limit) => {
+            json!({ "status": "out_of_memory", "memory_limit_bytes": limit })
+        }
+    }
+}
+
+///
+/// # allocations
+/// Returns the JSON object of the allocations of a part.
+///
+/// ## Arguments
+/// * `allocations` - The allocations
+///
+/// ## Returns
+/// * `Value` - The object
+pub fn allocations(allocations: &Allocations) -> Value {
+    json!({ "count": allocations.count, "bytes": allocations.bytes })
+}
+
+///
+/// # nanoseconds
+/// Returns a duration in nanoseconds, as stored in the documents.
+///
+/// ## Arguments
+/// * `duration` - The duration
+///
+/// ## Returns
+/// * `u64` - The nanoseconds, saturated
+pub fn nanoseconds(duration: Duration) -> u64 {
+    u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX)
+}
+
+///
+/// # escape
+/// Escapes a text for an XML or HTML attribute or element, dropping the characters XML forbids,
+/// such as the escape sequences of colored outputs.
+///
+/// ## Arguments
+/// * `text` - The text
+///
+/// ## Returns
+/// * `String` - The escaped text
+pub fn escape(text: &str) -> String {
+    text.chars()
+        .filter(|c| !c.is_control() || matches!(c, '\n' | '\t' | '\r'))
+        .fold(String::with_capacity(text.len()), |mut escaped, c| {
+            match c {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                '\'' => escaped.push_str("&apos;"),
+                _ => escaped.push(c),
+            }
+            escaped
+        })
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_document() {
+    let document = document("doctor", json!({ "checks": [] }));
+    let parsed = serde_json::from_str::<Value>(&document).unwrap();
+
+    assert_eq!(
+        parsed,
+        json!({ "schema_version": SCHEMA_VERSION, "command": "doctor", "checks": [] })
+    );
+    assert!(document.ends_with("}\n"));
+}
+
+#[test]
+fn test_part_run() {
+    let run = PartRun {
+        answer: Some("42".to_string()),
+        output: "42\n".to_string(),
+        duration: Duration::from_micros(1500),
+        outcome: Outcome::TimedOut(Duration::from_secs(2)),
+        peak_memory: Some(4096),
+        allocations: None,
+    };
+
+    assert_eq!(
+        part_run(1, &run),
+        json!({
+            "part": 1,
+            "answer": "42",
+            "outcome": { "status": "timed_out", "timeout_ms": 2000 },
+            "duration_ns": 1_500_000,
+            "peak_memory_bytes": 4096,
+            "allocations": null,
+            "output": "42\n",
+        })
+    );
+}
+
+/*
+ * End of file src/output.rs
+ */
@@ -0,0 +1,161 @@
+///
+/// # src/profile.rs
+/// Contains the account profiles of a project.
+///
+/// A profile is an Advent of Code account, with its own session and its own inputs: the inputs
+/// of a named profile are stored in a sub-folder of the inputs of the year, e.g.
+/// `data/year_2023/inputs/partner/day_01.txt`. The `default` profile uses the inputs of the
+/// layout, which are the ones included by the solutions.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use std::{
+    fmt,
+    fs::read_dir,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::layout::Layout;
+
+// Variables  =========================================================================== Variables
+/// The name of the profile used when none is given.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// An account profile, `None` being the default one.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Profile(Option<String>);
+
+// Functions  =========================================================================== Functions
+impl FromStr for Profile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == DEFAULT_PROFILE {
+            return Ok(Profile(None));
+        }
+
+        if s.is_empty()
+            || !s
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(
+                "A profile name should only contain letters, digits, '-' and '_'.".to_string(),
+            );
+        }
+
+        Ok(Profile(Some(s.to_string())))
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl Profile {
+    ///
+    /// # name
+    /// Returns the name of the profile.
+    ///
+    /// ## Returns
+    /// * `&str` - The name, `default` for the default profile
+    pub fn name(&self) -> &str {
+        self.0.as_deref().unwrap_or(DEFAULT_PROFILE)
+    }
+
+    ///
+    /// # is_default
+    /// Checks whether this is the default profile.
+    ///
+    /// ## Returns
+    /// * `bool` - `true` for the default profile
+    pub fn is_default(&self) -> bool {
+        self.0.is_none()
+    }
+
+    ///
+    /// # input
+    /// Returns the path of the input of a day for this profile, relative to the project root.
+    ///
+    /// ## Arguments
+    /// * `layout` - The layout of the project
+    /// * `year` - The year of the Advent of Code challenge
+    /// * `day` - The day of the Advent of Code challenge
+    ///
+    /// ## Returns
+    /// * `PathBuf` - The path of the input
+    pub fn input(&self, layout: &Layout, year: u16, day: u8) -> PathBuf {
+        let input = layout.input(year, day);
+
+        match (&self.0, input.parent(), input.file_name()) {
+            (Some(name), Some(folder), Some(file_name)) => folder.join(name).join(file_name),
+            _ => input,
+        }
+    }
+}
+
+///
+/// # profiles_with_input
+/// Returns the profiles having an input for the given day: the default one and every sub-folder
+/// of the inputs folder containing the input of the day.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `layout` - The layout of the project
+/// * `year` - The year of the Advent of Code challenge
+/// * `day` - The day of the Advent of Code challenge
+///
+/// ## Returns
+/// * `Vec<Profile>` - The profiles, sorted by name after the default one
+pub fn profiles_with_input(caller: &Path, layout: &Layout, year: u16, day: u8) -> Vec<Profile> {
+    let mut profiles = vec![Profile::default()];
+
+    let input = caller.join(layout.input(year, day));
+    let Some(folder) = input.parent().and_then(|folder| read_dir(folder).ok()) else {
+        return profiles;
+    };
+
+    let mut named = folder
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().to_str()?.parse::<Profile>().ok())
+        .filter(|profile| !profile.is_default())
+        .filter(|profile| caller.join(profile.input(layout, year, day)).is_file())
+        .collect::<Vec<_>>();
+    named.sort_by(|a, b| a.name().cmp(b.name()));
+
+    profiles.extend(named);
+    profiles
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_profile() {
+    let layout = Layout::default();
+
+    let default = "default".parse::<Profile>().unwrap();
+    assert!(default.is_default());
+    assert_eq!(
+        default.input(&layout, 2023, 1),
+        PathBuf::from("data/year_2023/inputs/day_01.txt")
+    );
+
+    let partner = "partner".parse::<Profile>().unwrap();
+    assert_eq!(partner.name(), "partner");
+    assert_eq!(
+        partner.input(&layout, 2023, 1),
+        PathBuf::from("data/year_2023/inputs/partner/day_01.txt")
+    );
+
+    assert!("".parse::<Profile>().is_err());
+    assert!("../other".parse::<Profile>().is_err());
+}
+
+/*
+ * End of file src/profile.rs
+ */
@@ -0,0 +1,181 @@
+///
+/// # src/puzzle.rs
+/// Contains the conversion of a puzzle page of the Advent of Code website to markdown.
+///
+/// Only the `<article class="day-desc">` elements are kept, one per unlocked part, and only the
+/// few tags they use are converted: headings, paragraphs, code blocks, inline code, emphasis,
+/// links and lists. Other tags are dropped, keeping their text.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Variables  =========================================================================== Variables
+/// The opening tag of the description of a part.
+const ARTICLE_START: &str = "<article class=\"day-desc\">";
+
+/// The closing tag of the description of a part.
+const ARTICLE_END: &str = "</article>";
+
+// Functions  =========================================================================== Functions
+///
+/// # to_markdown
+/// Converts the descriptions of a puzzle page to markdown.
+///
+/// ## Arguments
+/// * `page` - The HTML of the puzzle page
+///
+/// ## Returns
+/// * `Option<String>` - The markdown, `None` if the page contains no description
+pub fn to_markdown(page: &str) -> Option<String> {
+    let mut parts = Vec::new();
+    let mut rest = page;
+
+    while let Some(start) = rest.find(ARTICLE_START) {
+        let content = &rest[start + ARTICLE_START.len()..];
+        let end = content.find(ARTICLE_END).unwrap_or(content.len());
+
+        parts.push(convert(&content[..end]).trim().to_string());
+        rest = &content[end..];
+    }
+
+    if parts.is_empty() {
+        return None;
+    }
+
+    Some(parts.join("\n\n") + "\n")
+}
+
+///
+/// # convert
+/// Converts a fragment of HTML to markdown.
+///
+/// ## Arguments
+/// * `html` - The HTML fragment
+///
+/// ## Returns
+/// * `String` - The markdown
+fn convert(html: &str) -> String {
+    let mut markdown = String::with_capacity(html.len());
+    let mut links = Vec::new();
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        markdown.push_str(&unescape(&rest[..start]));
+
+        let Some(end) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let name = tag
+            .trim_start_matches('/')
+            .split_whitespace()
+            .next()
+            .unwrap_or_default();
+        let closing = tag.starts_with('/');
+
+        // Inside code blocks, only the text is kept
+        if in_pre && name != "pre" {
+            continue;
+        }
+
+        match (name, closing) {
+            ("h2", false) => markdown.push_str("## "),
+            ("h2", true) | ("p", true) | ("ul", true) => markdown.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                markdown.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("```\n\n");
+            }
+            ("code", _) => markdown.push('`'),
+            ("em", _) => markdown.push('*'),
+            ("li", false) => markdown.push_str("- "),
+            ("li", true) => markdown.push('\n'),
+            ("a", false) => {
+                links.push(attribute(tag, "href"));
+                markdown.push('[');
+            }
+            ("a", true) => match links.pop().flatten() {
+                Some(href) => markdown.push_str(&format!("]({})", href)),
+                None => markdown.push(']'),
+            },
+            _ => {}
+        }
+    }
+
+    markdown.push_str(&unescape(rest));
+    markdown
+}
+
+///
+/// # attribute
+/// Returns the value of an attribute of a tag.
+///
+/// ## Arguments
+/// * `tag` - The content of the tag, between `<` and `>`
+/// * `name` - The name of the attribute
+///
+/// ## Returns
+/// * `Option<String>` - The unescaped value, `None` if the tag has no such attribute
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{}=\"", name))? + name.len() + 2;
+    let end = tag[start..].find('"')? + start;
+
+    Some(unescape(&tag[start..end]))
+}
+
+///
+/// # unescape
+/// Replaces the HTML entities used by the website.
+///
+/// ## Arguments
+/// * `text` - The escaped text
+///
+/// ## Returns
+/// * `String` - The unescaped text
+pub fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_to_markdown() {
+    let page = "<html><main>\
+        <article class=\"day-desc\"><h2>--- Day 1: Trebuchet?! ---</h2>\
+        <p>Something is <em>wrong</em> with <a href=\"/2023/about\">global snow</a> &amp; more.</p>\
+        <pre><code>1abc2\n<em>pqr3stu8vwx</em>\n</code></pre>\
+        <ul><li>a <code>x &lt; y</code></li><li>b</li></ul>\
+        </article><p>Your puzzle answer was <code>142</code>.</p>\
+        <article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>Again.</p></article>\
+        </main></html>";
+
+    assert_eq!(
+        to_markdown(page).unwrap(),
+        "## --- Day 1: Trebuchet?! ---\n\n\
+        Something is *wrong* with [global snow](/2023/about) & more.\n\n\
+        ```\n1abc2\npqr3stu8vwx\n```\n\n\
+        - a `x < y`\n- b\n\n\
+        ## --- Part Two ---\n\n\
+        Again.\n"
+    );
+    assert_eq!(to_markdown("<p>Please log in.</p>"), None);
+}
+
+/*
+ * End of file src/puzzle.rs
+ */
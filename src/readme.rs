@@ -0,0 +1,418 @@
+///
+/// # src/readme.rs
+/// Contains the progress section of the README of the project and the star badges of every year,
+/// generated by `aoc readme`.
+///
+/// The section lies between two markers, everything outside of them is left untouched, and a
+/// README with only one of them is not updated. It is appended to the README the first time:
+/// ```markdown
+/// <!-- aoc:progress:start -->
+/// ### 2023 ![AoC 2023: 4/50 stars](badges/2023.svg)
+///
+/// | Day | Title | Stars | Runtime | Solution |
+/// | --: | :-- | :-: | --: | :-- |
+/// | 1 | Trebuchet?! | ⭐⭐ | 2.26ms | [day_01.rs](src/bin/year_2023/day_01.rs) |
+/// <!-- aoc:progress:end -->
+/// ```
+///
+/// The stars come from the answers file, the second star of the last day being counted once every
+/// other star is earned, and the runtimes from the latest `aoc bench` of each day.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use chrono::{DateTime, FixedOffset};
+
+use crate::benchmarks::Benchmarks;
+use crate::calendar::{days_in_year, latest_event_year, FIRST_YEAR};
+use crate::config::Config;
+use crate::ledger::Ledger;
+use crate::profile::Profile;
+use crate::spec::YearSelection;
+use crate::template::puzzle_title;
+
+// Variables  =========================================================================== Variables
+/// The marker starting the generated section.
+const START_MARKER: &str = "<!-- aoc:progress:start -->";
+
+/// The marker ending the generated section.
+const END_MARKER: &str = "<!-- aoc:progress:end -->";
+
+/// The progress of a day with a solution.
+pub struct DayRow {
+    /// The day of the Advent of Code challenge
+    pub day: u8,
+    /// The title of the puzzle
+    pub title: String,
+    /// The number of accepted answers
+    pub stars: u8,
+    /// The median duration of each part in the latest benchmark
+    pub medians: [Option<Duration>; 2],
+    /// The peak resident memory of each part in the latest benchmark, in bytes
+    pub peak_memories: [Option<u64>; 2],
+    /// The solution file, relative to the project root
+    pub solution: PathBuf,
+}
+
+// Functions  =========================================================================== Functions
+impl DayRow {
+    ///
+    /// # runtime
+    /// Returns the runtime of the day, the sum of the medians of its parts.
+    ///
+    /// ## Returns
+    /// * `Option<Duration>` - The runtime, `None` if the day was never benchmarked
+    pub fn runtime(&self) -> Option<Duration> {
+        self.medians
+            .iter()
+            .flatten()
+            .copied()
+            .reduce(|total, median| total + median)
+    }
+}
+
+///
+/// # progress
+/// Returns the progress of the selected years, leaving out the years without any day.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `config` - The configuration of the project
+/// * `profile` - The profile whose answers and benchmarks are shown
+/// * `years` - The selected years, every year of the event if `None`
+/// * `now` - The current time, in the unlock timezone
+/// * `puzzles` - Whether the days with a downloaded puzzle but no solution are kept
+///
+/// ## Returns
+/// * `Result<Vec<(u16, Vec<DayRow>)>, String>` - The progress of every year, the latest first
+pub fn progress(
+    caller: &Path,
+    config: &Config,
+    profile: &Profile,
+    years: Option<&YearSelection>,
+    now: &DateTime<FixedOffset>,
+    puzzles: bool,
+) -> Result<Vec<(u16, Vec<DayRow>)>, String> {
+    let years = match years {
+        Some(years) => years.resolve(now)?,
+        None => (FIRST_YEAR..=latest_event_year(now)).collect(),
+    };
+
+    let mut progress = Vec::new();
+    for year in years.into_iter().rev() {
+        let rows = rows(caller, config, profile, year, puzzles)?;
+        if !rows.is_empty() {
+            progress.push((year, rows));
+        }
+    }
+
+    Ok(progress)
+}
+
+///
+/// # rows
+/// Returns the progress of every day of a year with a solution.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `config` - The configuration of the project
+/// * `profile` - The profile whose answers and benchmarks are shown
+/// * `year` - The year of the Advent of Code challenge
+/// * `puzzles` - Whether the days with a downloaded puzzle but no solution are kept
+///
+/// ## Returns
+/// * `Result<Vec<DayRow>, String>` - The progress of the days, in order
+pub fn rows(
+    caller: &Path,
+    config: &Config,
+    profile: &Profile,
+    year: u16,
+    puzzles: bool,
+) -> Result<Vec<DayRow>, String> {
+    let layout = &config.layout;
+    let ledger = Ledger::load(caller, layout, year)?;
+    let benchmarks = Benchmarks::load(caller, layout, year)?;
+
+    let answered = |day| {
+        (1..=2)
+            .filter(|part| ledger.answer(profile, day, *part).is_some())
+            .count() as u8
+    };
+    // The second star of the last day has no answer, it is given once every other star is earned
+    let last = days_in_year(year);
+    let finished = answered(last) == 1 && (1..last).all(|day| answered(day) == 2);
+
+    Ok((1..=days_in_year(year))
+        .filter(|day| {
+            caller.join(layout.solution(year, *day)).exists()
+                || (puzzles && caller.join(layout.puzzle(year, *day)).exists())
+        })
+        .map(|day| DayRow {
+            day,
+            title: puzzle_title(&caller.join(layout.puzzle(year, day)), day),
+            stars: if day == last && finished {
+                2
+            } else {
+                answered(day)
+            },
+            medians: [1, 2].map(|part| benchmarks.median(profile, day, part)),
+            peak_memories: [1, 2].map(|part| benchmarks.peak_memory(profile, day, part)),
+            solution: layout.solution(year, day),
+        })
+        .collect())
+}
+
+///
+/// # render_section
+/// Renders the progress section of the README, markers included.
+///
+/// ## Arguments
+/// * `years` - The progress of every year, the latest first
+/// * `badges` - The folder of the badges, relative to the project root
+///
+/// ## Returns
+/// * `String` - The section
+pub fn render_section(years: &[(u16, Vec<DayRow>)], badges: &Path) -> String {
+    let mut section = format!("{}\n", START_MARKER);
+
+    for (year, rows) in years {
+        let stars = rows.iter().map(|row| u32::from(row.stars)).sum::<u32>();
+        section.push_str(&format!(
+            "### {} ![{}]({})\n\n\
+            | Day | Title | Stars | Runtime | Solution |\n\
+            | --: | :-- | :-: | --: | :-- |\n",
+            year,
+            badge_label(*year, stars),
+            badges.join(format!("{}.svg", year)).display()
+        ));
+
+        for row in rows {
+            section.push_str(&format!(
+                "| {} | {} | {} | {} | [{}]({}) |\n",
+                row.day,
+                row.title.replace('|', "\\|"),
+                "⭐".repeat(usize::from(row.stars)),
+                row.runtime()
+                    .map_or("-".to_string(), |runtime| format!("{:.2?}", runtime)),
+                row.solution
+                    .file_name()
+                    .map(|name| name.to_string_lossy())
+                    .unwrap_or_default(),
+                row.solution.display()
+            ));
+        }
+        section.push('\n');
+    }
+
+    section.push_str(END_MARKER);
+    section.push('\n');
+
+    section
+}
+
+///
+/// # update_section
+/// Replaces the progress section of a README, or appends it if the README has none yet.
+///
+/// ## Arguments
+/// * `readme` - The content of the README
+/// * `section` - The new section, markers included
+///
+/// ## Returns
+/// * `Result<String, String>` - The updated README, an error if only one of the markers is found
+pub fn update_section(readme: &str, section: &str) -> Result<String, String> {
+    match (readme.find(START_MARKER), readme.find(END_MARKER)) {
+        (Some(start), Some(end)) if start < end => {
+            let end = end + END_MARKER.len();
+            // The line break of the end marker belongs to the section
+            let end = end + usize::from(readme[end..].starts_with('\n'));
+
+            Ok(format!("{}{}{}", &readme[..start], section, &readme[end..]))
+        }
+        (None, None) if readme.trim().is_empty() => Ok(section.to_string()),
+        (None, None) => Ok(format!("{}\n\n{}", readme.trim_end(), section)),
+        _ => Err(format!(
+            "The progress markers of the README are unbalanced, it should have a {} line \
+            followed by a {} line.",
+            START_MARKER, END_MARKER
+        )),
+    }
+}
+
+///
+/// # badge
+/// Renders the star badge of a year, in the flat style of the usual README badges.
+///
+/// ## Arguments
+/// * `year` - The year of the Advent of Code challenge
+/// * `stars` - The number of stars
+///
+/// ## Returns
+/// * `String` - The SVG of the badge
+pub fn badge(year: u16, stars: u32) -> String {
+    let max = u32::from(days_in_year(year)) * 2;
+    let label = format!("AoC {}", year);
+    let value = format!("{}/{} ★", stars, max);
+    let color = match stars {
+        0 => "#9f9f9f",
+        stars if stars >= max => "#4c1",
+        _ => "#dfb317",
+    };
+
+    // Verdana is about 7px wide at 11px
+    let label_width = label.chars().count() as u32 * 7 + 10;
+    let value_width = value.chars().count() as u32 * 7 + 10;
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"20\" role=\"img\" \
+        aria-label=\"{title}\">\n\
+        \x20 <title>{title}</title>\n\
+        \x20 <rect width=\"{label_width}\" height=\"20\" fill=\"#555\"/>\n\
+        \x20 <rect x=\"{label_width}\" width=\"{value_width}\" height=\"20\" fill=\"{color}\"/>\n\
+        \x20 <g fill=\"#fff\" text-anchor=\"middle\" \
+        font-family=\"Verdana,Geneva,DejaVu Sans,sans-serif\" font-size=\"11\">\n\
+        \x20   <text x=\"{label_x}\" y=\"14\">{label}</text>\n\
+        \x20   <text x=\"{value_x}\" y=\"14\">{value}</text>\n\
+        \x20 </g>\n\
+        </svg>\n",
+        width = label_width + value_width,
+        title = badge_label(year, stars),
+        label_x = label_width / 2,
+        value_x = label_width + value_width / 2,
+    )
+}
+
+///
+/// # badge_label
+/// Returns the text alternative of the badge of a year.
+///
+/// ## Arguments
+/// * `year` - The year of the Advent of Code challenge
+/// * `stars` - The number of stars
+///
+/// ## Returns
+/// * `String` - The label, e.g. `AoC 2023: 34/50 stars`
+fn badge_label(year: u16, stars: u32) -> String {
+    format!(
+        "AoC {}: {}/{} stars",
+        year,
+        stars,
+        u32::from(days_in_year(year)) * 2
+    )
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_render_section() {
+    let rows = vec![
+        DayRow {
+            day: 1,
+            title: "Trebuchet?!".to_string(),
+            stars: 2,
+            medians: [
+                Some(Duration::from_millis(2)),
+                Some(Duration::from_millis(3)),
+            ],
+            peak_memories: [Some(9 << 20), None],
+            solution: PathBuf::from("src/bin/year_2023/day_01.rs"),
+        },
+        DayRow {
+            day: 2,
+            title: "A | B".to_string(),
+            stars: 1,
+            medians: [None, None],
+            peak_memories: [None, None],
+            solution: PathBuf::from("src/bin/year_2023/day_02.rs"),
+        },
+    ];
+
+    assert_eq!(
+        render_section(&[(2023, rows)], Path::new("badges")),
+        "<!-- aoc:progress:start -->\n\
+         ### 2023 ![AoC 2023: 3/50 stars](badges/2023.svg)\n\
+         \n\
+         | Day | Title | Stars | Runtime | Solution |\n\
+         | --: | :-- | :-: | --: | :-- |\n\
+         | 1 | Trebuchet?! | ⭐⭐ | 5.00ms | [day_01.rs](src/bin/year_2023/day_01.rs) |\n\
+         | 2 | A \\| B | ⭐ | - | [day_02.rs](src/bin/year_2023/day_02.rs) |\n\
+         \n\
+         <!-- aoc:progress:end -->\n"
+    );
+}
+
+#[test]
+fn test_update_section() {
+    let section = "<!-- aoc:progress:start -->\nnew\n<!-- aoc:progress:end -->\n";
+
+    assert_eq!(
+        update_section(
+            "# Title\n<!-- aoc:progress:start -->\nold\n<!-- aoc:progress:end -->\nFooter\n",
+            section
+        )
+        .unwrap(),
+        format!("# Title\n{}Footer\n", section)
+    );
+    assert_eq!(
+        update_section("# Title\n\nIntro\n", section).unwrap(),
+        format!("# Title\n\nIntro\n\n{}", section)
+    );
+    assert_eq!(update_section("", section).unwrap(), section);
+    // A marker without its pair is never completed by a second section
+    assert!(update_section("<!-- aoc:progress:end -->\n", section).is_err());
+    assert!(update_section("# Title\n<!-- aoc:progress:start -->\nold\n", section).is_err());
+    assert!(update_section(
+        "<!-- aoc:progress:end -->\n<!-- aoc:progress:start -->\n",
+        section
+    )
+    .is_err());
+}
+
+#[test]
+fn test_rows() {
+    let caller = crate::file_utils::temp_folder("readme-rows");
+    let config = Config::default();
+    let profile = Profile::default();
+    let mut ledger = Ledger::load(&caller, &config.layout, 2023).unwrap();
+    for day in 1..=25 {
+        let solution = caller.join(config.layout.solution(2023, day));
+        crate::file_utils::create_folder(solution.parent().unwrap());
+        std::fs::write(solution, "").unwrap();
+        ledger.set_answer(&profile, day, 1, "1").unwrap();
+    }
+    ledger.save().unwrap();
+
+    let stars = |caller: &Path| {
+        rows(caller, &config, &profile, 2023, false)
+            .unwrap()
+            .iter()
+            .map(|row| u32::from(row.stars))
+            .sum::<u32>()
+    };
+    assert_eq!(stars(&caller), 25);
+
+    // Every part 2 but the one of the last day, which has no answer
+    for day in 1..25 {
+        ledger.set_answer(&profile, day, 2, "2").unwrap();
+    }
+    ledger.save().unwrap();
+    assert_eq!(stars(&caller), 50);
+}
+
+#[test]
+fn test_badge() {
+    let badge = badge(2025, 24);
+
+    assert!(badge.contains("aria-label=\"AoC 2025: 24/24 stars\""));
+    assert!(badge.contains(">24/24 ★</text>"));
+    assert!(badge.contains("fill=\"#4c1\""));
+}
+
+/*
+ * End of file src/readme.rs
+ */
@@ -0,0 +1,843 @@
+///
+/// # src/runner.rs
+/// Contains the compilation and execution of the solution of a day, used by `aoc run` and
+/// `aoc verify`.
+///
+/// The solution is compiled in release mode through a generated 'src/main.rs' which runs the part
+/// given as argument. The answer of a part is the last line it prints.
+///
+/// As the solutions include their input at compile time, a named profile is run on a mirror of
+/// the solution under `target/aoc/<profile>/`, in which the input of the profile replaces the
+/// default one.
+///
+/// Every part runs in a child process with a wall-clock timeout and, on Linux, an address-space
+/// limit (setrlimit), configured in the `[run]` table of `aoc.toml`, so that an infinite loop or a
+/// runaway allocation is reported instead of hanging the machine:
+/// ```toml
+/// [run]
+/// timeout_ms = 60000  # 0 disables the timeout
+/// memory_mb = 2048    # no limit by default
+/// ```
+///
+/// The peak resident memory of every part is read from wait4 on Linux. As the kernel accounts the
+/// memory of the spawning process until the exec, it never goes below a few MiB. When asked, the
+/// solution is compiled with the `count-alloc` feature, which installs a counting global allocator
+/// in the generated main: the part then reports its number of allocations and the bytes allocated.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use toml_edit::{ArrayOfTables, DocumentMut, Table};
+
+use std::{
+    fmt,
+    fs::{self, read_to_string, write},
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process::{Child, Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::config::Config;
+use crate::export::{copy_file, rewrite_includes, write_file};
+use crate::layout::relative_path;
+use crate::profile::Profile;
+
+// Variables  =========================================================================== Variables
+/// The folder holding the mirrors of the solutions run on the input of a named profile.
+const MIRRORS_FOLDER: &str = "target/aoc";
+
+/// The message printed by the Rust runtime when an allocation fails.
+const ALLOCATION_FAILED: &str = "memory allocation of";
+
+/// The feature of the solutions enabling the counting global allocator of the generated main.
+const COUNT_ALLOC_FEATURE: &str = "count-alloc";
+
+/// The comment following the `count-alloc` feature added to `Cargo.toml` for a build, which tells
+/// it apart from a feature of the same name written by the user.
+const COUNT_ALLOC_MARKER: &str = " # added by aoc for the build, removed after it";
+
+/// The prefix of the line printed on stderr by the counting allocator, followed by the number of
+/// allocations and the bytes allocated.
+const ALLOCATIONS_MARKER: &str = "aoc-allocations:";
+
+/// The counting global allocator of the generated main, compiled with the `count-alloc` feature.
+const COUNTING_ALLOCATOR: &str = "\
+#[cfg(feature = \"count-alloc\")]
+mod allocations {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering::Relaxed};
+
+    pub static COUNT: AtomicU64 = AtomicU64::new(0);
+    pub static BYTES: AtomicU64 = AtomicU64::new(0);
+
+    struct Counter;
+
+    unsafe impl GlobalAlloc for Counter {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            COUNT.fetch_add(1, Relaxed);
+            BYTES.fetch_add(layout.size() as u64, Relaxed);
+            unsafe { System.alloc(layout) }
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            unsafe { System.dealloc(ptr, layout) }
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            COUNT.fetch_add(1, Relaxed);
+            BYTES.fetch_add(new_size.saturating_sub(layout.size()) as u64, Relaxed);
+            unsafe { System.realloc(ptr, layout, new_size) }
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: Counter = Counter;
+
+    pub fn report() {
+        eprintln!(\"aoc-allocations: {} {}\", COUNT.load(Relaxed), BYTES.load(Relaxed));
+    }
+}
+
+";
+
+/// The limits of the execution of a part, the `[run]` table of `aoc.toml`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Limits {
+    /// The wall-clock time after which the part is killed, `None` for no limit
+    pub timeout: Option<Duration>,
+    /// The maximum size of the address space of the part in bytes, `None` for no limit
+    pub memory: Option<u64>,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            timeout: Some(Duration::from_secs(60)),
+            memory: None,
+        }
+    }
+}
+
+/// How the execution of a part ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// The part exited successfully
+    Success,
+    /// The part exited with an error or crashed
+    Failed,
+    /// The part has been killed after the timeout
+    TimedOut(Duration),
+    /// The part could not allocate memory within the limit, in bytes
+    OutOfMemory(u64),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Success => write!(f, "ok"),
+            Outcome::Failed => write!(f, "failed"),
+            Outcome::TimedOut(timeout) => write!(f, "timed out after {:.2?}", timeout),
+            Outcome::OutOfMemory(limit) => {
+                write!(f, "out of memory, limit of {} MiB", limit / (1024 * 1024))
+            }
+        }
+    }
+}
+
+/// How a child process exited.
+struct Exit {
+    /// The exit status, `None` if the child was killed after the timeout
+    status: Option<ExitStatus>,
+    /// The peak resident memory of the child in bytes, `None` if unknown
+    peak_memory: Option<u64>,
+    /// When the child exited
+    exited_at: Instant,
+}
+
+/// The result of the execution of a part.
+pub struct PartRun {
+    /// The last line printed by the part, `None` if it printed nothing
+    pub answer: Option<String>,
+    /// Everything the part printed
+    pub output: String,
+    /// The wall-clock time of the execution
+    pub duration: Duration,
+    /// How the execution ended
+    pub outcome: Outcome,
+    /// The peak resident memory of the part in bytes, `None` if unknown
+    pub peak_memory: Option<u64>,
+    /// The allocations of the part, `None` unless compiled with the `count-alloc` feature
+    pub allocations: Option<Allocations>,
+}
+
+/// The allocations of a part, counted by the global allocator of the generated main.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Allocations {
+    /// The number of allocations and reallocations
+    pub count: u64,
+    /// The bytes allocated, the growth only for the reallocations
+    pub bytes: u64,
+}
+
+// Functions  =========================================================================== Functions
+///
+/// # build
+/// Compiles the solution of a day for the given profile.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `config` - The configuration of the project
+/// * `year` - The year of the Advent of Code challenge
+/// * `day` - The day of the Advent of Code challenge
+/// * `profile` - The profile whose input is used
+/// * `count_allocations` - Whether to compile the counting allocator of the generated main
+///
+/// ## Returns
+/// * `Result<PathBuf, String>` - The path of the compiled binary
+pub fn build(
+    caller: &Path,
+    config: &Config,
+    year: u16,
+    day: u8,
+    profile: &Profile,
+    count_allocations: bool,
+) -> Result<PathBuf, String> {
+    let layout = &config.layout;
+    let solution = layout.solution(year, day);
+    let input = profile.input(layout, year, day);
+
+    if !caller.join(&solution).exists() {
+        return Err(format!("{} does not exist.", solution.display()));
+    }
+    if !caller.join(&input).exists() {
+        return Err(format!(
+            "{} does not exist, the profile '{}' has no input for this day.",
+            input.display(),
+            profile
+        ));
+    }
+
+    let compiled = if profile.is_default() {
+        solution
+    } else {
+        let mirror = Path::new(MIRRORS_FOLDER).join(profile.name());
+        let code = read_to_string(caller.join(&solution))
+            .map_err(|e| format!("Failed to read {}: {}", solution.display(), e))?;
+
+        let code = rewrite_includes(
+            caller,
+            &solution,
+            &code,
+            &[],
+            &solution,
+            &caller.join(&mirror),
+        )?;
+        write_file(&caller.join(&mirror), &solution, &code)?;
+        copy_file(
+            &caller.join(&input),
+            &caller.join(&mirror),
+            &layout.input(year, day),
+        )?;
+
+        mirror.join(solution)
+    };
+
+    prepare_run_main(caller, &compiled, count_allocations);
+
+    update_cargo_toml(caller, day, year, true, count_allocations)
+        .map_err(|e| format!("Failed to update Cargo.toml: {}", e))?;
+
+    let mut command = Command::new("cargo");
+    command
+        .current_dir(caller)
+        .args(["build", "--release", "--quiet"]);
+    if count_allocations {
+        command.args(["--features", COUNT_ALLOC_FEATURE]);
+    }
+    let status = command.status();
+
+    if let Err(e) = update_cargo_toml(caller, day, year, false, false) {
+        eprintln!("Failed to clean up Cargo.toml: {}", e);
+    }
+
+    match status {
+        Ok(status) if status.success() => Ok(caller
+            .join("target/release")
+            .join(format!("day_{:02}_year_{}", day, year))),
+        Ok(_) => Err("Failed to compile the solution".to_string()),
+        Err(e) => Err(format!("Failed to execute the build command: {}", e)),
+    }
+}
+
+///
+/// # run_part
+/// Runs a part of a compiled solution in a child process, within the limits.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `binary` - The compiled solution
+/// * `part` - The part to run, 1 or 2
+/// * `limits` - The limits of the execution
+///
+/// ## Returns
+/// * `Result<PartRun, String>` - The result of the execution, an error if it could not start
+pub fn run_part(
+    caller: &Path,
+    binary: &Path,
+    part: u8,
+    limits: &Limits,
+) -> Result<PartRun, String> {
+    let mut command = Command::new(binary);
+    command
+        .current_dir(caller)
+        .arg(part.to_string())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(memory) = limits.memory {
+        limit_memory(&mut command, memory);
+    }
+
+    let start = Instant::now();
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("Failed to run {}: {}", binary.display(), e))?;
+
+    // Read the outputs while the part runs, a full pipe would block it
+    let stdout = read_in_background(child.stdout.take());
+    let stderr = read_in_background(child.stderr.take());
+
+    let Exit {
+        status,
+        peak_memory,
+        exited_at,
+    } = wait(&mut child, limits.timeout)
+        .map_err(|e| format!("Failed to run {}: {}", binary.display(), e))?;
+    let duration = exited_at.duration_since(start);
+
+    let stdout = stdout.join().unwrap_or_default();
+    let (stderr, allocations) = allocations(&stderr.join().unwrap_or_default());
+
+    let outcome = match (status, limits.memory) {
+        (Some(status), _) if status.success() => Outcome::Success,
+        (Some(_), Some(memory)) if stderr.contains(ALLOCATION_FAILED) => {
+            Outcome::OutOfMemory(memory)
+        }
+        (Some(_), _) => Outcome::Failed,
+        (None, _) => Outcome::TimedOut(limits.timeout.unwrap_or_default()),
+    };
+
+    let mut printed = stdout.clone();
+    if outcome != Outcome::Success {
+        printed.push_str(&stderr);
+    }
+
+    Ok(PartRun {
+        answer: answer(&stdout),
+        output: printed,
+        duration,
+        outcome,
+        peak_memory,
+        allocations,
+    })
+}
+
+///
+/// # allocations
+/// Separates the allocations reported by the counting allocator from the rest of the stderr of
+/// a part.
+///
+/// ## Arguments
+/// * `stderr` - The stderr of the part
+///
+/// ## Returns
+/// * `(String, Option<Allocations>)` - The stderr without the report, and the allocations
+fn allocations(stderr: &str) -> (String, Option<Allocations>) {
+    let mut allocations = None;
+    let mut rest = String::with_capacity(stderr.len());
+
+    for line in stderr.lines() {
+        let counts = line
+            .strip_prefix(ALLOCATIONS_MARKER)
+            .and_then(|counts| counts.trim().split_once(' '))
+            .and_then(|(count, bytes)| Some((count.parse().ok()?, bytes.parse().ok()?)));
+
+        match counts {
+            Some((count, bytes)) => allocations = Some(Allocations { count, bytes }),
+            None => {
+                rest.push_str(line);
+                rest.push('\n');
+            }
+        }
+    }
+
+    (rest, allocations)
+}
+
+///
+/// # format_bytes
+/// Formats a number of bytes with a binary unit, e.g. `12.4 MiB`.
+///
+/// ## Arguments
+/// * `bytes` - The number of bytes
+///
+/// ## Returns
+/// * `String` - The formatted size
+pub fn format_bytes(bytes: u64) -> String {
+    let mut size = bytes as f64;
+    for unit in ["B", "KiB", "MiB"] {
+        if size < 1024.0 {
+            return match unit {
+                "B" => format!("{} B", bytes),
+                _ => format!("{:.1} {}", size, unit),
+            };
+        }
+        size /= 1024.0;
+    }
+
+    format!("{:.1} GiB", size)
+}
+
+///
+/// # wait
+/// Waits for a child process to exit, killing it after the timeout, and reads its peak resident
+/// memory with wait4.
+///
+/// The exit is awaited without reaping the child (waitid with WNOWAIT) on another thread, which
+/// notes when it happened, while this thread enforces the timeout: the clock stops as soon as the
+/// child exits, and the child cannot be reaped, and its pid reused, before it is killed.
+///
+/// ## Arguments
+/// * `child` - The child process
+/// * `timeout` - The timeout, `None` to wait forever
+///
+/// ## Returns
+/// * `io::Result<Exit>` - How the child exited
+#[cfg(target_os = "linux")]
+fn wait(child: &mut Child, timeout: Option<Duration>) -> io::Result<Exit> {
+    use std::os::unix::process::ExitStatusExt;
+    use std::sync::mpsc::{self, RecvTimeoutError};
+
+    let pid = child.id() as libc::pid_t;
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let _ = sender.send(wait_exit(pid).map(|_| Instant::now()));
+    });
+
+    let disconnected = || io::Error::other("the waiting thread stopped");
+    let (exited_at, killed) = match timeout {
+        Some(timeout) => match receiver.recv_timeout(timeout) {
+            Ok(exited_at) => (exited_at?, false),
+            Err(RecvTimeoutError::Timeout) => {
+                child.kill()?;
+                (receiver.recv().map_err(|_| disconnected())??, true)
+            }
+            Err(RecvTimeoutError::Disconnected) => return Err(disconnected()),
+        },
+        None => (receiver.recv().map_err(|_| disconnected())??, false),
+    };
+
+    let mut status = 0;
+    // SAFETY: rusage only holds integers, and wait4 fills it when the child has exited
+    let mut usage = unsafe { std::mem::zeroed::<libc::rusage>() };
+    while unsafe { libc::wait4(pid, &mut status, 0, &mut usage) } == -1 {
+        let error = io::Error::last_os_error();
+        if error.kind() != io::ErrorKind::Interrupted {
+            return Err(error);
+        }
+    }
+
+    Ok(Exit {
+        status: (!killed).then(|| ExitStatus::from_raw(status)),
+        // ru_maxrss is in KiB on Linux
+        peak_memory: Some(usage.ru_maxrss as u64 * 1024),
+        exited_at,
+    })
+}
+
+///
+/// # wait_exit
+/// Blocks until a child process exits, leaving it to be reaped.
+///
+/// ## Arguments
+/// * `pid` - The pid of the child process
+///
+/// ## Returns
+/// * `io::Result<()>` - Nothing, an error if the child cannot be waited for
+#[cfg(target_os = "linux")]
+fn wait_exit(pid: libc::pid_t) -> io::Result<()> {
+    // SAFETY: siginfo_t only holds integers, and waitid fills it when the child has exited
+    let mut info = unsafe { std::mem::zeroed::<libc::siginfo_t>() };
+    let options = libc::WEXITED | libc::WNOWAIT;
+
+    while unsafe { libc::waitid(libc::P_PID, pid as libc::id_t, &mut info, options) } == -1 {
+        let error = io::Error::last_os_error();
+        if error.kind() != io::ErrorKind::Interrupted {
+            return Err(error);
+        }
+    }
+
+    Ok(())
+}
+
+///
+/// # wait
+/// Waits for a child process to exit, killing it after the timeout. The peak resident memory is
+/// only read, and the exit only awaited without polling under a timeout, on Linux.
+///
+/// ## Arguments
+/// * `child` - The child process
+/// * `timeout` - The timeout, `None` to wait forever
+///
+/// ## Returns
+/// * `io::Result<Exit>` - How the child exited, without its peak resident memory
+#[cfg(not(target_os = "linux"))]
+fn wait(child: &mut Child, timeout: Option<Duration>) -> io::Result<Exit> {
+    let exit = |status| Exit {
+        status,
+        peak_memory: None,
+        exited_at: Instant::now(),
+    };
+
+    let Some(timeout) = timeout else {
+        return child.wait().map(|status| exit(Some(status)));
+    };
+
+    let start = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(exit(Some(status)));
+        }
+
+        if start.elapsed() >= timeout {
+            child.kill()?;
+            child.wait()?;
+            return Ok(exit(None));
+        }
+
+        thread::sleep(Duration::from_millis(1));
+    }
+}
+///
+/// # read_in_background
+/// Reads an output of a child process until its end, in another thread.
+///
+/// ## Arguments
+/// * `output` - The output
+///
+/// ## Returns
+/// * `thread::JoinHandle<String>` - The thread, returning everything read
+fn read_in_background(output: Option<impl Read + Send + 'static>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut output) = output {
+            let _ = output.read_to_end(&mut bytes);
+        }
+
+        String::from_utf8_lossy(&bytes).into_owned()
+    })
+}
+
+///
+/// # limit_memory
+/// Limits the address space of the process started by a command.
+///
+/// ## Arguments
+/// * `command` - The command
+/// * `bytes` - The maximum size of the address space
+///
+/// ## Returns
+/// * `()` - Nothing
+#[cfg(target_os = "linux")]
+fn limit_memory(command: &mut Command, bytes: u64) {
+    use std::os::unix::process::CommandExt;
+
+    let limit = libc::rlimit {
+        rlim_cur: bytes as libc::rlim_t,
+        rlim_max: bytes as libc::rlim_t,
+    };
+
+    // SAFETY: setrlimit is async-signal-safe, so it can run between fork and exec
+    unsafe {
+        command.pre_exec(move || {
+            if libc::setrlimit(libc::RLIMIT_AS, &limit) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(())
+        });
+    }
+}
+
+///
+/// # limit_memory
+/// Does nothing, the memory limit relies on setrlimit, only used on Linux.
+///
+/// ## Arguments
+/// * `_command` - The command
+/// * `_bytes` - The maximum size of the address space
+///
+/// ## Returns
+/// * `()` - Nothing
+#[cfg(not(target_os = "linux"))]
+fn limit_memory(_command: &mut Command, _bytes: u64) {}
+
+///
+/// # answer
+/// Returns the answer printed by a part: its last non-empty line.
+///
+/// ## Arguments
+/// * `stdout` - What the part printed
+///
+/// ## Returns
+/// * `Option<String>` - The answer, `None` if the part printed nothing
+fn answer(stdout: &str) -> Option<String> {
+    stdout
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+///
+/// # prepare_run_main
+/// Writes the 'src/main.rs' file running the part of the solution given as argument.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `solution` - The path of the solution to run, relative to the project root
+/// * `count_allocations` - If true, the counting global allocator and its report are included
+///
+/// ## Returns
+/// * `()` - Nothing
+fn prepare_run_main(caller: &Path, solution: &Path, count_allocations: bool) {
+    // Solutions may use the shared 'src/point.rs' module
+    let point = if caller.join("src/point.rs").exists() {
+        "#[path = \"point.rs\"]\nmod point;\n\n"
+    } else {
+        ""
+    };
+
+    // Only included when counting, as the feature is only declared for these builds
+    let (allocator, report) = if count_allocations {
+        (
+            COUNTING_ALLOCATOR,
+            "\n    #[cfg(feature = \"count-alloc\")]\n    allocations::report();\n",
+        )
+    } else {
+        ("", "")
+    };
+
+    let content = format!(
+        "\
+        ///\n\
+        /// # src/main.rs\n\
+        /// Contains the main function for the program, generated by `aoc run`.\n\
+        ///\n\
+        \n\
+        // Imports  ==============================================================================  Imports\n\
+        #[path = \"./{}\"]\n\
+        mod day;\n\
+        \n\
+        {}\
+        {}\
+        // Main  ====================================================================================  Main\n\
+        fn main() {{\n\
+        \x20   match std::env::args().nth(1).as_deref() {{\n\
+        \x20       Some(\"2\") => day::response_part_2(),\n\
+        \x20       _ => day::response_part_1(),\n\
+        \x20   }}\n\
+        {}\
+        }}\n\
+        ",
+        relative_path(Path::new("src"), solution),
+        point,
+        allocator,
+        report
+    );
+
+    write(caller.join("src/main.rs"), content).expect("Failed to write to file !");
+}
+
+///
+/// # update_cargo_toml
+///
+/// Updates the 'Cargo.toml' file by removing the existing [[bin]] sections and maybe adding a new one for the given day and year.
+///
+/// ## Arguments
+///
+/// * `caller` - The path of the caller
+/// * `day` - The day of the Advent of Code challenge
+/// * `year` - The year of the Advent of Code challenge
+/// * `add_bin` - If true, a new [[bin]] section will be added for the given day and year
+/// * `count_allocations` - If true, the `count-alloc` feature is added along the [[bin]] section
+///
+/// ## Returns
+/// * `Result<(), Box<dyn std::error::Error>>` - The result of the operation
+pub fn update_cargo_toml(
+    caller: &Path,
+    day: u8,
+    year: u16,
+    add_bin: bool,
+    count_allocations: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let cargo_toml_path = caller.join("Cargo.toml");
+    let toml_content = fs::read_to_string(&cargo_toml_path)?;
+    let mut doc = toml_content.parse::<DocumentMut>()?;
+
+    // Remove existing [[bin]] sections
+    doc.remove("bin");
+
+    // Remove the `count-alloc` feature added by a previous build, and [features] if it was alone
+    if let Some(features) = doc.get_mut("features").and_then(|item| item.as_table_mut()) {
+        let added = features
+            .get(COUNT_ALLOC_FEATURE)
+            .and_then(|feature| feature.as_value())
+            .and_then(|feature| feature.decor().suffix())
+            .and_then(|suffix| suffix.as_str())
+            == Some(COUNT_ALLOC_MARKER);
+        if added {
+            features.remove(COUNT_ALLOC_FEATURE);
+            if features.is_empty() {
+                doc.remove("features");
+            }
+        }
+    }
+
+    if add_bin {
+        let desired_name = format!("day_{:02}_year_{}", day, year);
+        let mut bin_array = ArrayOfTables::new();
+        let mut bin_table = Table::new();
+
+        bin_table.insert("name", toml_edit::value(desired_name));
+        bin_table.insert("path", toml_edit::value("src/main.rs"));
+        bin_array.push(bin_table);
+
+        doc["bin"] = toml_edit::Item::ArrayOfTables(bin_array);
+
+        // The counting allocator of the generated main is behind a feature of the solutions
+        if count_allocations
+            && doc
+                .get("features")
+                .and_then(|features| features.get(COUNT_ALLOC_FEATURE))
+                .is_none()
+        {
+            let features = doc
+                .entry("features")
+                .or_insert(toml_edit::Item::Table(Table::new()));
+            let mut feature = toml_edit::Value::Array(toml_edit::Array::new());
+            feature.decor_mut().set_suffix(COUNT_ALLOC_MARKER);
+            features[COUNT_ALLOC_FEATURE] = toml_edit::Item::Value(feature);
+        }
+    }
+
+    fs::write(&cargo_toml_path, doc.to_string())?;
+    Ok(())
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_answer() {
+    assert_eq!(answer("Day 01 - Part 1\n1234\n\n").as_deref(), Some("1234"));
+    assert_eq!(answer("\n  \n"), None);
+}
+
+#[test]
+fn test_allocations() {
+    let (rest, counted) = allocations("warning\naoc-allocations: 12 4096\n");
+
+    assert_eq!(rest, "warning\n");
+    assert_eq!(
+        counted,
+        Some(Allocations {
+            count: 12,
+            bytes: 4096
+        })
+    );
+    assert_eq!(allocations("aoc-allocations: x\n").1, None);
+}
+
+#[test]
+fn test_update_cargo_toml() {
+    let caller = crate::file_utils::temp_folder("cargo-toml");
+    let manifest = "[package]\nname = \"aoc\"\n\n[dependencies]\n";
+    fs::write(caller.join("Cargo.toml"), manifest).unwrap();
+
+    update_cargo_toml(&caller, 1, 2023, true, false).unwrap();
+    let built = fs::read_to_string(caller.join("Cargo.toml")).unwrap();
+    assert!(built.contains("name = \"day_01_year_2023\""));
+    assert!(!built.contains(COUNT_ALLOC_FEATURE));
+
+    update_cargo_toml(&caller, 1, 2023, true, true).unwrap();
+    let built = fs::read_to_string(caller.join("Cargo.toml")).unwrap();
+    assert!(built.contains("count-alloc = []"));
+
+    update_cargo_toml(&caller, 1, 2023, false, false).unwrap();
+    assert_eq!(
+        fs::read_to_string(caller.join("Cargo.toml")).unwrap(),
+        manifest
+    );
+
+    // A feature of the same name written by the user is kept
+    let manifest = "[package]\nname = \"aoc\"\n\n[features]\ncount-alloc = []\n";
+    fs::write(caller.join("Cargo.toml"), manifest).unwrap();
+
+    update_cargo_toml(&caller, 1, 2023, true, true).unwrap();
+    update_cargo_toml(&caller, 1, 2023, false, false).unwrap();
+    assert_eq!(
+        fs::read_to_string(caller.join("Cargo.toml")).unwrap(),
+        manifest
+    );
+}
+
+#[test]
+fn test_run_part_limits() {
+    let caller = crate::file_utils::temp_folder("limits");
+
+    // `sleep 2`, the part being the only argument
+    let limits = Limits {
+        timeout: Some(Duration::from_millis(200)),
+        memory: None,
+    };
+    let run = run_part(&caller, Path::new("sleep"), 2, &limits).unwrap();
+    assert_eq!(run.outcome, Outcome::TimedOut(Duration::from_millis(200)));
+    assert!(run.duration < Duration::from_secs(2));
+
+    if cfg!(target_os = "linux") {
+        let source = caller.join("allocate.rs");
+        std::fs::write(
+            &source,
+            "fn main() { println!(\"{}\", vec![1u8; 1 << 30].len()); }\n",
+        )
+        .unwrap();
+        let status = Command::new("rustc")
+            .arg(&source)
+            .arg("-o")
+            .arg(caller.join("allocate"))
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let limits = Limits {
+            timeout: None,
+            memory: Some(256 * 1024 * 1024),
+        };
+        let run = run_part(&caller, &caller.join("allocate"), 1, &limits).unwrap();
+        assert_eq!(run.outcome, Outcome::OutOfMemory(256 * 1024 * 1024));
+    }
+}
+
+#[test]
+fn test_format_bytes() {
+    assert_eq!(format_bytes(512), "512 B");
+    assert_eq!(format_bytes(1536), "1.5 KiB");
+    assert_eq!(format_bytes(12 * 1024 * 1024 + 400 * 1024), "12.4 MiB");
+    assert_eq!(format_bytes(3 << 30), "3.0 GiB");
+}
+
+/*
+ * End of file src/runner.rs
+ */
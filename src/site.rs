@@ -0,0 +1,645 @@
+///
+/// # src/site.rs
+/// Contains the static site generated by `aoc site`: the stored puzzle descriptions, the
+/// solutions with syntax highlighting, the latest benchmarks and the stars of every day.
+///
+/// The site has no dependency and works from the file system:
+/// ```text
+/// public/
+/// ├── index.html            the years and their stars
+/// ├── style.css
+/// └── 2023/
+///     ├── badge.svg
+///     ├── index.html        the days of the year
+///     └── day_01.html       the puzzle, the benchmarks and the solution of the day
+/// ```
+///
+/// Inputs must not be shared: they are never read, only the puzzle markdown files and the
+/// solution sources are.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use std::{
+    fs::read_to_string,
+    path::{Path, PathBuf},
+};
+
+use crate::calendar::days_in_year;
+use crate::config::Config;
+use crate::export::write_file;
+use crate::http::AOC_URL;
+use crate::output::escape;
+use crate::readme::{badge, DayRow};
+use crate::runner::format_bytes;
+
+// Variables  =========================================================================== Variables
+/// The keywords of Rust, highlighted in the solutions.
+const KEYWORDS: [&str; 38] = [
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while",
+];
+
+/// The stylesheet of the site, close to the colors of the Advent of Code website.
+const STYLE: &str = "\
+body { margin: 0; background: #0f0f23; color: #cccccc; font-family: 'Source Code Pro', monospace; }
+main { max-width: 60em; margin: 0 auto; padding: 1em; }
+a { color: #009900; text-decoration: none; }
+a:hover { color: #99ff99; }
+h1, h2 { color: #ffffff; font-weight: normal; }
+em { color: #ffffff; font-style: normal; text-shadow: 0 0 5px #ffffff; }
+code { background: #10101a; }
+pre { background: #10101a; border: 1px solid #333340; padding: 0.5em; overflow-x: auto; }
+table { border-collapse: collapse; }
+th, td { padding: 0.2em 0.8em; text-align: left; }
+td.number { text-align: right; }
+nav { display: flex; gap: 1em; margin-bottom: 1em; }
+.stars { color: #ffff66; }
+.keyword { color: #cc7832; }
+.type { color: #ffc66d; }
+.string { color: #6a8759; }
+.number { color: #6897bb; }
+.comment { color: #808080; }
+.macro { color: #9876aa; }
+";
+
+// Functions  =========================================================================== Functions
+///
+/// # write_site
+/// Writes the site of the given years, overwriting the pages of a previous run.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `config` - The configuration of the project
+/// * `years` - The progress of every year, the latest first, see `readme::progress`
+/// * `out` - The folder of the site
+///
+/// ## Returns
+/// * `Result<usize, String>` - The number of pages written
+pub fn write_site(
+    caller: &Path,
+    config: &Config,
+    years: &[(u16, Vec<DayRow>)],
+    out: &Path,
+) -> Result<usize, String> {
+    write_file(out, Path::new("style.css"), STYLE)?;
+    write_file(out, Path::new("index.html"), &index_page(years))?;
+    let mut pages = 1;
+
+    for (year, rows) in years {
+        let folder = PathBuf::from(year.to_string());
+        let stars = rows.iter().map(|row| u32::from(row.stars)).sum();
+
+        write_file(out, &folder.join("badge.svg"), &badge(*year, stars))?;
+        write_file(out, &folder.join("index.html"), &year_page(*year, rows))?;
+        pages += 1;
+
+        for (index, row) in rows.iter().enumerate() {
+            let neighbours = [
+                index.checked_sub(1).map(|previous| rows[previous].day),
+                rows.get(index + 1).map(|next| next.day),
+            ];
+            let puzzle = read_to_string(caller.join(config.layout.puzzle(*year, row.day))).ok();
+            let solution = read_to_string(caller.join(&row.solution)).ok();
+
+            write_file(
+                out,
+                &folder.join(format!("day_{:02}.html", row.day)),
+                &day_page(
+                    *year,
+                    row,
+                    neighbours,
+                    puzzle.as_deref(),
+                    solution.as_deref(),
+                ),
+            )?;
+            pages += 1;
+        }
+    }
+
+    Ok(pages)
+}
+
+///
+/// # index_page
+/// Renders the home page of the site, with the stars of every year.
+///
+/// ## Arguments
+/// * `years` - The progress of every year, the latest first
+///
+/// ## Returns
+/// * `String` - The HTML of the page
+fn index_page(years: &[(u16, Vec<DayRow>)]) -> String {
+    let mut body = String::from("<h1>Advent of Code</h1>\n<ul>\n");
+
+    for (year, rows) in years {
+        let stars = rows.iter().map(|row| u32::from(row.stars)).sum::<u32>();
+        body.push_str(&format!(
+            "  <li><a href=\"{year}/index.html\">{year}</a> \
+            <img src=\"{year}/badge.svg\" alt=\"{stars}/{max} stars\"></li>\n",
+            year = year,
+            stars = stars,
+            max = u32::from(days_in_year(*year)) * 2
+        ));
+    }
+    body.push_str("</ul>\n");
+
+    page("Advent of Code", "", &body)
+}
+
+///
+/// # year_page
+/// Renders the page of a year, with a row per day.
+///
+/// ## Arguments
+/// * `year` - The year of the Advent of Code challenge
+/// * `rows` - The progress of the days of the year
+///
+/// ## Returns
+/// * `String` - The HTML of the page
+fn year_page(year: u16, rows: &[DayRow]) -> String {
+    let mut body = format!(
+        "<nav><a href=\"../index.html\">Advent of Code</a></nav>\n\
+        <h1>{}</h1>\n\
+        <table>\n  <tr><th>Day</th><th>Title</th><th>Stars</th><th>Runtime</th></tr>\n",
+        year
+    );
+
+    for row in rows {
+        body.push_str(&format!(
+            "  <tr><td class=\"number\">{day}</td>\
+            <td><a href=\"day_{day:02}.html\">{title}</a></td>\
+            <td class=\"stars\">{stars}</td><td class=\"number\">{runtime}</td></tr>\n",
+            day = row.day,
+            title = escape(&row.title),
+            stars = "★".repeat(usize::from(row.stars)),
+            runtime = row
+                .runtime()
+                .map_or("-".to_string(), |runtime| format!("{:.2?}", runtime))
+        ));
+    }
+    body.push_str("</table>\n");
+
+    page(&year.to_string(), "../", &body)
+}
+
+///
+/// # day_page
+/// Renders the page of a day: its stars, its benchmarks, its puzzle and its solution.
+///
+/// ## Arguments
+/// * `year` - The year of the Advent of Code challenge
+/// * `row` - The progress of the day
+/// * `neighbours` - The previous and the next days of the year on the site
+/// * `puzzle` - The puzzle markdown, `None` if it was not downloaded
+/// * `solution` - The source of the solution, `None` if there is none yet
+///
+/// ## Returns
+/// * `String` - The HTML of the page
+fn day_page(
+    year: u16,
+    row: &DayRow,
+    neighbours: [Option<u8>; 2],
+    puzzle: Option<&str>,
+    solution: Option<&str>,
+) -> String {
+    let mut body = String::from("<nav><a href=\"../index.html\">Advent of Code</a>");
+    body.push_str(&format!("<a href=\"index.html\">{}</a>", year));
+    if let Some(previous) = neighbours[0] {
+        body.push_str(&format!(
+            "<a href=\"day_{:02}.html\">&larr; Day {}</a>",
+            previous, previous
+        ));
+    }
+    if let Some(next) = neighbours[1] {
+        body.push_str(&format!(
+            "<a href=\"day_{:02}.html\">Day {} &rarr;</a>",
+            next, next
+        ));
+    }
+    body.push_str("</nav>\n");
+
+    body.push_str(&format!(
+        "<h1>Day {}: {}</h1>\n<p class=\"stars\">{}</p>\n",
+        row.day,
+        escape(&row.title),
+        match row.stars {
+            0 => "No star yet".to_string(),
+            stars => "★".repeat(usize::from(stars)),
+        }
+    ));
+
+    if row.medians.iter().any(Option::is_some) {
+        body.push_str("<table>\n  <tr><th>Part</th><th>Median</th><th>Peak memory</th></tr>\n");
+        for (part, (median, peak_memory)) in row.medians.iter().zip(row.peak_memories).enumerate() {
+            body.push_str(&format!(
+                "  <tr><td class=\"number\">{}</td><td class=\"number\">{}</td>\
+                <td class=\"number\">{}</td></tr>\n",
+                part + 1,
+                median.map_or("-".to_string(), |median| format!("{:.2?}", median)),
+                peak_memory.map_or("-".to_string(), format_bytes)
+            ));
+        }
+        body.push_str("</table>\n");
+    }
+
+    match puzzle {
+        Some(puzzle) => body.push_str(&format!(
+            "<article>\n{}</article>\n",
+            markdown_to_html(puzzle)
+        )),
+        None => body.push_str("<p>The puzzle was not downloaded.</p>\n"),
+    }
+
+    body.push_str("<h2>Solution</h2>\n");
+    match solution {
+        Some(solution) => body.push_str(&format!(
+            "<p><code>{}</code></p>\n<pre><code>{}</code></pre>\n",
+            escape(&row.solution.display().to_string()),
+            highlight(solution)
+        )),
+        None => body.push_str("<p>No solution yet.</p>\n"),
+    }
+
+    page(&format!("{} day {}", year, row.day), "../", &body)
+}
+
+///
+/// # page
+/// Wraps the body of a page in an HTML document.
+///
+/// ## Arguments
+/// * `title` - The title of the page
+/// * `root` - The path from the page to the root of the site, e.g. `../`
+/// * `body` - The HTML of the body
+///
+/// ## Returns
+/// * `String` - The HTML document
+fn page(title: &str, root: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n\
+        <html lang=\"en\">\n\
+        <head>\n\
+        \x20 <meta charset=\"utf-8\">\n\
+        \x20 <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+        \x20 <title>{}</title>\n\
+        \x20 <link rel=\"stylesheet\" href=\"{}style.css\">\n\
+        </head>\n\
+        <body>\n<main>\n{}</main>\n</body>\n\
+        </html>\n",
+        escape(title),
+        root,
+        body
+    )
+}
+
+///
+/// # markdown_to_html
+/// Converts a puzzle markdown file to HTML. Only the markdown written by `aoc download` is
+/// supported, see src/puzzle.rs: headings, paragraphs, code blocks, lists, inline code, emphasis
+/// and links.
+///
+/// ## Arguments
+/// * `markdown` - The markdown
+///
+/// ## Returns
+/// * `String` - The HTML
+pub fn markdown_to_html(markdown: &str) -> String {
+    let mut html = String::new();
+    let mut paragraph = Vec::new();
+    let mut code_block: Option<String> = None;
+    let mut in_list = false;
+
+    for line in markdown.lines() {
+        if let Some(code) = &mut code_block {
+            if line.trim_end() == "```" {
+                html.push_str(&format!("<pre><code>{}</code></pre>\n", escape(code)));
+                code_block = None;
+            } else {
+                code.push_str(line);
+                code.push('\n');
+            }
+            continue;
+        }
+
+        let item = line.strip_prefix("- ");
+        if item.is_none() && in_list {
+            html.push_str("</ul>\n");
+            in_list = false;
+        }
+        // Every block ends the current paragraph
+        if item.is_some()
+            || line.trim().is_empty()
+            || line.starts_with("```")
+            || line.starts_with("## ")
+        {
+            end_paragraph(&mut html, &mut paragraph);
+        }
+
+        if line.starts_with("```") {
+            code_block = Some(String::new());
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            html.push_str(&format!("<h2>{}</h2>\n", inline(heading.trim())));
+        } else if let Some(item) = item {
+            if !in_list {
+                html.push_str("<ul>\n");
+                in_list = true;
+            }
+            html.push_str(&format!("<li>{}</li>\n", inline(item)));
+        } else if !line.trim().is_empty() {
+            paragraph.push(line);
+        }
+    }
+
+    end_paragraph(&mut html, &mut paragraph);
+    if in_list {
+        html.push_str("</ul>\n");
+    }
+    if let Some(code) = code_block {
+        html.push_str(&format!("<pre><code>{}</code></pre>\n", escape(&code)));
+    }
+
+    html
+}
+
+///
+/// # end_paragraph
+/// Writes the lines of the current paragraph, if any, and empties them.
+///
+/// ## Arguments
+/// * `html` - The HTML being written
+/// * `paragraph` - The lines of the paragraph
+///
+/// ## Returns
+/// * `()` - Nothing
+fn end_paragraph(html: &mut String, paragraph: &mut Vec<&str>) {
+    if !paragraph.is_empty() {
+        html.push_str(&format!("<p>{}</p>\n", inline(&paragraph.join("\n"))));
+        paragraph.clear();
+    }
+}
+
+///
+/// # inline
+/// Converts the inline code, emphasis and links of a markdown text to HTML. The links of the
+/// website, relative to its root, are made absolute.
+///
+/// ## Arguments
+/// * `text` - The markdown text
+///
+/// ## Returns
+/// * `String` - The HTML
+fn inline(text: &str) -> String {
+    let mut html = String::with_capacity(text.len());
+    let mut in_code = false;
+    let mut in_emphasis = false;
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let plain = rest.find(['`', '*', '[']).unwrap_or(rest.len());
+        if plain > 0 {
+            html.push_str(&escape(&rest[..plain]));
+            rest = &rest[plain..];
+            continue;
+        }
+
+        let link = rest.strip_prefix('[').and_then(|link| {
+            let (label, target) = link.split_once("](")?;
+            let (href, after) = target.split_once(')')?;
+
+            Some((label, href, after))
+        });
+
+        match (rest.as_bytes()[0], link) {
+            (b'`', _) => {
+                html.push_str(if in_code { "</code>" } else { "<code>" });
+                in_code = !in_code;
+            }
+            // A lone star is no emphasis
+            (b'*', _) if in_emphasis || rest[1..].contains('*') => {
+                html.push_str(if in_emphasis { "</em>" } else { "<em>" });
+                in_emphasis = !in_emphasis;
+            }
+            (b'[', Some((label, href, after))) if !in_code && !label.contains('[') => {
+                let href = match href.strip_prefix('/') {
+                    Some(path) => format!("{}{}", AOC_URL, path),
+                    None => href.to_string(),
+                };
+                html.push_str(&format!(
+                    "<a href=\"{}\">{}</a>",
+                    escape(&href),
+                    inline(label)
+                ));
+                rest = after;
+                continue;
+            }
+            (special, _) => html.push(char::from(special)),
+        }
+        rest = &rest[1..];
+    }
+
+    if in_emphasis {
+        html.push_str("</em>");
+    }
+    if in_code {
+        html.push_str("</code>");
+    }
+
+    html
+}
+
+///
+/// # highlight
+/// Highlights the source of a Rust solution, wrapping its keywords, types, macros, literals and
+/// comments in `<span>` elements whose classes are styled by the stylesheet of the site.
+///
+/// ## Arguments
+/// * `source` - The Rust source
+///
+/// ## Returns
+/// * `String` - The escaped and highlighted HTML
+pub fn highlight(source: &str) -> String {
+    let mut html = String::with_capacity(source.len() * 2);
+    let mut rest = source;
+
+    while let Some(c) = rest.chars().next() {
+        let word = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+
+        let (class, length) = if rest.starts_with("//") {
+            (Some("comment"), rest.find('\n').unwrap_or(rest.len()))
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            (
+                Some("comment"),
+                comment.find("*/").map_or(rest.len(), |end| end + 4),
+            )
+        } else if let Some(length) = string_length(rest) {
+            (Some("string"), length)
+        } else if c == '\'' {
+            match char_length(rest) {
+                Some(length) => (Some("string"), length),
+                // A lifetime or a label
+                None => (
+                    None,
+                    1 + rest[1..]
+                        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                        .unwrap_or(rest.len() - 1),
+                ),
+            }
+        } else if c.is_ascii_digit() {
+            let mut length = word;
+            // The fractional part of a float
+            while rest[length..].starts_with('.')
+                && rest[length + 1..].starts_with(|c: char| c.is_ascii_digit())
+            {
+                length += 1 + rest[length + 1..]
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len() - length - 1);
+            }
+            (Some("number"), length)
+        } else if c.is_alphabetic() || c == '_' {
+            let identifier = &rest[..word];
+
+            if KEYWORDS.contains(&identifier) {
+                (Some("keyword"), word)
+            } else if rest[word..].starts_with('!') && !rest[word..].starts_with("!=") {
+                (Some("macro"), word + 1)
+            } else if c.is_uppercase() {
+                (Some("type"), word)
+            } else {
+                (None, word)
+            }
+        } else {
+            (None, c.len_utf8())
+        };
+
+        match class {
+            Some(class) => html.push_str(&format!(
+                "<span class=\"{}\">{}</span>",
+                class,
+                escape(&rest[..length])
+            )),
+            None => html.push_str(&escape(&rest[..length])),
+        }
+        rest = &rest[length..];
+    }
+
+    html
+}
+
+///
+/// # string_length
+/// Returns the length of the string literal a source starts with: a string, a byte string or a
+/// raw string.
+///
+/// ## Arguments
+/// * `source` - The Rust source
+///
+/// ## Returns
+/// * `Option<usize>` - The length of the literal in bytes, `None` if there is none
+fn string_length(source: &str) -> Option<usize> {
+    let (prefix, literal) = ["br", "r", "b", ""].into_iter().find_map(|prefix| {
+        let literal = source.strip_prefix(prefix)?;
+        let raw = prefix.contains('r') && literal.starts_with('#');
+
+        (literal.starts_with('"') || raw).then_some((prefix, literal))
+    })?;
+    let hashes = literal.len() - literal.trim_start_matches('#').len();
+    let start = prefix.len() + hashes;
+    if !source[start..].starts_with('"') {
+        return None;
+    }
+
+    if prefix.contains('r') {
+        let end = format!("\"{}", "#".repeat(hashes));
+        return Some(
+            source[start + 1..]
+                .find(&end)
+                .map_or(source.len(), |end_index| start + 1 + end_index + end.len()),
+        );
+    }
+
+    let mut escaped = false;
+    for (index, c) in source[start + 1..].char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return Some(start + 1 + index + 1),
+            _ => {}
+        }
+    }
+
+    Some(source.len())
+}
+
+///
+/// # char_length
+/// Returns the length of the character literal a source starts with.
+///
+/// ## Arguments
+/// * `source` - The Rust source, starting with a `'`
+///
+/// ## Returns
+/// * `Option<usize>` - The length of the literal in bytes, `None` for a lifetime or a label
+fn char_length(source: &str) -> Option<usize> {
+    let content = &source[1..];
+
+    if content.starts_with('\\') {
+        // The escaped character may be a quote, e.g. '\''
+        return content.get(2..)?.find('\'').map(|end| 1 + 2 + end + 1);
+    }
+
+    let c = content.chars().next()?;
+    content[c.len_utf8()..]
+        .starts_with('\'')
+        .then(|| 1 + c.len_utf8() + 1)
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_markdown_to_html() {
+    let markdown = "## --- Day 1: Trebuchet?! ---\n\n\
+        Something is *wrong* with [global snow](/2023/about) & more,\n2 * 3 stars.\n\n\
+        ```\n1abc2\n<em>\n```\n\n\
+        - a `x < y`\n- b `*42*`\n\n\
+        Again.\n";
+
+    assert_eq!(
+        markdown_to_html(markdown),
+        "<h2>--- Day 1: Trebuchet?! ---</h2>\n\
+        <p>Something is <em>wrong</em> with \
+        <a href=\"https://adventofcode.com/2023/about\">global snow</a> &amp; more,\n\
+        2 * 3 stars.</p>\n\
+        <pre><code>1abc2\n&lt;em&gt;\n</code></pre>\n\
+        <ul>\n<li>a <code>x &lt; y</code></li>\n<li>b <code><em>42</em></code></li>\n</ul>\n\
+        <p>Again.</p>\n"
+    );
+}
+
+#[test]
+fn test_highlight() {
+    assert_eq!(
+        highlight("pub fn f<'a>(s: &'a str) -> Vec<u8> { // <a>\n"),
+        "<span class=\"keyword\">pub</span> <span class=\"keyword\">fn</span> f&lt;&apos;a&gt;(s: \
+        &amp;&apos;a str) -&gt; <span class=\"type\">Vec</span>&lt;u8&gt; { \
+        <span class=\"comment\">// &lt;a&gt;</span>\n"
+    );
+    assert_eq!(
+        highlight("println!(\"{}\\\"\", '\\'', 1.5e3, r#\"\"#);"),
+        "<span class=\"macro\">println!</span>(<span class=\"string\">&quot;{}\\&quot;&quot;\
+        </span>, <span class=\"string\">&apos;\\&apos;&apos;</span>, \
+        <span class=\"number\">1.5e3</span>, <span class=\"string\">r#&quot;&quot;#</span>);"
+    );
+    assert_eq!(
+        highlight("a != b'x' /* é */"),
+        "a != b<span class=\"string\">&apos;x&apos;</span> <span class=\"comment\">/* é */</span>"
+    );
+}
+
+/*
+ * End of file src/site.rs
+ */
@@ -0,0 +1,454 @@
+///
+/// # src/spec.rs
+/// Contains the day and year specifications accepted on the command line.
+///
+/// Besides plain numbers, days can be given relatively to the puzzle calendar:
+/// `today`, `latest`, `next`, `prev`, `+N` and `-N` (relative to the latest unlocked day).
+/// Years accept `current` (the latest event) and `last` (the one before).
+///
+/// Both can be combined into selections: comma separated lists of values and ranges, using the
+/// Rust range syntax (`1..5` excludes 5, `1..=5` includes it), e.g. `1..=5,12,latest`.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use chrono::{DateTime, Datelike, FixedOffset};
+
+use std::str::FromStr;
+
+use crate::calendar::{days_in_year, latest_event_year, unlocked_days, FIRST_YEAR};
+
+// Variables  =========================================================================== Variables
+/// A day, either absolute or relative to the puzzle calendar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DaySpec {
+    /// A fixed day
+    Day(u8),
+    /// The day of the month, during the event
+    Today,
+    /// The latest unlocked day
+    Latest,
+    /// The latest unlocked day shifted by the given amount (`next` is `+1`, `prev` is `-1`)
+    Offset(i8),
+}
+
+/// A year, either absolute or relative to the puzzle calendar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum YearSpec {
+    /// A fixed year
+    Year(u16),
+    /// The year of the latest event
+    Current,
+    /// The year before the latest event
+    Last,
+}
+
+/// One element of a selection: a single value or a range of values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SelectionItem<T> {
+    /// A single value
+    Single(T),
+    /// A range of values, `start..end` or `start..=end`
+    Range { start: T, end: T, inclusive: bool },
+}
+
+/// A comma separated list of values and ranges.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Selection<T>(Vec<SelectionItem<T>>);
+
+/// A selection of days, e.g. `1..=5` or `3,7,12`.
+pub type DaySelection = Selection<DaySpec>;
+
+/// A selection of years, e.g. `2015..2018`.
+pub type YearSelection = Selection<YearSpec>;
+
+// Functions  =========================================================================== Functions
+impl FromStr for DaySpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "today" => Ok(DaySpec::Today),
+            "latest" => Ok(DaySpec::Latest),
+            "next" => Ok(DaySpec::Offset(1)),
+            "prev" => Ok(DaySpec::Offset(-1)),
+            s if s.starts_with('+') || s.starts_with('-') => s
+                .parse::<i8>()
+                .map(DaySpec::Offset)
+                .map_err(|_| format!("'{}' is not a valid day offset.", s)),
+            s => {
+                let day = s.parse::<u8>().map_err(|_| {
+                    format!(
+                        "'{}' is not a valid day, expected a number or one of: today, latest, next, prev, +N, -N.",
+                        s
+                    )
+                })?;
+
+                if !(1..=25).contains(&day) {
+                    return Err("The day should be between 1 and 25.".to_string());
+                }
+
+                Ok(DaySpec::Day(day))
+            }
+        }
+    }
+}
+
+impl DaySpec {
+    ///
+    /// # resolve
+    /// Resolves the specification to an actual day of the given year.
+    ///
+    /// ## Arguments
+    /// * `year` - The year the day belongs to
+    /// * `now` - The current time, in the unlock timezone
+    ///
+    /// ## Returns
+    /// * `Result<u8, String>` - The resolved day
+    pub fn resolve(&self, year: u16, now: &DateTime<FixedOffset>) -> Result<u8, String> {
+        let last_day = days_in_year(year);
+
+        let day = match self {
+            DaySpec::Day(day) => i16::from(*day),
+            DaySpec::Today => {
+                if now.month() != 12 {
+                    return Err("There is no puzzle today, the event runs in December.".to_string());
+                }
+
+                now.day() as i16
+            }
+            DaySpec::Latest => {
+                let unlocked = unlocked_days(year, now);
+                if unlocked == 0 {
+                    return Err(format!("No puzzle of year {} is unlocked yet.", year));
+                }
+
+                i16::from(unlocked)
+            }
+            DaySpec::Offset(offset) => i16::from(unlocked_days(year, now)) + i16::from(*offset),
+        };
+
+        if day < 1 || day > i16::from(last_day) {
+            return Err(format!(
+                "The day should be between 1 and {} for year {}.",
+                last_day, year
+            ));
+        }
+
+        Ok(day as u8)
+    }
+}
+
+impl FromStr for YearSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "current" => Ok(YearSpec::Current),
+            "last" => Ok(YearSpec::Last),
+            s => s.parse::<u16>().map(YearSpec::Year).map_err(|_| {
+                format!(
+                    "'{}' is not a valid year, expected a number or one of: current, last.",
+                    s
+                )
+            }),
+        }
+    }
+}
+
+impl YearSpec {
+    ///
+    /// # resolve
+    /// Resolves the specification to an actual year.
+    ///
+    /// ## Arguments
+    /// * `now` - The current time, in the unlock timezone
+    ///
+    /// ## Returns
+    /// * `Result<u16, String>` - The resolved year
+    pub fn resolve(&self, now: &DateTime<FixedOffset>) -> Result<u16, String> {
+        let year = match self {
+            YearSpec::Year(year) => *year,
+            YearSpec::Current => latest_event_year(now),
+            YearSpec::Last => latest_event_year(now) - 1,
+        };
+
+        if year < FIRST_YEAR || year > now.year() as u16 {
+            return Err("The year should be between 2015 and the current year".to_string());
+        }
+
+        Ok(year)
+    }
+}
+
+impl<T: FromStr<Err = String>> FromStr for Selection<T> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let items = s
+            .split(',')
+            .map(|item| {
+                let item = item.trim();
+
+                if let Some((start, end)) = item.split_once("..=") {
+                    Ok(SelectionItem::Range {
+                        start: start.parse()?,
+                        end: end.parse()?,
+                        inclusive: true,
+                    })
+                } else if let Some((start, end)) = item.split_once("..") {
+                    Ok(SelectionItem::Range {
+                        start: start.parse()?,
+                        end: end.parse()?,
+                        inclusive: false,
+                    })
+                } else {
+                    Ok(SelectionItem::Single(item.parse()?))
+                }
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(Selection(items))
+    }
+}
+
+impl<T> Selection<T> {
+    ///
+    /// # specs
+    /// Returns every specification of the selection: the single values and the bounds of the
+    /// ranges.
+    ///
+    /// ## Returns
+    /// * `impl Iterator<Item = &T>` - The specifications, in order
+    pub fn specs(&self) -> impl Iterator<Item = &T> {
+        self.0.iter().flat_map(|item| match item {
+            SelectionItem::Single(value) => vec![value],
+            SelectionItem::Range { start, end, .. } => vec![start, end],
+        })
+    }
+
+    ///
+    /// # expand
+    /// Expands the selection into a sorted list of unique values.
+    ///
+    /// ## Arguments
+    /// * `resolve` - Resolves a single specification to its value
+    /// * `resolve_bound` - Resolves a bound of a range to its value, which may exceed `last`
+    /// * `last` - The last value, the ranges going further are cut there and the ones starting
+    ///   after it are an error, as they would select nothing
+    ///
+    /// ## Returns
+    /// * `Result<Vec<u16>, String>` - The selected values
+    fn expand<F, G>(&self, resolve: F, resolve_bound: G, last: u16) -> Result<Vec<u16>, String>
+    where
+        F: Fn(&T) -> Result<u16, String>,
+        G: Fn(&T) -> Result<u16, String>,
+    {
+        let mut values = Vec::new();
+
+        for item in &self.0 {
+            match item {
+                SelectionItem::Single(value) => values.push(resolve(value)?),
+                SelectionItem::Range {
+                    start,
+                    end,
+                    inclusive,
+                } => {
+                    let start = resolve_bound(start)?;
+                    let end = resolve_bound(end)?;
+                    let end = if *inclusive {
+                        end
+                    } else {
+                        end.saturating_sub(1)
+                    };
+
+                    if start > end {
+                        return Err(format!("The range {}..={} is empty.", start, end));
+                    }
+                    if start > last {
+                        return Err(format!(
+                            "The range {}..={} starts after the last day of the year, {}.",
+                            start, end, last
+                        ));
+                    }
+
+                    values.extend(start..=end.min(last));
+                }
+            }
+        }
+
+        values.sort_unstable();
+        values.dedup();
+
+        Ok(values)
+    }
+}
+
+impl DaySelection {
+    ///
+    /// # resolve
+    /// Resolves the selection to the actual days of the given year. Ranges are cut at the last
+    /// day of the year, so `1..=25` selects the 12 days of 2025, while a single day or a range
+    /// starting past it is an error.
+    ///
+    /// ## Arguments
+    /// * `year` - The year the days belong to
+    /// * `now` - The current time, in the unlock timezone
+    ///
+    /// ## Returns
+    /// * `Result<Vec<u8>, String>` - The selected days, sorted
+    pub fn resolve(&self, year: u16, now: &DateTime<FixedOffset>) -> Result<Vec<u8>, String> {
+        let days = self.expand(
+            |day| day.resolve(year, now).map(u16::from),
+            |day| match day {
+                DaySpec::Day(day) => Ok(u16::from(*day)),
+                day => day.resolve(year, now).map(u16::from),
+            },
+            u16::from(days_in_year(year)),
+        )?;
+
+        Ok(days.into_iter().map(|day| day as u8).collect())
+    }
+}
+
+impl YearSelection {
+    ///
+    /// # resolve
+    /// Resolves the selection to actual years.
+    ///
+    /// ## Arguments
+    /// * `now` - The current time, in the unlock timezone
+    ///
+    /// ## Returns
+    /// * `Result<Vec<u16>, String>` - The selected years, sorted
+    pub fn resolve(&self, now: &DateTime<FixedOffset>) -> Result<Vec<u16>, String> {
+        self.expand(|year| year.resolve(now), |year| year.resolve(now), u16::MAX)
+    }
+}
+
+// Tests ==================================================================================== Tests
+#[cfg(test)]
+use crate::calendar::at;
+
+#[test]
+fn test_parse_day_spec() {
+    assert_eq!("7".parse::<DaySpec>(), Ok(DaySpec::Day(7)));
+    assert_eq!("today".parse::<DaySpec>(), Ok(DaySpec::Today));
+    assert_eq!("latest".parse::<DaySpec>(), Ok(DaySpec::Latest));
+    assert_eq!("next".parse::<DaySpec>(), Ok(DaySpec::Offset(1)));
+    assert_eq!("prev".parse::<DaySpec>(), Ok(DaySpec::Offset(-1)));
+    assert_eq!("+2".parse::<DaySpec>(), Ok(DaySpec::Offset(2)));
+    assert_eq!("-3".parse::<DaySpec>(), Ok(DaySpec::Offset(-3)));
+
+    assert!("foo".parse::<DaySpec>().is_err());
+    assert!("+foo".parse::<DaySpec>().is_err());
+    assert!("0".parse::<DaySpec>().is_err());
+    assert!("".parse::<DaySpec>().is_err());
+}
+
+#[test]
+fn test_resolve_day_spec() {
+    let during = at(2023, 12, 10, 8);
+
+    assert_eq!(DaySpec::Day(3).resolve(2023, &during), Ok(3));
+    assert_eq!(DaySpec::Today.resolve(2023, &during), Ok(10));
+    assert_eq!(DaySpec::Latest.resolve(2023, &during), Ok(10));
+    assert_eq!(DaySpec::Offset(1).resolve(2023, &during), Ok(11));
+    assert_eq!(DaySpec::Offset(-1).resolve(2023, &during), Ok(9));
+    assert_eq!(DaySpec::Latest.resolve(2016, &during), Ok(25));
+
+    assert!(DaySpec::Offset(1).resolve(2016, &during).is_err());
+    assert!(DaySpec::Day(20).resolve(2025, &during).is_err());
+
+    let before = at(2023, 11, 20, 8);
+    assert!(DaySpec::Today.resolve(2023, &before).is_err());
+    assert!(DaySpec::Latest.resolve(2023, &before).is_err());
+    assert_eq!(DaySpec::Offset(1).resolve(2023, &before), Ok(1));
+}
+
+#[test]
+fn test_year_spec() {
+    let now = at(2023, 11, 20, 8);
+
+    assert_eq!("2016".parse::<YearSpec>(), Ok(YearSpec::Year(2016)));
+    assert_eq!("current".parse::<YearSpec>(), Ok(YearSpec::Current));
+    assert_eq!("last".parse::<YearSpec>(), Ok(YearSpec::Last));
+    assert!("soon".parse::<YearSpec>().is_err());
+
+    assert_eq!(YearSpec::Current.resolve(&now), Ok(2022));
+    assert_eq!(YearSpec::Last.resolve(&now), Ok(2021));
+    assert_eq!(YearSpec::Current.resolve(&at(2023, 12, 1, 0)), Ok(2023));
+    assert!(YearSpec::Year(2014).resolve(&now).is_err());
+    assert!(YearSpec::Year(2024).resolve(&now).is_err());
+}
+
+#[test]
+fn test_day_selection() {
+    let now = at(2023, 12, 10, 8);
+
+    let range = "1..=5".parse::<DaySelection>().unwrap();
+    assert_eq!(range.resolve(2023, &now), Ok(vec![1, 2, 3, 4, 5]));
+
+    let exclusive = "1..5".parse::<DaySelection>().unwrap();
+    assert_eq!(exclusive.resolve(2023, &now), Ok(vec![1, 2, 3, 4]));
+
+    let list = "12, 3,7,3".parse::<DaySelection>().unwrap();
+    assert_eq!(list.resolve(2016, &now), Ok(vec![3, 7, 12]));
+
+    let relative = "prev..=latest,1".parse::<DaySelection>().unwrap();
+    assert_eq!(relative.resolve(2023, &now), Ok(vec![1, 9, 10]));
+
+    assert!("5..=1"
+        .parse::<DaySelection>()
+        .unwrap()
+        .resolve(2023, &now)
+        .is_err());
+    assert!("1..=30".parse::<DaySelection>().is_err());
+
+    let event = "1..=25".parse::<DaySelection>().unwrap();
+    assert_eq!(event.resolve(2016, &now), Ok((1..=25).collect()));
+    assert_eq!(event.resolve(2025, &now), Ok((1..=12).collect()));
+    assert_eq!(
+        "20..=25"
+            .parse::<DaySelection>()
+            .unwrap()
+            .resolve(2025, &now),
+        Err("The range 20..=25 starts after the last day of the year, 12.".to_string())
+    );
+    assert!("20"
+        .parse::<DaySelection>()
+        .unwrap()
+        .resolve(2025, &now)
+        .is_err());
+    assert_eq!(
+        "3,1..=2"
+            .parse::<DaySelection>()
+            .unwrap()
+            .specs()
+            .collect::<Vec<_>>(),
+        [&DaySpec::Day(3), &DaySpec::Day(1), &DaySpec::Day(2)]
+    );
+    assert!("1,,2".parse::<DaySelection>().is_err());
+}
+
+#[test]
+fn test_year_selection() {
+    let now = at(2023, 12, 10, 8);
+
+    let range = "2015..2018".parse::<YearSelection>().unwrap();
+    assert_eq!(range.resolve(&now), Ok(vec![2015, 2016, 2017]));
+
+    let list = "last,current".parse::<YearSelection>().unwrap();
+    assert_eq!(list.resolve(&now), Ok(vec![2022, 2023]));
+
+    assert!("2015..=2030"
+        .parse::<YearSelection>()
+        .unwrap()
+        .resolve(&now)
+        .is_err());
+}
+
+/*
+ * End of file src/spec.rs
+ */
@@ -0,0 +1,196 @@
+///
+/// # src/submit.rs
+/// Contains the submission of answers to the Advent of Code website.
+///
+/// The website answers a submission with a page whose `<article>` tells whether the answer is
+/// right, wrong (sometimes too high or too low), sent too soon after a wrong one, or for a part
+/// that is already solved.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use crate::http::Client;
+use crate::puzzle::unescape;
+
+// Variables  =========================================================================== Variables
+/// The verdict of the website on a submitted answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// The answer is accepted
+    Right,
+    /// The answer is wrong, with the hint of the website, e.g. `your answer is too high`
+    Wrong(Option<String>),
+    /// A wrong answer has been submitted too recently, with the message telling how long to wait
+    TooRecently(String),
+    /// The part is already solved, or the first part is not
+    WrongLevel,
+    /// Any other message of the website
+    Unknown(String),
+}
+
+// Functions  =========================================================================== Functions
+///
+/// # submit
+/// Submits the answer of a part.
+///
+/// ## Arguments
+/// * `client` - The client authenticated with the session of the profile
+/// * `year` - The year of the Advent of Code challenge
+/// * `day` - The day of the Advent of Code challenge
+/// * `part` - The part of the puzzle, 1 or 2
+/// * `answer` - The answer
+///
+/// ## Returns
+/// * `Result<Verdict, String>` - The verdict of the website
+pub fn submit(
+    client: &Client,
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: &str,
+) -> Result<Verdict, String> {
+    let page = client.post(
+        &format!("{}/day/{}/answer", year, day),
+        &format!("level={}&answer={}", part, encode(answer)),
+    )?;
+
+    Ok(verdict(&page))
+}
+
+///
+/// # verdict
+/// Reads the verdict from the page answering a submission.
+///
+/// ## Arguments
+/// * `page` - The HTML of the page
+///
+/// ## Returns
+/// * `Verdict` - The verdict
+fn verdict(page: &str) -> Verdict {
+    let article = page
+        .split_once("<article>")
+        .map_or(page, |(_, article)| article);
+    let article = article
+        .split_once("</article>")
+        .map_or(article, |(article, _)| article);
+    let message = text(article);
+
+    if message.contains("That's the right answer") {
+        Verdict::Right
+    } else if message.contains("That's not the right answer") {
+        let hint = ["your answer is too high", "your answer is too low"]
+            .into_iter()
+            .find(|hint| message.contains(hint))
+            .map(str::to_string);
+
+        Verdict::Wrong(hint)
+    } else if message.contains("You gave an answer too recently") {
+        Verdict::TooRecently(message)
+    } else if message.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown(message)
+    }
+}
+
+///
+/// # text
+/// Returns the text of an HTML fragment, its whitespace collapsed.
+///
+/// ## Arguments
+/// * `html` - The HTML fragment
+///
+/// ## Returns
+/// * `String` - The text
+fn text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    unescape(&text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+///
+/// # encode
+/// Encodes a value for a URL-encoded form.
+///
+/// ## Arguments
+/// * `value` - The value
+///
+/// ## Returns
+/// * `String` - The encoded value
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                char::from(byte).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_verdict() {
+    let page = |message: &str| {
+        format!(
+            "<html><main><article><p>{}</p></article></main></html>",
+            message
+        )
+    };
+
+    assert_eq!(
+        verdict(&page(
+            "That's the right answer!  You are <em>one gold star</em> closer."
+        )),
+        Verdict::Right
+    );
+    assert_eq!(
+        verdict(&page(
+            "That's not the right answer;  your answer is too low.  Please wait."
+        )),
+        Verdict::Wrong(Some("your answer is too low".to_string()))
+    );
+    assert_eq!(
+        verdict(&page("That's not the right answer.  Please wait.")),
+        Verdict::Wrong(None)
+    );
+    assert_eq!(
+        verdict(&page(
+            "You gave an answer too recently; you have 42s left to wait."
+        )),
+        Verdict::TooRecently(
+            "You gave an answer too recently; you have 42s left to wait.".to_string()
+        )
+    );
+    assert_eq!(
+        verdict(&page(
+            "You don't seem to be solving the right level.  Did you already complete it?"
+        )),
+        Verdict::WrongLevel
+    );
+}
+
+#[test]
+fn test_encode() {
+    assert_eq!(encode("1234"), "1234");
+    assert_eq!(encode("6,4 5&x"), "6%2C4%205%26x");
+}
+
+/*
+ * End of file src/submit.rs
+ */
@@ -0,0 +1,294 @@
+///
+/// # src/sync.rs
+/// Contains the synchronisation of the progress of the logged in user with the Advent of Code
+/// website.
+///
+/// The stars of every year are read from the `/events` page, the stars of every day from the
+/// calendar page of the year, and the accepted answers from the "Your puzzle answer was" lines of
+/// the puzzle pages. The missing answers are backfilled in the answers ledger, so that solved
+/// puzzles can be verified without submitting anything again.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use std::{collections::BTreeMap, path::Path, time::Duration};
+
+use crate::calendar::days_in_year;
+use crate::config::Config;
+use crate::http::{username, Client, SESSION_EXPIRED};
+use crate::ledger::Ledger;
+use crate::profile::Profile;
+use crate::puzzle::unescape;
+
+// Variables  =========================================================================== Variables
+/// The element of a year in the `/events` page.
+const EVENT_START: &str = "<div class=\"eventlist-event\">";
+
+/// The element of the star count of a year.
+const STAR_COUNT_START: &str = "<span class=\"star-count\">";
+
+/// The text preceding an accepted answer in a puzzle page.
+const ANSWER_START: &str = "Your puzzle answer was <code>";
+
+/// What the synchronisation of a year found.
+pub struct YearSync {
+    /// The year
+    pub year: u16,
+    /// The number of stars of the year
+    pub stars: u32,
+    /// The answers added to the ledger, as `(day, part)`
+    pub backfilled: Vec<(u8, u8)>,
+    /// The answers of the ledger differing from the website, as `(day, part, ledger, website)`
+    pub conflicts: Vec<(u8, u8, String, String)>,
+}
+
+// Functions  =========================================================================== Functions
+///
+/// # events
+/// Fetches the number of stars of every year.
+///
+/// ## Arguments
+/// * `client` - The client authenticated with the session of the profile
+///
+/// ## Returns
+/// * `Result<Vec<(u16, u32)>, String>` - The stars of every year, the latest first, an error if
+///   the page was served logged out, as it would show no star at all
+pub fn events(client: &Client) -> Result<Vec<(u16, u32)>, String> {
+    let page = client.get_cached("events", Duration::ZERO)?;
+    if username(&page).is_none() {
+        return Err(SESSION_EXPIRED.to_string());
+    }
+
+    Ok(parse_events(&page))
+}
+
+///
+/// # sync_year
+/// Synchronises a year: reads the stars of every day and backfills the answers of the solved
+/// parts missing from the ledger. The answers already in the ledger are never replaced.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `config` - The configuration of the project
+/// * `client` - The client authenticated with the session of the profile
+/// * `profile` - The profile whose answers are backfilled
+/// * `year` - The year to synchronise
+/// * `stars` - The number of stars of the year, from the `/events` page
+///
+/// ## Returns
+/// * `Result<YearSync, String>` - What the synchronisation found
+pub fn sync_year(
+    caller: &Path,
+    config: &Config,
+    client: &Client,
+    profile: &Profile,
+    year: u16,
+    stars: u32,
+) -> Result<YearSync, String> {
+    let mut ledger = Ledger::load(caller, &config.layout, year)?;
+    let mut sync = YearSync {
+        year,
+        stars,
+        backfilled: Vec::new(),
+        conflicts: Vec::new(),
+    };
+
+    let calendar = client.get_cached(&year.to_string(), Duration::ZERO)?;
+    for (day, stars) in parse_calendar(&calendar) {
+        let known = (1..=2)
+            .filter(|part| ledger.answer(profile, day, *part).is_some())
+            .count();
+        if answered_parts(year, day, stars) <= known {
+            continue;
+        }
+
+        let page = client.get_cached(&format!("{}/day/{}", year, day), Duration::ZERO)?;
+        for (part, answer) in (1..=2).zip(parse_answers(&page)) {
+            match ledger.answer(profile, day, part) {
+                None => {
+                    ledger.set_answer(profile, day, part, &answer)?;
+                    sync.backfilled.push((day, part));
+                }
+                Some(known) if known != answer => {
+                    sync.conflicts.push((day, part, known, answer));
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    if !sync.backfilled.is_empty() {
+        ledger.save()?;
+    }
+
+    Ok(sync)
+}
+
+///
+/// # answered_parts
+/// Returns the number of answers the page of a day shows for its stars. The second star of the
+/// last day is given for the other stars of the year, without any answer.
+///
+/// ## Arguments
+/// * `year` - The year of the day
+/// * `day` - The day
+/// * `stars` - The stars of the day
+///
+/// ## Returns
+/// * `usize` - The number of answers of the page
+fn answered_parts(year: u16, day: u8, stars: u8) -> usize {
+    if day == days_in_year(year) {
+        usize::from(stars.min(1))
+    } else {
+        usize::from(stars)
+    }
+}
+
+///
+/// # parse_events
+/// Reads the number of stars of every year from the `/events` page.
+///
+/// ## Arguments
+/// * `page` - The HTML of the page
+///
+/// ## Returns
+/// * `Vec<(u16, u32)>` - The stars of every year, 0 for the years without any
+fn parse_events(page: &str) -> Vec<(u16, u32)> {
+    page.split(EVENT_START)
+        .skip(1)
+        .filter_map(|event| {
+            let event = event.split("</div>").next().unwrap_or_default();
+            let year = event
+                .split_once("href=\"/")?
+                .1
+                .split(['"', '/'])
+                .next()?
+                .parse()
+                .ok()?;
+            let stars = event
+                .split_once(STAR_COUNT_START)
+                .and_then(|(_, count)| count.split('*').next())
+                .and_then(|count| count.trim().parse().ok())
+                .unwrap_or(0);
+
+            Some((year, stars))
+        })
+        .collect()
+}
+
+///
+/// # parse_calendar
+/// Reads the number of stars of every day from the calendar page of a year, where each day is
+/// a link labelled `Day 1`, `Day 1, one star` or `Day 1, two stars`.
+///
+/// ## Arguments
+/// * `page` - The HTML of the page
+///
+/// ## Returns
+/// * `BTreeMap<u8, u8>` - The stars of every unlocked day
+fn parse_calendar(page: &str) -> BTreeMap<u8, u8> {
+    page.split("aria-label=\"Day ")
+        .skip(1)
+        .filter_map(|label| {
+            let label = label.split('"').next()?;
+            let (day, stars) = label.split_once(',').unwrap_or((label, ""));
+            let stars = match stars.trim() {
+                "two stars" => 2,
+                "one star" => 1,
+                _ => 0,
+            };
+
+            Some((day.trim().parse().ok()?, stars))
+        })
+        .collect()
+}
+
+///
+/// # parse_answers
+/// Reads the accepted answers from a puzzle page, in the order of the parts.
+///
+/// ## Arguments
+/// * `page` - The HTML of the page
+///
+/// ## Returns
+/// * `Vec<String>` - The answers of the solved parts
+fn parse_answers(page: &str) -> Vec<String> {
+    page.split(ANSWER_START)
+        .skip(1)
+        .filter_map(|answer| answer.split_once("</code>"))
+        .map(|(answer, _)| unescape(answer.trim()))
+        .collect()
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_parse_events() {
+    let page = "<main><article><p>Here are the events.</p>\
+        <div class=\"eventlist-event\"><a href=\"/2023\">[2023]</a> \
+        <span class=\"star-count\">34*</span></div>\
+        <div class=\"eventlist-event\"><a href=\"/2022\">[2022]</a></div>\
+        <div class=\"eventlist-event\"><a href=\"/2015\">[2015]</a>  \
+        <span class=\"star-count\"> 50*</span></div></article></main>";
+
+    assert_eq!(parse_events(page), [(2023, 34), (2022, 0), (2015, 50)]);
+}
+
+#[test]
+fn test_parse_calendar() {
+    let page = "<pre class=\"calendar\">\
+        <a aria-label=\"Day 1, two stars\" href=\"/2023/day/1\" class=\"calendar-day1\">1</a>\
+        <a aria-label=\"Day 2, one star\" href=\"/2023/day/2\" class=\"calendar-day2\">2</a>\
+        <a aria-label=\"Day 3\" href=\"/2023/day/3\" class=\"calendar-day3\">3</a></pre>";
+
+    assert_eq!(
+        parse_calendar(page),
+        BTreeMap::from([(1, 2), (2, 1), (3, 0)])
+    );
+}
+
+#[test]
+fn test_parse_answers() {
+    let page = "<article class=\"day-desc\">...</article>\
+        <p>Your puzzle answer was <code>142</code>.</p>\
+        <article class=\"day-desc\">...</article>\
+        <p>Your puzzle answer was <code>a&lt;b</code>.</p>";
+
+    assert_eq!(parse_answers(page), ["142", "a<b"]);
+    assert!(parse_answers("<p>Please log in.</p>").is_empty());
+}
+
+#[test]
+fn test_answered_parts() {
+    assert_eq!(answered_parts(2023, 24, 2), 2);
+    assert_eq!(answered_parts(2023, 25, 2), 1);
+    assert_eq!(answered_parts(2023, 25, 0), 0);
+    assert_eq!(answered_parts(2025, 12, 2), 1);
+}
+
+#[test]
+fn test_events() {
+    let fixtures = crate::mock::fixtures("events");
+    std::fs::create_dir_all(fixtures.join("events")).unwrap();
+    std::fs::write(
+        fixtures.join("events/index.html"),
+        "<!DOCTYPE html>\n<html><header><div class=\"user\">{{user}}</div></header><main>\
+        <div class=\"eventlist-event\"><a href=\"/2023\">[2023]</a> \
+        <span class=\"star-count\">34*</span></div></main></html>",
+    )
+    .unwrap();
+    let url = crate::mock::start(fixtures);
+
+    assert_eq!(
+        events(&crate::http::test_client(&url, "abc123")),
+        Ok(vec![(2023, 34)])
+    );
+    assert_eq!(
+        events(&crate::http::test_client(&url, "expired")),
+        Err(SESSION_EXPIRED.to_string())
+    );
+}
+
+/*
+ * End of file src/sync.rs
+ */
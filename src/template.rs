@@ -0,0 +1,352 @@
+///
+/// # src/template.rs
+/// Contains the templates used to generate the files of a day.
+///
+/// Every template has a built-in default, which can be overridden by a file in the
+/// `.aoc/templates/` folder of the project:
+/// ```text
+/// .aoc/templates/
+/// ├── day.rs.tmpl      // the solution file
+/// ├── puzzle.md.tmpl   // the puzzle description, until it is downloaded
+/// └── test.rs.tmpl     // the test module, inserted in place of `{{tests}}`
+/// ```
+///
+/// The templates can use the following placeholders:
+/// `{{day}}`, `{{day_padded}}`, `{{year}}`, `{{title}}`, `{{input_path}}`, `{{author}}` and,
+/// in the day template only, `{{tests}}`.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use clap::ValueEnum;
+
+use std::{
+    fs::{copy, create_dir_all, read_to_string, write},
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::file_utils::backup_path;
+
+// Variables  =========================================================================== Variables
+/// The folder containing the user templates, relative to the project root.
+pub const TEMPLATES_FOLDER: &str = ".aoc/templates";
+
+/// The templates used to generate the files of a day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum TemplateName {
+    /// The solution file
+    Day,
+    /// The puzzle description
+    Puzzle,
+    /// The test module of the solution file
+    Test,
+}
+
+/// The values the placeholders of a template are replaced with.
+#[derive(Clone)]
+pub struct TemplateVariables {
+    pub day: u8,
+    pub year: u16,
+    pub title: String,
+    pub input_path: String,
+    pub author: String,
+    /// The rendered test module, set by `render_day`
+    pub tests: String,
+}
+
+// Functions  =========================================================================== Functions
+impl TemplateName {
+    ///
+    /// # all
+    /// Returns every template.
+    ///
+    /// ## Returns
+    /// * `[TemplateName; 3]` - The templates
+    pub fn all() -> [TemplateName; 3] {
+        [TemplateName::Day, TemplateName::Puzzle, TemplateName::Test]
+    }
+
+    ///
+    /// # file_name
+    /// Returns the name of the file of the template.
+    ///
+    /// ## Returns
+    /// * `&str` - The file name
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            TemplateName::Day => "day.rs.tmpl",
+            TemplateName::Puzzle => "puzzle.md.tmpl",
+            TemplateName::Test => "test.rs.tmpl",
+        }
+    }
+
+    ///
+    /// # builtin
+    /// Returns the built-in content of the template.
+    ///
+    /// ## Returns
+    /// * `&str` - The built-in template
+    pub fn builtin(&self) -> &'static str {
+        match self {
+            TemplateName::Day => include_str!("templates/day.rs.tmpl"),
+            TemplateName::Puzzle => include_str!("templates/puzzle.md.tmpl"),
+            TemplateName::Test => include_str!("templates/test.rs.tmpl"),
+        }
+    }
+
+    ///
+    /// # path
+    /// Returns the path of the user template in the given project.
+    ///
+    /// ## Arguments
+    /// * `caller` - The folder from which the program was called
+    ///
+    /// ## Returns
+    /// * `PathBuf` - The path of the user template
+    pub fn path(&self, caller: &Path) -> PathBuf {
+        caller.join(TEMPLATES_FOLDER).join(self.file_name())
+    }
+
+    ///
+    /// # load
+    /// Returns the template used in the given project: the user one if it exists,
+    /// the built-in one otherwise.
+    ///
+    /// ## Arguments
+    /// * `caller` - The folder from which the program was called
+    ///
+    /// ## Returns
+    /// * `Result<String, String>` - The template, an error if the user one cannot be read
+    pub fn load(&self, caller: &Path) -> Result<String, String> {
+        let path = self.path(caller);
+
+        match read_to_string(&path) {
+            Ok(template) => Ok(template),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(self.builtin().to_string()),
+            Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    ///
+    /// # reset
+    /// Restores the built-in template in the `.aoc/templates` folder of the given project.
+    /// A customized template is saved next to it first, with a `.bak` extension.
+    ///
+    /// ## Arguments
+    /// * `caller` - The folder from which the program was called
+    ///
+    /// ## Returns
+    /// * `std::io::Result<(PathBuf, Option<PathBuf>)>` - The path of the restored template and
+    ///   the backup of the customized one, if any
+    pub fn reset(&self, caller: &Path) -> std::io::Result<(PathBuf, Option<PathBuf>)> {
+        let path = self.path(caller);
+
+        let backup = match read_to_string(&path) {
+            Ok(template) if template != self.builtin() => {
+                let backup = backup_path(&path);
+                copy(&path, &backup)?;
+
+                Some(backup)
+            }
+            Ok(_) => None,
+            Err(e) if e.kind() == ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
+
+        create_dir_all(caller.join(TEMPLATES_FOLDER))?;
+        write(&path, self.builtin())?;
+
+        Ok((path, backup))
+    }
+}
+
+///
+/// # render
+/// Replaces the placeholders of the given template.
+/// Placeholders may contain spaces around the name (`{{ day }}`), unknown ones are left as is.
+///
+/// ## Arguments
+/// * `template` - The template to render
+/// * `variables` - The values of the placeholders
+///
+/// ## Returns
+/// * `String` - The rendered template
+pub fn render(template: &str, variables: &TemplateVariables) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+
+        let Some(end) = rest[start..].find("}}") else {
+            rest = &rest[start..];
+            break;
+        };
+
+        let placeholder = &rest[start..start + end + 2];
+        let value = match placeholder[2..placeholder.len() - 2].trim() {
+            "day" => variables.day.to_string(),
+            "day_padded" => format!("{:02}", variables.day),
+            "year" => variables.year.to_string(),
+            "title" => variables.title.clone(),
+            "input_path" => variables.input_path.clone(),
+            "author" => variables.author.clone(),
+            "tests" => variables.tests.clone(),
+            _ => placeholder.to_string(),
+        };
+
+        rendered.push_str(&value);
+        rest = &rest[start + end + 2..];
+    }
+
+    rendered.push_str(rest);
+    rendered
+}
+
+///
+/// # render_day
+/// Renders the solution file of a day, with its test module.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `variables` - The values of the placeholders
+///
+/// ## Returns
+/// * `Result<String, String>` - The content of the solution file, an error if a template cannot
+///   be read
+pub fn render_day(caller: &Path, variables: &TemplateVariables) -> Result<String, String> {
+    let variables = TemplateVariables {
+        tests: render(&TemplateName::Test.load(caller)?, variables),
+        ..variables.clone()
+    };
+
+    Ok(render(&TemplateName::Day.load(caller)?, &variables))
+}
+
+///
+/// # puzzle_title
+/// Returns the title of a puzzle, read from the first heading of its markdown file.
+/// Falls back to `Day N` if the puzzle has not been downloaded yet.
+///
+/// ## Arguments
+/// * `puzzle_file` - The path of the puzzle markdown file
+/// * `day` - The day of the puzzle
+///
+/// ## Returns
+/// * `String` - The title of the puzzle
+pub fn puzzle_title(puzzle_file: &Path, day: u8) -> String {
+    read_to_string(puzzle_file)
+        .ok()
+        .and_then(|content| {
+            content
+                .lines()
+                .map(|line| line.trim_start_matches('#').trim().trim_matches('-').trim())
+                .find(|line| !line.is_empty())
+                .map(|line| match line.split_once(": ") {
+                    Some((prefix, title)) if prefix.starts_with("Day ") => title.to_string(),
+                    _ => line.to_string(),
+                })
+        })
+        .unwrap_or_else(|| format!("Day {}", day))
+}
+
+///
+/// # author
+/// Returns the name of the author of the solutions, taken from the git configuration.
+///
+/// ## Returns
+/// * `String` - The name of the author, empty if unknown
+pub fn author() -> String {
+    Command::new("git")
+        .args(["config", "user.name"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default()
+}
+
+// Tests ==================================================================================== Tests
+#[cfg(test)]
+fn variables() -> TemplateVariables {
+    TemplateVariables {
+        day: 7,
+        year: 2016,
+        title: "Internet Protocol Version 7".to_string(),
+        input_path: "../../../data/year_2016/inputs/day_07.txt".to_string(),
+        author: "Tom".to_string(),
+        tests: String::new(),
+    }
+}
+
+#[test]
+fn test_render() {
+    let template =
+        "{{day}} {{ day_padded }} {{year}} {{title}} {{input_path}} {{author}} {{other}} {{";
+
+    assert_eq!(
+        render(template, &variables()),
+        "7 07 2016 Internet Protocol Version 7 ../../../data/year_2016/inputs/day_07.txt Tom {{other}} {{"
+    );
+}
+
+#[test]
+fn test_builtin_day_template() {
+    let rendered = render(TemplateName::Day.builtin(), &variables());
+
+    assert!(rendered.contains("/// # day_07.rs"));
+    assert!(rendered.contains("include_str!(\"../../../data/year_2016/inputs/day_07.txt\")"));
+    assert!(rendered.contains("pub fn response_part_1()"));
+    assert!(rendered.contains("pub fn response_part_2()"));
+}
+
+#[test]
+fn test_render_day() {
+    let caller = crate::file_utils::temp_folder("render-day");
+    create_dir_all(caller.join(TEMPLATES_FOLDER)).unwrap();
+    write(
+        TemplateName::Day.path(&caller),
+        "pub fn response_part_1() {}\n{{ tests }}",
+    )
+    .unwrap();
+
+    let rendered = render_day(&caller, &variables()).unwrap();
+    assert!(rendered.starts_with("pub fn response_part_1() {}\n#[cfg(test)]\nmod tests {"));
+    assert!(!rendered.contains("{{"));
+
+    let puzzle = render(TemplateName::Puzzle.builtin(), &variables());
+    assert!(puzzle.starts_with("# Day 7: Internet Protocol Version 7\n"));
+    assert!(puzzle.contains("https://adventofcode.com/2016/day/7"));
+}
+
+#[test]
+fn test_load_and_reset() {
+    let caller = crate::file_utils::temp_folder("templates");
+    let path = TemplateName::Day.path(&caller);
+
+    assert_eq!(
+        TemplateName::Day.load(&caller).unwrap(),
+        TemplateName::Day.builtin()
+    );
+
+    // A customized template is saved before being restored
+    create_dir_all(path.parent().unwrap()).unwrap();
+    write(&path, "custom").unwrap();
+    let (restored, backup) = TemplateName::Day.reset(&caller).unwrap();
+    assert_eq!(restored, path);
+    assert_eq!(read_to_string(backup.unwrap()).unwrap(), "custom");
+    assert_eq!(read_to_string(&path).unwrap(), TemplateName::Day.builtin());
+    assert_eq!(TemplateName::Day.reset(&caller).unwrap().1, None);
+
+    // Only a missing template falls back to the built-in one
+    let unreadable = TemplateName::Test.path(&caller);
+    create_dir_all(&unreadable).unwrap();
+    assert!(TemplateName::Test.load(&caller).is_err());
+}
+
+/*
+ * End of file src/template.rs
+ */
@@ -0,0 +1,240 @@
+///
+/// # src/timing.rs
+/// Contains the personal solve times of a year, stored in a `timings.toml` file next to the
+/// answers file (`data/year_{year}/timings.toml` by default), one table per profile and day:
+/// ```toml
+/// [default.day_01]
+/// started = 2023-12-01T00:00:04-05:00
+/// part_1 = 2023-12-01T00:12:31-05:00
+/// part_2 = 2023-12-01T00:20:05-05:00
+/// ```
+///
+/// A day is started by `aoc create`, and a part is finished when `aoc submit` gets its answer
+/// accepted. Only the first start and finish are kept.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use chrono::{DateTime, FixedOffset, SecondsFormat};
+use toml_edit::{value, Datetime, DocumentMut};
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::layout::Layout;
+use crate::ledger::{day_key, day_table, load_next_to_answers, save};
+use crate::profile::Profile;
+
+// Variables  =========================================================================== Variables
+/// The name of the timings file, in the folder of the answers file.
+const TIMINGS_FILE: &str = "timings.toml";
+
+/// The solve times of a year.
+pub struct Timings {
+    /// The content of the timings file
+    doc: DocumentMut,
+    /// The path of the timings file
+    path: PathBuf,
+}
+
+// Functions  =========================================================================== Functions
+impl Timings {
+    ///
+    /// # load
+    /// Loads the timings of a year, empty ones if the timings file does not exist yet.
+    ///
+    /// ## Arguments
+    /// * `caller` - The folder from which the program was called
+    /// * `layout` - The layout of the project
+    /// * `year` - The year of the Advent of Code challenge
+    ///
+    /// ## Returns
+    /// * `Result<Timings, String>` - The timings
+    pub fn load(caller: &Path, layout: &Layout, year: u16) -> Result<Timings, String> {
+        let (doc, path) = load_next_to_answers(caller, layout, year, TIMINGS_FILE)?;
+
+        Ok(Timings { doc, path })
+    }
+
+    ///
+    /// # start
+    /// Records the start of a day, unless it has already been started.
+    ///
+    /// ## Arguments
+    /// * `profile` - The profile solving the day
+    /// * `day` - The day of the Advent of Code challenge
+    /// * `at` - The time of the start
+    ///
+    /// ## Returns
+    /// * `Result<bool, String>` - Whether the start has been recorded, an error if the timings
+    ///   file holds something else for the day
+    pub fn start(
+        &mut self,
+        profile: &Profile,
+        day: u8,
+        at: &DateTime<FixedOffset>,
+    ) -> Result<bool, String> {
+        self.record(profile, day, "started", at)
+    }
+
+    ///
+    /// # finish
+    /// Records the finish of a part, unless it has already been finished.
+    ///
+    /// ## Arguments
+    /// * `profile` - The profile solving the day
+    /// * `day` - The day of the Advent of Code challenge
+    /// * `part` - The part of the puzzle, 1 or 2
+    /// * `at` - The time the answer was accepted
+    ///
+    /// ## Returns
+    /// * `Result<bool, String>` - Whether the finish has been recorded, an error if the timings
+    ///   file holds something else for the day
+    pub fn finish(
+        &mut self,
+        profile: &Profile,
+        day: u8,
+        part: u8,
+        at: &DateTime<FixedOffset>,
+    ) -> Result<bool, String> {
+        self.record(profile, day, &format!("part_{}", part), at)
+    }
+
+    ///
+    /// # elapsed
+    /// Returns the time between the start of a day and the finish of a part.
+    ///
+    /// ## Arguments
+    /// * `profile` - The profile solving the day
+    /// * `day` - The day of the Advent of Code challenge
+    /// * `part` - The part of the puzzle, 1 or 2
+    ///
+    /// ## Returns
+    /// * `Option<Duration>` - The solve time, `None` if the day is not started or the part not
+    ///   finished
+    pub fn elapsed(&self, profile: &Profile, day: u8, part: u8) -> Option<Duration> {
+        let started = self.get(profile, day, "started")?;
+        let finished = self.get(profile, day, &format!("part_{}", part))?;
+
+        (finished - started).to_std().ok()
+    }
+
+    ///
+    /// # started
+    /// Returns the start of a day.
+    ///
+    /// ## Arguments
+    /// * `profile` - The profile solving the day
+    /// * `day` - The day of the Advent of Code challenge
+    ///
+    /// ## Returns
+    /// * `Option<DateTime<FixedOffset>>` - The start, `None` if the day is not started
+    pub fn started(&self, profile: &Profile, day: u8) -> Option<DateTime<FixedOffset>> {
+        self.get(profile, day, "started")
+    }
+
+    ///
+    /// # save
+    /// Writes the timings to the timings file.
+    ///
+    /// ## Returns
+    /// * `Result<(), String>` - An error if the file could not be written
+    pub fn save(&self) -> Result<(), String> {
+        save(&self.doc, &self.path)
+    }
+
+    ///
+    /// # get
+    /// Returns a time of a day.
+    ///
+    /// ## Arguments
+    /// * `profile` - The profile solving the day
+    /// * `day` - The day of the Advent of Code challenge
+    /// * `key` - `started`, `part_1` or `part_2`
+    ///
+    /// ## Returns
+    /// * `Option<DateTime<FixedOffset>>` - The time, `None` if it is not recorded
+    fn get(&self, profile: &Profile, day: u8, key: &str) -> Option<DateTime<FixedOffset>> {
+        let time = self
+            .doc
+            .get(profile.name())?
+            .get(day_key(day))?
+            .get(key)?
+            .as_datetime()?;
+
+        DateTime::parse_from_rfc3339(&time.to_string()).ok()
+    }
+
+    ///
+    /// # record
+    /// Records a time of a day, unless it is already recorded.
+    ///
+    /// ## Arguments
+    /// * `profile` - The profile solving the day
+    /// * `day` - The day of the Advent of Code challenge
+    /// * `key` - `started`, `part_1` or `part_2`
+    /// * `at` - The time to record
+    ///
+    /// ## Returns
+    /// * `bool` - Whether the time has been recorded
+    fn record(
+        &mut self,
+        profile: &Profile,
+        day: u8,
+        key: &str,
+        at: &DateTime<FixedOffset>,
+    ) -> Result<bool, String> {
+        if self.get(profile, day, key).is_some() {
+            return Ok(false);
+        }
+
+        let Ok(at) = at
+            .to_rfc3339_opts(SecondsFormat::Secs, false)
+            .parse::<Datetime>()
+        else {
+            return Ok(false);
+        };
+        day_table(&mut self.doc, &self.path, profile, day)?[key] = value(at);
+
+        Ok(true)
+    }
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_timings() {
+    use crate::calendar::at;
+
+    let mut timings = Timings {
+        doc: DocumentMut::new(),
+        path: PathBuf::new(),
+    };
+    let profile = Profile::default();
+
+    assert!(timings.start(&profile, 1, &at(2023, 12, 1, 0)).unwrap());
+    assert!(!timings.start(&profile, 1, &at(2023, 12, 1, 5)).unwrap());
+    assert!(timings.finish(&profile, 1, 1, &at(2023, 12, 1, 1)).unwrap());
+    assert!(timings.finish(&profile, 1, 2, &at(2023, 12, 2, 3)).unwrap());
+
+    assert_eq!(
+        timings.elapsed(&profile, 1, 1),
+        Some(Duration::from_secs(3600))
+    );
+    assert_eq!(
+        timings.elapsed(&profile, 1, 2),
+        Some(Duration::from_secs(27 * 3600))
+    );
+    assert_eq!(timings.elapsed(&profile, 2, 1), None);
+    assert_eq!(
+        timings.doc.to_string(),
+        "[default.day_01]\nstarted = 2023-12-01T00:00:00-05:00\n\
+         part_1 = 2023-12-01T01:00:00-05:00\npart_2 = 2023-12-02T03:00:00-05:00\n"
+    );
+}
+
+/*
+ * End of file src/timing.rs
+ */
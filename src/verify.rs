@@ -0,0 +1,353 @@
+///
+/// # src/verify.rs
+/// Contains the verification of the answers of the solutions against the accepted ones, stored in
+/// the answers file of each year, used by `aoc verify`.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use serde_json::{json, Value};
+
+use std::{fmt, time::Duration};
+
+use crate::junit::{CaseResult, TestCase};
+use crate::output::{nanoseconds, outcome};
+use crate::profile::Profile;
+use crate::runner::{Outcome, PartRun};
+
+// Variables  =========================================================================== Variables
+/// The result of the verification of a part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The answer is the accepted one
+    Ok,
+    /// The answer differs from the accepted one
+    Wrong,
+    /// There is no accepted answer to compare with
+    Unverified,
+    /// The part failed, timed out or ran out of memory
+    Failed(Outcome),
+    /// The solution could not be compiled or run
+    Error,
+}
+
+/// The verification of a part, or of a whole day when its solution could not be compiled.
+pub struct Verification {
+    /// The year of the Advent of Code challenge
+    pub year: u16,
+    /// The day of the Advent of Code challenge
+    pub day: u8,
+    /// The part, `None` when the solution could not be compiled
+    pub part: Option<u8>,
+    /// The profile whose input and answers are used
+    pub profile: Profile,
+    /// The result of the verification
+    pub status: Status,
+    /// The answer printed by the part
+    pub answer: Option<String>,
+    /// The accepted answer
+    pub expected: Option<String>,
+    /// The wall-clock time of the part, `None` if it did not run
+    pub duration: Option<Duration>,
+    /// The output of a failed part, or the error
+    pub message: String,
+}
+
+// Functions  =========================================================================== Functions
+impl Verification {
+    ///
+    /// # part
+    /// Verifies the execution of a part against the accepted answer.
+    ///
+    /// ## Arguments
+    /// * `year` - The year of the Advent of Code challenge
+    /// * `day` - The day of the Advent of Code challenge
+    /// * `part` - The part, 1 or 2
+    /// * `profile` - The profile whose input was used
+    /// * `run` - The execution of the part, an error if it could not start
+    /// * `expected` - The accepted answer, `None` if there is none yet
+    ///
+    /// ## Returns
+    /// * `Verification` - The verification
+    pub fn part(
+        year: u16,
+        day: u8,
+        part: u8,
+        profile: &Profile,
+        run: Result<PartRun, String>,
+        expected: Option<String>,
+    ) -> Verification {
+        let mut verification = Verification::error(year, day, Some(part), profile, String::new());
+        verification.expected = expected;
+
+        let run = match run {
+            Ok(run) => run,
+            Err(e) => {
+                verification.message = e;
+                return verification;
+            }
+        };
+
+        verification.status = match (run.outcome, &run.answer, &verification.expected) {
+            (Outcome::Success, Some(answer), Some(expected)) if answer == expected => Status::Ok,
+            (Outcome::Success, _, Some(_)) => Status::Wrong,
+            (Outcome::Success, _, None) => Status::Unverified,
+            (outcome, _, _) => Status::Failed(outcome),
+        };
+        if let Status::Failed(_) = verification.status {
+            verification.message = run.output;
+        }
+        verification.answer = run.answer;
+        verification.duration = Some(run.duration);
+
+        verification
+    }
+
+    ///
+    /// # error
+    /// Returns the verification of a part or a day that could not run.
+    ///
+    /// ## Arguments
+    /// * `year` - The year of the Advent of Code challenge
+    /// * `day` - The day of the Advent of Code challenge
+    /// * `part` - The part, `None` for the whole day
+    /// * `profile` - The profile whose input was used
+    /// * `message` - The error
+    ///
+    /// ## Returns
+    /// * `Verification` - The verification
+    pub fn error(
+        year: u16,
+        day: u8,
+        part: Option<u8>,
+        profile: &Profile,
+        message: String,
+    ) -> Verification {
+        Verification {
+            year,
+            day,
+            part,
+            profile: profile.clone(),
+            status: Status::Error,
+            answer: None,
+            expected: None,
+            duration: None,
+            message,
+        }
+    }
+
+    ///
+    /// # is_failure
+    /// Returns whether the verification failed, an unverified answer being no failure.
+    ///
+    /// ## Returns
+    /// * `bool` - Whether it failed
+    pub fn is_failure(&self) -> bool {
+        !matches!(self.status, Status::Ok | Status::Unverified)
+    }
+
+    ///
+    /// # label
+    /// Returns the label of the verification, e.g. `2023 day 01 part 2 [default]`.
+    ///
+    /// ## Returns
+    /// * `String` - The label
+    pub fn label(&self) -> String {
+        match self.part {
+            Some(part) => format!(
+                "{} day {:02} part {} [{}]",
+                self.year, self.day, part, self.profile
+            ),
+            None => format!("{} day {:02} [{}]", self.year, self.day, self.profile),
+        }
+    }
+
+    ///
+    /// # to_test_case
+    /// Returns the test case of the verification in a JUnit report, see src/junit.rs.
+    ///
+    /// ## Returns
+    /// * `TestCase` - The test case
+    pub fn to_test_case(&self) -> TestCase {
+        let answer = self.answer.clone().unwrap_or_default();
+        let result = match self.status {
+            Status::Ok => CaseResult::Passed,
+            Status::Wrong => CaseResult::Failure(
+                "wrong".to_string(),
+                format!(
+                    "wrong, got {}, expected {}",
+                    answer,
+                    self.expected.as_deref().unwrap_or_default()
+                ),
+                format!(
+                    "expected: {}\nactual: {}",
+                    self.expected.as_deref().unwrap_or_default(),
+                    answer
+                ),
+            ),
+            Status::Unverified => {
+                CaseResult::Skipped(format!("no accepted answer yet, got {}", answer))
+            }
+            Status::Failed(failure) => {
+                let kind = match failure {
+                    Outcome::TimedOut(_) => "timed_out",
+                    Outcome::OutOfMemory(_) => "out_of_memory",
+                    _ => "failed",
+                };
+
+                CaseResult::Failure(kind.to_string(), failure.to_string(), self.message.clone())
+            }
+            Status::Error => CaseResult::Error(
+                self.message.lines().next().unwrap_or_default().to_string(),
+                self.message.clone(),
+            ),
+        };
+
+        TestCase {
+            class: format!("{}.day_{:02}", self.year, self.day),
+            name: match self.part {
+                Some(part) => format!("part {} [{}]", part, self.profile),
+                None => format!("build [{}]", self.profile),
+            },
+            time: self.duration,
+            result,
+        }
+    }
+
+    ///
+    /// # to_json
+    /// Returns the JSON object of the verification, see src/output.rs.
+    ///
+    /// ## Returns
+    /// * `Value` - The object
+    pub fn to_json(&self) -> Value {
+        let status = match self.status {
+            Status::Ok => "ok",
+            Status::Wrong => "wrong",
+            Status::Unverified => "unverified",
+            Status::Failed(_) => "failed",
+            Status::Error => "error",
+        };
+
+        json!({
+            "year": self.year,
+            "day": self.day,
+            "part": self.part,
+            "profile": self.profile.name(),
+            "status": status,
+            "outcome": match self.status {
+                Status::Failed(failure) => Some(outcome(&failure)),
+                _ => None,
+            },
+            "answer": self.answer,
+            "expected": self.expected,
+            "duration_ns": self.duration.map(nanoseconds),
+            "message": self.message,
+        })
+    }
+}
+
+impl fmt::Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let answer = self.answer.as_deref().unwrap_or_default();
+
+        match self.status {
+            Status::Ok => write!(
+                f,
+                "{}: ok {} ({:.2?})",
+                self.label(),
+                answer,
+                self.duration.unwrap_or_default()
+            ),
+            Status::Wrong => write!(
+                f,
+                "{}: wrong, got {}, expected {}",
+                self.label(),
+                answer,
+                self.expected.as_deref().unwrap_or_default()
+            ),
+            Status::Unverified => {
+                write!(
+                    f,
+                    "{}: no accepted answer yet, got {}",
+                    self.label(),
+                    answer
+                )
+            }
+            Status::Failed(Outcome::Failed) => {
+                write!(f, "{}: failed\n{}", self.label(), self.message)
+            }
+            Status::Failed(outcome) => write!(f, "{}: {}", self.label(), outcome),
+            Status::Error => write!(f, "{}: {}", self.label(), self.message),
+        }
+    }
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_verification() {
+    let profile = Profile::default();
+    let run = |answer: &str, outcome| {
+        Ok(PartRun {
+            answer: Some(answer.to_string()),
+            output: format!("{}\n", answer),
+            duration: Duration::from_millis(3),
+            outcome,
+            peak_memory: None,
+            allocations: None,
+        })
+    };
+    let expected = || Some("42".to_string());
+
+    let ok = Verification::part(
+        2023,
+        1,
+        1,
+        &profile,
+        run("42", Outcome::Success),
+        expected(),
+    );
+    assert_eq!(ok.status, Status::Ok);
+    assert_eq!(
+        ok.to_string(),
+        "2023 day 01 part 1 [default]: ok 42 (3.00ms)"
+    );
+
+    let wrong = Verification::part(
+        2023,
+        1,
+        2,
+        &profile,
+        run("41", Outcome::Success),
+        expected(),
+    );
+    assert!(wrong.is_failure());
+    assert_eq!(
+        wrong.to_string(),
+        "2023 day 01 part 2 [default]: wrong, got 41, expected 42"
+    );
+
+    let unverified = Verification::part(2023, 2, 1, &profile, run("7", Outcome::Success), None);
+    assert!(!unverified.is_failure());
+
+    let timeout = Outcome::TimedOut(Duration::from_secs(1));
+    let timed_out = Verification::part(2023, 2, 2, &profile, run("42", timeout), expected());
+    assert_eq!(timed_out.status, Status::Failed(timeout));
+    assert_eq!(timed_out.to_json()["outcome"]["status"], "timed_out");
+    assert_eq!(
+        wrong.to_test_case().result,
+        CaseResult::Failure(
+            "wrong".to_string(),
+            "wrong, got 41, expected 42".to_string(),
+            "expected: 42\nactual: 41".to_string()
+        )
+    );
+
+    let error = Verification::error(2023, 3, None, &profile, "no solution".to_string());
+    assert_eq!(error.to_string(), "2023 day 03 [default]: no solution");
+}
+
+/*
+ * End of file src/verify.rs
+ */
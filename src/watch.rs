@@ -0,0 +1,371 @@
+///
+/// # src/watch.rs
+/// Contains the watch mode: the files of some days are polled, and every change triggers a new
+/// run of the days concerned.
+///
+/// The watched files are the solution of each day, its input for the selected profile, its
+/// examples and the shared helper modules anywhere under `src/` (e.g. `src/point.rs` or
+/// `src/utils/grid.rs`). `src/main.rs` is not watched, as it is generated by the runs themselves,
+/// nor `src/bin/`, which holds the solutions of the other days.
+///
+/// ## Author
+/// Tom Planche <github.com/tomPlanche>
+///
+// Imports  ==============================================================================  Imports
+use std::{
+    collections::BTreeMap,
+    fs::{metadata, read_dir},
+    path::{Path, PathBuf},
+    thread::sleep,
+    time::{Duration, SystemTime},
+};
+
+use crate::layout::Layout;
+use crate::profile::Profile;
+
+// Variables  =========================================================================== Variables
+/// How often the files are polled.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The modification time and size of every watched file.
+type Snapshot = BTreeMap<PathBuf, (SystemTime, u64)>;
+
+// Functions  =========================================================================== Functions
+///
+/// # watch
+/// Runs `run` once for every day, then again for the days whose files changed, every day when a
+/// shared module changed. Never returns.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `layout` - The layout of the project
+/// * `profile` - The profile whose inputs are watched
+/// * `days` - The watched days, as `(year, day)`
+/// * `run` - The run, given the days to run and the changed files, relative to the project root
+///
+/// ## Returns
+/// * `()` - Nothing
+pub fn watch(
+    caller: &Path,
+    layout: &Layout,
+    profile: &Profile,
+    days: &[(u16, u8)],
+    mut run: impl FnMut(&[(u16, u8)], &[PathBuf]),
+) {
+    let mut previous = snapshot(&watched_files(caller, layout, profile, days));
+    run(days, &[]);
+
+    loop {
+        sleep(POLL_INTERVAL);
+
+        let current = snapshot(&watched_files(caller, layout, profile, days));
+        let changed = changes(&previous, &current);
+        if changed.is_empty() {
+            continue;
+        }
+
+        // Editors often write a file in several steps, wait for the last one
+        sleep(POLL_INTERVAL);
+        previous = snapshot(&watched_files(caller, layout, profile, days));
+
+        let affected = affected_days(caller, layout, profile, days, &changed);
+        let changed = changed
+            .iter()
+            .map(|path| path.strip_prefix(caller).unwrap_or(path).to_path_buf())
+            .collect::<Vec<_>>();
+        run(&affected, &changed);
+    }
+}
+
+///
+/// # watched_files
+/// Returns the files watched for some days: their own files and the shared modules.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `layout` - The layout of the project
+/// * `profile` - The profile whose inputs are watched
+/// * `days` - The watched days, as `(year, day)`
+///
+/// ## Returns
+/// * `Vec<PathBuf>` - The files, existing or not
+fn watched_files(
+    caller: &Path,
+    layout: &Layout,
+    profile: &Profile,
+    days: &[(u16, u8)],
+) -> Vec<PathBuf> {
+    let mut files = days
+        .iter()
+        .flat_map(|(year, day)| day_files(caller, layout, profile, *year, *day))
+        .collect::<Vec<_>>();
+
+    let src = caller.join("src");
+    let skipped = [src.join("main.rs"), src.join("bin")];
+    shared_modules(&src, &skipped, &mut files);
+
+    files
+}
+
+///
+/// # shared_modules
+/// Adds the Rust files of a folder and of its subfolders, recursively, to a list.
+///
+/// ## Arguments
+/// * `folder` - The folder to walk
+/// * `skipped` - The files and folders left out
+/// * `files` - The list the files are added to
+///
+/// ## Returns
+/// * `()` - Nothing
+fn shared_modules(folder: &Path, skipped: &[PathBuf], files: &mut Vec<PathBuf>) {
+    let Ok(entries) = read_dir(folder) else {
+        return;
+    };
+
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if skipped.contains(&path) {
+            continue;
+        }
+
+        if path.is_dir() {
+            shared_modules(&path, skipped, files);
+        } else if path.extension().is_some_and(|extension| extension == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+///
+/// # day_files
+/// Returns the own files of a day: its solution, the input of the profile and its examples.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `layout` - The layout of the project
+/// * `profile` - The profile whose input is watched
+/// * `year` - The year of the Advent of Code challenge
+/// * `day` - The day of the Advent of Code challenge
+///
+/// ## Returns
+/// * `Vec<PathBuf>` - The files, existing or not
+fn day_files(
+    caller: &Path,
+    layout: &Layout,
+    profile: &Profile,
+    year: u16,
+    day: u8,
+) -> Vec<PathBuf> {
+    let mut files = vec![
+        caller.join(layout.solution(year, day)),
+        caller.join(profile.input(layout, year, day)),
+    ];
+
+    if let Ok(examples) = read_dir(caller.join(layout.examples(year, day))) {
+        files.extend(examples.flatten().map(|entry| entry.path()));
+    }
+
+    files
+}
+
+///
+/// # affected_days
+/// Returns the days to run again after some files changed: every day if a shared module changed,
+/// the days owning the changed files otherwise.
+///
+/// ## Arguments
+/// * `caller` - The folder from which the program was called
+/// * `layout` - The layout of the project
+/// * `profile` - The profile whose inputs are watched
+/// * `days` - The watched days, as `(year, day)`
+/// * `changed` - The changed files
+///
+/// ## Returns
+/// * `Vec<(u16, u8)>` - The days to run, in the order they are watched
+fn affected_days(
+    caller: &Path,
+    layout: &Layout,
+    profile: &Profile,
+    days: &[(u16, u8)],
+    changed: &[PathBuf],
+) -> Vec<(u16, u8)> {
+    let files = days
+        .iter()
+        .map(|(year, day)| day_files(caller, layout, profile, *year, *day))
+        .collect::<Vec<_>>();
+    // Examples are listed from their folder, so a deleted one is matched by its folder
+    let owns = |(year, day): &(u16, u8), own: &[PathBuf], path: &PathBuf| {
+        own.contains(path) || path.starts_with(caller.join(layout.examples(*year, *day)))
+    };
+
+    let shared = changed.iter().any(|path| {
+        !days
+            .iter()
+            .zip(&files)
+            .any(|(day, own)| owns(day, own, path))
+    });
+    if shared {
+        return days.to_vec();
+    }
+
+    days.iter()
+        .zip(&files)
+        .filter(|(day, own)| changed.iter().any(|path| owns(day, own, path)))
+        .map(|(day, _)| *day)
+        .collect()
+}
+
+///
+/// # snapshot
+/// Reads the modification time and size of files.
+///
+/// ## Arguments
+/// * `files` - The files, the missing ones are left out
+///
+/// ## Returns
+/// * `Snapshot` - The modification time and size of every existing file
+fn snapshot(files: &[PathBuf]) -> Snapshot {
+    files
+        .iter()
+        .filter_map(|file| {
+            let metadata = metadata(file).ok()?;
+            Some((file.clone(), (metadata.modified().ok()?, metadata.len())))
+        })
+        .collect()
+}
+
+///
+/// # changes
+/// Returns the files created, modified or deleted between two snapshots.
+///
+/// ## Arguments
+/// * `before` - The previous snapshot
+/// * `after` - The current snapshot
+///
+/// ## Returns
+/// * `Vec<PathBuf>` - The changed files
+fn changes(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut changed = after
+        .iter()
+        .filter(|(path, state)| before.get(*path) != Some(state))
+        .map(|(path, _)| path.clone())
+        .collect::<Vec<_>>();
+    changed.extend(
+        before
+            .keys()
+            .filter(|path| !after.contains_key(*path))
+            .cloned(),
+    );
+
+    changed
+}
+
+// Tests ==================================================================================== Tests
+#[test]
+fn test_changes() {
+    let at = |seconds| SystemTime::UNIX_EPOCH + Duration::from_secs(seconds);
+    let before = Snapshot::from([
+        (PathBuf::from("a.rs"), (at(1), 10)),
+        (PathBuf::from("b.rs"), (at(1), 10)),
+        (PathBuf::from("c.rs"), (at(1), 10)),
+    ]);
+    let after = Snapshot::from([
+        (PathBuf::from("a.rs"), (at(1), 10)),
+        (PathBuf::from("b.rs"), (at(2), 12)),
+        (PathBuf::from("d.rs"), (at(2), 1)),
+    ]);
+
+    assert_eq!(
+        changes(&before, &after),
+        [
+            PathBuf::from("b.rs"),
+            PathBuf::from("d.rs"),
+            PathBuf::from("c.rs")
+        ]
+    );
+    assert!(changes(&after, &after).is_empty());
+}
+
+#[test]
+fn test_affected_days() {
+    let layout = Layout::default();
+    let caller = Path::new("/project");
+    let profile = Profile::default();
+    let days = [(2023, 1), (2023, 2)];
+
+    assert_eq!(
+        affected_days(
+            caller,
+            &layout,
+            &profile,
+            &days,
+            &[caller.join(layout.solution(2023, 2))]
+        ),
+        [(2023, 2)]
+    );
+    assert_eq!(
+        affected_days(
+            caller,
+            &layout,
+            &profile,
+            &days,
+            &[caller.join(layout.examples(2023, 1)).join("1.txt")]
+        ),
+        [(2023, 1)]
+    );
+    assert_eq!(
+        affected_days(
+            caller,
+            &layout,
+            &profile,
+            &days,
+            &[caller.join("src/point.rs")]
+        ),
+        days
+    );
+
+    // The input of a named profile belongs to its day
+    let partner = "partner".parse::<Profile>().unwrap();
+    assert_eq!(
+        affected_days(
+            caller,
+            &layout,
+            &partner,
+            &days,
+            &[caller.join("data/year_2023/inputs/partner/day_02.txt")]
+        ),
+        [(2023, 2)]
+    );
+}
+
+#[test]
+fn test_watched_files() {
+    let caller = crate::file_utils::temp_folder("watch");
+    let layout = Layout::default();
+    for file in [
+        "src/main.rs",
+        "src/point.rs",
+        "src/utils/grid.rs",
+        "src/utils/README.md",
+        "src/bin/year_2023/day_02.rs",
+    ] {
+        crate::file_utils::create_folder(caller.join(file).parent().unwrap());
+        std::fs::write(caller.join(file), "").unwrap();
+    }
+
+    let profile = Profile::default();
+    let mut files = watched_files(&caller, &layout, &profile, &[(2023, 1)]);
+    files.sort();
+    let mut expected = day_files(&caller, &layout, &profile, 2023, 1);
+    expected.extend([
+        caller.join("src/point.rs"),
+        caller.join("src/utils/grid.rs"),
+    ]);
+    expected.sort();
+
+    assert_eq!(files, expected);
+}
+
+/*
+ * End of file src/watch.rs
+ */